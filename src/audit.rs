@@ -0,0 +1,97 @@
+//! Append-only audit log of task mutations.
+//!
+//! When `AUDIT_LOG_PATH` is set, every successful create/update/delete/tag change is
+//! appended to that file as one JSON object per line (JSONL). Writing is best-effort:
+//! failures are logged and never affect the request.
+//!
+//! Changes are recorded through a trail: while `collect` runs a request, every change
+//! the repository's store makes is noted, and the middleware writes one line per change
+//! afterwards.
+
+use crate::models::repository::ActivityType;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::io::Write;
+use uuid::Uuid;
+
+tokio::task_local! {
+    static TRAIL: RefCell<Vec<(AuditOp, Uuid)>>;
+}
+
+/// Kind of mutation recorded in the audit log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOp {
+    Create,
+    Update,
+    Delete,
+    Tags,
+}
+
+impl From<ActivityType> for AuditOp {
+    fn from(kind: ActivityType) -> Self {
+        match kind {
+            ActivityType::Created => AuditOp::Create,
+            ActivityType::Updated => AuditOp::Update,
+            ActivityType::Deleted => AuditOp::Delete,
+            ActivityType::Tagged => AuditOp::Tags,
+        }
+    }
+}
+
+/// One line of the audit log.
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub operation: AuditOp,
+    pub task_id: Uuid,
+    /// Value of the request's `X-Actor` header, if any.
+    pub actor: Option<String>,
+}
+
+impl AuditEntry {
+    /// Entry for `operation` on `task_id`, stamped now.
+    pub fn new(operation: AuditOp, task_id: Uuid, actor: Option<String>) -> Self {
+        AuditEntry {
+            timestamp: Utc::now(),
+            operation,
+            task_id,
+            actor,
+        }
+    }
+}
+
+/// Append `entries` to the JSONL file at `path` in one write, creating it if needed.
+/// Blocking; errors are logged and swallowed.
+pub fn append(path: &str, entries: &[AuditEntry]) {
+    let result = entries
+        .iter()
+        .map(|e| serde_json::to_string(e).map(|line| line + "\n"))
+        .collect::<Result<String, _>>()
+        .map_err(std::io::Error::other)
+        .and_then(|lines| {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            file.write_all(lines.as_bytes())
+        });
+    if let Err(e) = result {
+        log::warn!("failed to write audit log {}: {}", path, e);
+    }
+}
+
+/// Run `fut`, returning its output with every change noted while it ran, in order.
+pub async fn collect<F: Future>(fut: F) -> (F::Output, Vec<(AuditOp, Uuid)>) {
+    TRAIL
+        .scope(RefCell::new(Vec::new()), async {
+            let out = fut.await;
+            (out, TRAIL.with(|t| t.take()))
+        })
+        .await
+}
+
+/// Note a change to `task_id` for the surrounding `collect`; a no-op outside one.
+pub fn note(op: AuditOp, task_id: Uuid) {
+    let _ = TRAIL.try_with(|t| t.borrow_mut().push((op, task_id)));
+}
@@ -0,0 +1,433 @@
+//! Runtime configuration read from environment variables at startup.
+//!
+//! The config is stored on the `TaskRepository` (the router state) so handlers can
+//! read it, and tests can build a repository with any config without touching env vars.
+
+use crate::models::task::{Priority, Task};
+use crate::utils::text::validate_title_template;
+use chrono::{DateTime, Duration, Utc};
+use std::env;
+
+/// Default for `AppConfig::max_bulk_items`.
+pub const DEFAULT_MAX_BULK_ITEMS: usize = 10_000;
+
+/// Default for `AppConfig::bulk_max_bytes` (1 MB).
+pub const DEFAULT_BULK_MAX_BYTES: usize = 1024 * 1024;
+
+/// Default for `AppConfig::max_tag_len`.
+pub const DEFAULT_MAX_TAG_LEN: usize = 64;
+
+/// Default for `AppConfig::max_page`.
+pub const DEFAULT_MAX_PAGE: usize = 10_000;
+
+/// Default for `AppConfig::stats_workers`.
+pub const DEFAULT_STATS_WORKERS: usize = 4;
+
+/// Default for `AppConfig::request_timeout_ms` (30 s).
+pub const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 30_000;
+
+/// Default for `AppConfig::fuzzy_threshold`.
+pub const DEFAULT_FUZZY_THRESHOLD: f64 = 0.3;
+
+/// Default for `AppConfig::rate_limit_window_secs`.
+pub const DEFAULT_RATE_LIMIT_WINDOW_SECS: u64 = 60;
+
+/// Default for `AppConfig::log_body_max_bytes`.
+pub const DEFAULT_LOG_BODY_MAX_BYTES: usize = 4096;
+
+/// Application settings. `Default` gives the built-in behavior: every option unset and
+/// the documented default limits.
+#[derive(Clone, Debug)]
+pub struct AppConfig {
+    /// Maximum number of tasks emitted by `GET /tasks/export` (`EXPORT_MAX`).
+    pub export_max: Option<usize>,
+    /// Reject imports without a `Content-Type` (415) instead of treating them as JSON
+    /// (`IMPORT_STRICT_CONTENT_TYPE`).
+    pub import_strict_content_type: bool,
+    /// Path of a JSON file of tasks loaded into the repository at startup (`SEED_TASKS`).
+    pub seed_tasks: Option<String>,
+    /// JSON snapshot file loaded at startup and rewritten by `POST /tasks/compact`
+    /// (`DATA_FILE`). Unset keeps the repository purely in memory.
+    pub data_file: Option<String>,
+    /// Template applied to titles of newly created tasks, e.g. `[{date}] {title}`
+    /// (`TITLE_TEMPLATE`). Invalid templates are logged and ignored.
+    pub title_template: Option<String>,
+    /// How tags are normalized before storing and matching (`TAG_NORMALIZATION`).
+    pub tag_normalization: TagNormalization,
+    /// Maximum number of elements accepted in one bulk request body (`MAX_BULK_ITEMS`).
+    pub max_bulk_items: usize,
+    /// Honor the `X-Now` header in time-based read filters (`ALLOW_NOW_OVERRIDE`).
+    /// Development/QA only.
+    pub allow_now_override: bool,
+    /// Serve reads but reject every mutating request with 503 (`READ_ONLY`).
+    pub read_only: bool,
+    /// Minimum description length (chars, trimmed) for critical tasks; 0 disables the
+    /// rule (`CRITICAL_MIN_DESC`).
+    pub critical_min_desc: usize,
+    /// Maximum length of a single tag in bytes (`MAX_TAG_LEN`).
+    pub max_tag_len: usize,
+    /// JSONL file that successful task mutations are appended to (`AUDIT_LOG_PATH`).
+    pub audit_log_path: Option<String>,
+    /// Enable the `DELETE /tasks/all` admin endpoint (`ALLOW_DELETE_ALL`).
+    pub allow_delete_all: bool,
+    /// Maximum age per priority before an open task breaches its SLA (`SLA_MAX_AGE`).
+    pub sla: SlaPolicy,
+    /// Request body limit for the bulk JSON-array routes (`BULK_MAX_BYTES`).
+    pub bulk_max_bytes: usize,
+    /// Highest `page` accepted by `GET /tasks`; deeper pages get 400 (`MAX_PAGE`).
+    pub max_page: usize,
+    /// Prefix every route is served under, e.g. `/api/v1` (`BASE_PATH`). Empty serves
+    /// routes at the root.
+    pub base_path: String,
+    /// Serve `/health` under `base_path` too instead of at the root (`NEST_HEALTH`).
+    pub nest_health: bool,
+    /// How many heavy aggregations (stats, duplicates, tag co-occurrence) may run on
+    /// blocking threads at once (`STATS_WORKERS`); further requests wait for a slot.
+    pub stats_workers: usize,
+    /// Reject (429) a `POST /tasks` whose normalized title was created within this many
+    /// milliseconds (`CREATE_DEBOUNCE_MS`). Unset disables the check.
+    pub create_debounce_ms: Option<u64>,
+    /// Lowest trigram similarity (0.0-1.0) a task needs to appear in
+    /// `GET /tasks/search?fuzzy=true` results (`FUZZY_THRESHOLD`).
+    pub fuzzy_threshold: f64,
+    /// Requests whose handler takes longer than this many milliseconds get 408
+    /// (`REQUEST_TIMEOUT_MS`). `0` disables the timeout.
+    pub request_timeout_ms: u64,
+    /// Requests each client may make per rate-limit window (`RATE_LIMIT`). Unset
+    /// disables rate limiting.
+    pub rate_limit: Option<u32>,
+    /// Length of the rate-limit window in seconds (`RATE_LIMIT_WINDOW_SECS`).
+    pub rate_limit_window_secs: u64,
+    /// Peer addresses of reverse proxies whose `X-Forwarded-For` is believed when keying
+    /// rate limits (`TRUSTED_PROXIES`, comma-separated). Empty ignores the header.
+    pub trusted_proxies: Vec<std::net::IpAddr>,
+    /// Description given to created and imported tasks whose description is blank
+    /// (`DEFAULT_DESCRIPTION`). Empty leaves blank descriptions alone.
+    pub default_description: String,
+    /// Priority given to created and imported tasks that do not specify one
+    /// (`DEFAULT_PRIORITY`). Invalid values fall back to `medium`.
+    pub default_priority: Priority,
+    /// Reject completing a task while any of its subtasks is open
+    /// (`STRICT_SUBTASK_COMPLETION`).
+    pub strict_subtask_completion: bool,
+    /// Log request and response bodies of mutating requests at debug level (`LOG_BODIES`).
+    pub log_bodies: bool,
+    /// Logged bodies are cut to this many bytes (`LOG_BODY_MAX_BYTES`).
+    pub log_body_max_bytes: usize,
+    /// JSON field names whose values are replaced with `[REDACTED]` in logged bodies,
+    /// at any depth (`LOG_REDACT_FIELDS`, comma-separated).
+    pub log_redact_fields: Vec<String>,
+    /// Longest description (in chars) the repository will store, whatever the entry
+    /// point (`REPO_MAX_DESCRIPTION`). Unset means unbounded.
+    pub repo_max_description: Option<usize>,
+    /// What the repository does with descriptions over `repo_max_description`
+    /// (`DESCRIPTION_OVERFLOW`).
+    pub description_overflow: OverflowPolicy,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            export_max: None,
+            import_strict_content_type: false,
+            seed_tasks: None,
+            data_file: None,
+            title_template: None,
+            tag_normalization: TagNormalization::default(),
+            max_bulk_items: DEFAULT_MAX_BULK_ITEMS,
+            allow_now_override: false,
+            read_only: false,
+            critical_min_desc: 0,
+            max_tag_len: DEFAULT_MAX_TAG_LEN,
+            audit_log_path: None,
+            allow_delete_all: false,
+            sla: SlaPolicy::default(),
+            bulk_max_bytes: DEFAULT_BULK_MAX_BYTES,
+            max_page: DEFAULT_MAX_PAGE,
+            base_path: String::new(),
+            nest_health: false,
+            stats_workers: DEFAULT_STATS_WORKERS,
+            create_debounce_ms: None,
+            fuzzy_threshold: DEFAULT_FUZZY_THRESHOLD,
+            request_timeout_ms: DEFAULT_REQUEST_TIMEOUT_MS,
+            rate_limit: None,
+            rate_limit_window_secs: DEFAULT_RATE_LIMIT_WINDOW_SECS,
+            trusted_proxies: Vec::new(),
+            default_description: String::new(),
+            default_priority: Priority::default(),
+            strict_subtask_completion: false,
+            log_bodies: false,
+            log_body_max_bytes: DEFAULT_LOG_BODY_MAX_BYTES,
+            log_redact_fields: Vec::new(),
+            repo_max_description: None,
+            description_overflow: OverflowPolicy::default(),
+        }
+    }
+}
+
+/// Tag normalization mode. Every mode trims and lowercases.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TagNormalization {
+    /// Trim and lowercase only: `"Front End"` -> `"front end"`.
+    #[default]
+    Lowercase,
+    /// Also join words with single hyphens: `"Front  End"` -> `"front-end"`.
+    Kebab,
+}
+
+impl TagNormalization {
+    /// Normalize one tag according to this mode.
+    pub fn apply(&self, tag: &str) -> String {
+        let lower = tag.trim().to_lowercase();
+        match self {
+            TagNormalization::Lowercase => lower,
+            TagNormalization::Kebab => lower
+                .split(|c: char| c.is_whitespace() || c == '-')
+                .filter(|w| !w.is_empty())
+                .collect::<Vec<_>>()
+                .join("-"),
+        }
+    }
+}
+
+impl std::str::FromStr for TagNormalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "lowercase" => Ok(TagNormalization::Lowercase),
+            "kebab" => Ok(TagNormalization::Kebab),
+            _ => Err(format!("invalid tag normalization: '{}'", s)),
+        }
+    }
+}
+
+/// How an over-length value is handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Refuse the write with an error.
+    #[default]
+    Reject,
+    /// Clip the value to the limit and store it.
+    Truncate,
+}
+
+impl std::str::FromStr for OverflowPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "reject" => Ok(OverflowPolicy::Reject),
+            "truncate" => Ok(OverflowPolicy::Truncate),
+            _ => Err(format!("invalid overflow policy: '{}'", s)),
+        }
+    }
+}
+
+/// Maximum age of an incomplete task per priority; priorities without a limit never
+/// breach. Parsed from e.g. `critical=1d,high=3d,medium=12h` (units `h` or `d`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlaPolicy {
+    pub critical: Option<Duration>,
+    pub high: Option<Duration>,
+    pub medium: Option<Duration>,
+    pub low: Option<Duration>,
+}
+
+impl Default for SlaPolicy {
+    /// Critical tasks get one day and high-priority tasks three; others have no limit.
+    fn default() -> Self {
+        SlaPolicy {
+            critical: Some(Duration::days(1)),
+            high: Some(Duration::days(3)),
+            medium: None,
+            low: None,
+        }
+    }
+}
+
+impl SlaPolicy {
+    /// Allowed age for tasks of `priority`, if limited.
+    pub fn max_age(&self, priority: &Priority) -> Option<Duration> {
+        match priority {
+            Priority::Critical => self.critical,
+            Priority::High => self.high,
+            Priority::Medium => self.medium,
+            Priority::Low => self.low,
+        }
+    }
+
+    /// True if `task` is still open and older than its priority allows at `now`.
+    pub fn is_breached(&self, task: &Task, now: DateTime<Utc>) -> bool {
+        !task.completed
+            && self
+                .max_age(&task.priority)
+                .is_some_and(|max| now - task.created_at > max)
+    }
+}
+
+impl std::str::FromStr for SlaPolicy {
+    type Err = String;
+
+    /// Only the listed priorities get a limit; the rest are unlimited.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut policy = SlaPolicy {
+            critical: None,
+            high: None,
+            medium: None,
+            low: None,
+        };
+        for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (priority, age) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("invalid sla entry: '{}'", entry))?;
+            let age = parse_age(age.trim())?;
+            match Priority::parse(priority)? {
+                Priority::Critical => policy.critical = Some(age),
+                Priority::High => policy.high = Some(age),
+                Priority::Medium => policy.medium = Some(age),
+                Priority::Low => policy.low = Some(age),
+            }
+        }
+        Ok(policy)
+    }
+}
+
+/// Parse an age such as `36h` or `2d`.
+fn parse_age(s: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid age: '{}'", s);
+    let (n, unit): (&str, fn(i64) -> Option<Duration>) = if let Some(n) = s.strip_suffix('h') {
+        (n, Duration::try_hours)
+    } else if let Some(n) = s.strip_suffix('d') {
+        (n, Duration::try_days)
+    } else {
+        return Err(invalid());
+    };
+    n.parse().ok().and_then(unit).ok_or_else(invalid)
+}
+
+impl AppConfig {
+    /// `base_path` as a mount point (`api/v1/` -> `/api/v1`), or `None` when routes are
+    /// served at the root.
+    pub fn mount_path(&self) -> Option<String> {
+        let trimmed = self.base_path.trim().trim_matches('/');
+        (!trimmed.is_empty()).then(|| format!("/{}", trimmed))
+    }
+
+    /// Build the config from environment variables, ignoring unparseable values.
+    pub fn from_env() -> Self {
+        AppConfig {
+            export_max: env_parse("EXPORT_MAX"),
+            import_strict_content_type: env_flag("IMPORT_STRICT_CONTENT_TYPE"),
+            seed_tasks: env::var("SEED_TASKS").ok().filter(|p| !p.trim().is_empty()),
+            data_file: env::var("DATA_FILE").ok().filter(|p| !p.trim().is_empty()),
+            title_template: env_title_template("TITLE_TEMPLATE"),
+            tag_normalization: env_parse("TAG_NORMALIZATION").unwrap_or_default(),
+            max_bulk_items: env_parse("MAX_BULK_ITEMS").unwrap_or(DEFAULT_MAX_BULK_ITEMS),
+            allow_now_override: env_flag("ALLOW_NOW_OVERRIDE"),
+            read_only: env_flag("READ_ONLY"),
+            critical_min_desc: env_parse("CRITICAL_MIN_DESC").unwrap_or(0),
+            max_tag_len: env_parse("MAX_TAG_LEN").unwrap_or(DEFAULT_MAX_TAG_LEN),
+            audit_log_path: env::var("AUDIT_LOG_PATH")
+                .ok()
+                .filter(|p| !p.trim().is_empty()),
+            allow_delete_all: env_flag("ALLOW_DELETE_ALL"),
+            sla: env_parse("SLA_MAX_AGE").unwrap_or_default(),
+            bulk_max_bytes: env_parse("BULK_MAX_BYTES").unwrap_or(DEFAULT_BULK_MAX_BYTES),
+            max_page: env_parse("MAX_PAGE").unwrap_or(DEFAULT_MAX_PAGE),
+            base_path: env::var("BASE_PATH").unwrap_or_default(),
+            nest_health: env_flag("NEST_HEALTH"),
+            stats_workers: env_parse("STATS_WORKERS").unwrap_or(DEFAULT_STATS_WORKERS),
+            create_debounce_ms: env_parse("CREATE_DEBOUNCE_MS").filter(|ms| *ms > 0),
+            fuzzy_threshold: env_parse("FUZZY_THRESHOLD")
+                .filter(|t: &f64| (0.0..=1.0).contains(t))
+                .unwrap_or(DEFAULT_FUZZY_THRESHOLD),
+            request_timeout_ms: env_parse("REQUEST_TIMEOUT_MS")
+                .unwrap_or(DEFAULT_REQUEST_TIMEOUT_MS),
+            rate_limit: env_parse("RATE_LIMIT").filter(|n| *n > 0),
+            rate_limit_window_secs: env_parse("RATE_LIMIT_WINDOW_SECS")
+                .filter(|s| *s > 0)
+                .unwrap_or(DEFAULT_RATE_LIMIT_WINDOW_SECS),
+            trusted_proxies: env::var("TRUSTED_PROXIES")
+                .map(|v| {
+                    v.split(',')
+                        .map(str::trim)
+                        .filter(|p| !p.is_empty())
+                        .filter_map(|p| match p.parse() {
+                            Ok(ip) => Some(ip),
+                            Err(_) => {
+                                log::warn!("ignoring invalid TRUSTED_PROXIES entry {:?}", p);
+                                None
+                            }
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            default_description: env::var("DEFAULT_DESCRIPTION").unwrap_or_default(),
+            default_priority: env_priority("DEFAULT_PRIORITY"),
+            strict_subtask_completion: env_flag("STRICT_SUBTASK_COMPLETION"),
+            log_bodies: env_flag("LOG_BODIES"),
+            log_body_max_bytes: env_parse("LOG_BODY_MAX_BYTES")
+                .unwrap_or(DEFAULT_LOG_BODY_MAX_BYTES),
+            log_redact_fields: env::var("LOG_REDACT_FIELDS")
+                .map(|v| {
+                    v.split(',')
+                        .map(str::trim)
+                        .filter(|f| !f.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            repo_max_description: env_parse("REPO_MAX_DESCRIPTION"),
+            description_overflow: env_parse("DESCRIPTION_OVERFLOW").unwrap_or_default(),
+        }
+    }
+}
+
+/// Read and parse an env var, logging and ignoring invalid values.
+fn env_parse<T: std::str::FromStr>(key: &str) -> Option<T> {
+    let raw = env::var(key).ok()?;
+    match raw.trim().parse() {
+        Ok(v) => Some(v),
+        Err(_) => {
+            log::warn!("ignoring invalid {}={:?}", key, raw);
+            None
+        }
+    }
+}
+
+/// Read a title template, dropping it (with a warning) if its placeholders are invalid.
+fn env_title_template(key: &str) -> Option<String> {
+    let raw = env::var(key).ok().filter(|t| !t.is_empty())?;
+    match validate_title_template(&raw) {
+        Ok(()) => Some(raw),
+        Err(e) => {
+            log::warn!("ignoring invalid {}={:?}: {}", key, raw, e);
+            None
+        }
+    }
+}
+
+/// Read a priority with `Priority::parse`, falling back (with a warning) to the
+/// built-in default if it is invalid.
+fn env_priority(key: &str) -> Priority {
+    let Some(raw) = env::var(key).ok().filter(|p| !p.trim().is_empty()) else {
+        return Priority::default();
+    };
+    Priority::parse(raw.trim()).unwrap_or_else(|e| {
+        log::warn!("ignoring invalid {}={:?}: {}", key, raw, e);
+        Priority::default()
+    })
+}
+
+/// Read a boolean flag; `1`, `true`, `yes` and `on` (any case) enable it.
+fn env_flag(key: &str) -> bool {
+    env::var(key)
+        .map(|v| {
+            matches!(
+                v.trim().to_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+        .unwrap_or(false)
+}
@@ -2,143 +2,534 @@
 //!
 //! This file includes handlers and small helpers used by integration tests.
 
-use axum::body::Bytes;
-use axum::http::HeaderMap;
+use axum::body::{Body, Bytes};
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum::response::{IntoResponse, Response};
 use axum::{
     Json,
     extract::{Path, Query, State},
     http::StatusCode,
 };
 use csv::ReaderBuilder;
+use futures_util::stream;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::models::repository::TaskRepository;
-use crate::models::task::{Task, TaskCreate, TaskUpdate};
+use crate::config::{AppConfig, TagNormalization};
+use crate::models::repository::{
+    ImportClaim, RepoError, SortField, StoreView, TaskFilter, TaskRepository, TaskSort,
+};
+use crate::models::task::{
+    Priority, TagMeta, Task, TaskCreate, TaskUpdate, check_color, join_field_errors,
+};
+use crate::utils::json_patch::{self, PatchOp};
 use crate::utils::logger::log_info;
+use crate::utils::text::{
+    glob_match, levenshtein, render_title_template, trigram_similarity_to, trigrams,
+};
 use serde::Deserialize;
+use std::collections::HashMap;
 
 type AppState = TaskRepository;
 
 /// Create a task: POST /tasks
+/// Returns 400 with `{"errors": [{field, message}, ...]}` listing every validation
+/// failure, or with `{"error": ...}` if the payload breaks the `CRITICAL_MIN_DESC` rule. A client-supplied
+/// `id` is used as-is; if a task with that id exists, 409 is returned and it is kept.
+/// An `X-Actor` header is recorded as the task's `created_by`. With `CREATE_DEBOUNCE_MS`
+/// set, repeating a title created within that window gets 429; creates that fail after
+/// the check do not count.
 pub async fn create_task(
     State(repo): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<TaskCreate>,
-) -> (StatusCode, Json<Task>) {
+) -> Result<(StatusCode, Json<Task>), (StatusCode, Json<serde_json::Value>)> {
     log_info("create_task called");
-    let task = Task::new_full(&payload.title, &payload.description);
+    payload
+        .validate()
+        .map_err(|errors| (StatusCode::BAD_REQUEST, Json(json!({"errors": errors}))))?;
+    let priority = payload
+        .priority
+        .clone()
+        .unwrap_or_else(|| repo.config().default_priority.clone());
+    check_critical_description(&repo, &priority, &payload.description)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+    if let Some(ms) = repo.config().create_debounce_ms
+        && !repo.debounce_create(&payload.title, std::time::Duration::from_millis(ms))
+    {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({"error": "duplicate create too soon"})),
+        ));
+    }
+    let title = payload.title.clone();
+    let created = store_created(&repo, &headers, payload);
+    if created.is_err() && repo.config().create_debounce_ms.is_some() {
+        repo.release_create(&title);
+    }
+    created
+}
+
+/// Store a validated create payload for `create_task`: 409 if its `id` is taken, 400 if
+/// the repository refuses it.
+fn store_created(
+    repo: &AppState,
+    headers: &HeaderMap,
+    mut payload: TaskCreate,
+) -> Result<(StatusCode, Json<Task>), (StatusCode, Json<serde_json::Value>)> {
+    apply_create_defaults(repo, std::slice::from_mut(&mut payload));
+    let task = Task {
+        created_by: request_actor(headers),
+        ..Task::from_create(&payload)
+    };
     // tags not provided via creation DTO (legacy tests). Accept optional header 'x-tags'
     // with comma-separated list of tags for future clients.
     // NOTE: This is a placeholder; will be expanded when DTO evolves.
-    repo.insert(task.clone());
-    (StatusCode::CREATED, Json(task))
+    let task = match payload.id {
+        Some(id) => {
+            let task = Task { id, ..task };
+            repo.insert_if_absent(task).map_err(repo_error)?.ok_or((
+                StatusCode::CONFLICT,
+                Json(json!({"error": "id already exists", "id": id})),
+            ))?
+        }
+        // return the stored copy so changes made by an insert hook are visible
+        None => repo.insert(task).map_err(repo_error)?,
+    };
+    Ok((StatusCode::CREATED, Json(task)))
+}
+
+/// 400 response for a write the repository refused.
+fn repo_error(e: RepoError) -> (StatusCode, Json<serde_json::Value>) {
+    e.into()
+}
+
+impl From<RepoError> for (StatusCode, Json<serde_json::Value>) {
+    fn from(e: RepoError) -> Self {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": e.to_string()})),
+        )
+    }
+}
+
+impl From<RepoError> for (StatusCode, String) {
+    fn from(e: RepoError) -> Self {
+        (StatusCode::BAD_REQUEST, e.to_string())
+    }
+}
+
+/// Longest `X-Actor` value kept, in chars; longer values are cut.
+pub const MAX_ACTOR_LEN: usize = 64;
+
+/// The trimmed `X-Actor` header, limited to `MAX_ACTOR_LEN` chars. Missing or blank
+/// headers give `None`.
+pub(crate) fn request_actor(headers: &HeaderMap) -> Option<String> {
+    let actor = headers.get("x-actor")?.to_str().ok()?.trim();
+    if actor.is_empty() {
+        return None;
+    }
+    Some(actor.chars().take(MAX_ACTOR_LEN).collect())
+}
+
+/// Enforce `CRITICAL_MIN_DESC`: critical tasks need a description of at least that
+/// many characters (trimmed). Other priorities are not checked.
+fn check_critical_description(
+    repo: &AppState,
+    priority: &Priority,
+    description: &str,
+) -> Result<(), String> {
+    let min = repo.config().critical_min_desc;
+    if *priority == Priority::Critical && description.trim().chars().count() < min {
+        return Err(format!(
+            "critical tasks require a description of at least {} chars",
+            min
+        ));
+    }
+    Ok(())
+}
+
+/// Enforce `STRICT_SUBTASK_COMPLETION`: completing `task` with an open subtask is a
+/// 409. Call from inside a `try_modify` closure, so the check and the write share one
+/// lock, and only when the request would mark the task done.
+fn check_subtasks_done(
+    repo: &AppState,
+    store: StoreView<'_>,
+    task: &Task,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    if repo.config().strict_subtask_completion && store.has_open_subtasks(task) {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({"error": "incomplete subtasks"})),
+        ));
+    }
+    Ok(())
+}
+
+/// 400 `{"error": e}` for a rejected edit.
+fn bad_edit(e: String) -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::BAD_REQUEST, Json(json!({"error": e})))
+}
+
+/// Apply the configured defaults to new tasks: titles are rewritten with
+/// `TITLE_TEMPLATE`, blank descriptions get `DEFAULT_DESCRIPTION`, and a missing
+/// priority becomes `DEFAULT_PRIORITY`. Call after the raw payloads have been validated.
+fn apply_create_defaults(repo: &AppState, creates: &mut [TaskCreate]) {
+    let config = repo.config();
+    if let Some(template) = config.title_template.as_deref() {
+        let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        for c in creates.iter_mut() {
+            c.title = render_title_template(template, &c.title, &date);
+        }
+    }
+    if !config.default_description.is_empty() {
+        for c in creates
+            .iter_mut()
+            .filter(|c| c.description.trim().is_empty())
+        {
+            c.description = config.default_description.clone();
+        }
+    }
+    for c in creates.iter_mut().filter(|c| c.priority.is_none()) {
+        c.priority = Some(config.default_priority.clone());
+    }
+}
+
+/// Reject bulk bodies with more than `MAX_BULK_ITEMS` elements before any repository work.
+fn check_bulk_len(
+    repo: &AppState,
+    len: usize,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    if len > repo.config().max_bulk_items {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "too many items"})),
+        ));
+    }
+    Ok(())
+}
+
+/// The time read-only filters should use: the `X-Now` header (RFC 3339) when
+/// `ALLOW_NOW_OVERRIDE` is set, otherwise the real clock. Never used for writes.
+pub(crate) fn request_now(repo: &AppState, headers: &HeaderMap) -> chrono::DateTime<chrono::Utc> {
+    let now = chrono::Utc::now();
+    if !repo.config().allow_now_override {
+        return now;
+    }
+    let Some(raw) = headers.get("x-now").and_then(|v| v.to_str().ok()) else {
+        return now;
+    };
+    match chrono::DateTime::parse_from_rfc3339(raw.trim()) {
+        Ok(t) => t.with_timezone(&chrono::Utc),
+        Err(_) => {
+            log_info(&format!("ignoring invalid X-Now={:?}", raw));
+            now
+        }
+    }
 }
 
 /// Query params for GET /tasks
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default)]
 pub struct ListParams {
     pub completed: Option<bool>,
     pub page: Option<usize>,
     pub per_page: Option<usize>,
     pub sort: Option<String>,
+    /// Include tasks past their `expires_at` (hidden by default).
+    pub include_expired: Option<bool>,
+    /// Only tasks with this tag (case-insensitive).
+    pub tag: Option<String>,
+    /// Exclude tasks with this tag (case-insensitive).
+    pub not_tag: Option<String>,
+    /// Annotate each item with `sla_breached` (see `SLA_MAX_AGE`).
+    pub with_sla: Option<bool>,
+    /// Only tasks whose title starts with this (case-insensitive); empty means no filter.
+    pub title_prefix: Option<String>,
+    /// Reject out-of-range `page`/`per_page` with 400 instead of clamping them.
+    pub strict_pagination: Option<bool>,
+    /// Only pinned tasks when `true`.
+    pub pinned_only: Option<bool>,
+    /// Only tasks whose `created_by` equals this actor.
+    pub created_by: Option<String>,
+    /// Include archived tasks, which are hidden by default.
+    pub include_archived: Option<bool>,
+    /// Only tasks with this priority.
+    pub priority: Option<Priority>,
+    /// Only tasks created at or after this instant (RFC 3339).
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only tasks created strictly before this instant (RFC 3339).
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Largest `per_page` accepted by `GET /tasks`; larger values are clamped (or rejected in
+/// strict mode).
+pub const MAX_PER_PAGE: usize = 100;
+
 /// List tasks: GET /tasks
-/// Supports optional filters: completed, pagination (page, per_page), and sorting (sort=created_at[:asc|:desc] or sort=priority[:asc|:desc]).
+/// Archived tasks are left out unless `include_archived=true`; templates are always left out.
+/// Supports optional filters: completed, priority, tag, not_tag, title_prefix, pinned_only, created_by, created_after/created_before, pagination (page, per_page), and sorting (sort=created_at[:asc|:desc] or sort=priority[:asc|:desc]).
+/// Pinned tasks always come first, with the requested sort applied within each group.
+/// With `with_sla=true` every item also carries `"sla_breached": bool`.
+/// `page` and `per_page` are clamped to valid values unless `strict_pagination=true`, in
+/// which case out-of-range values get 400. A `page` above the `MAX_PAGE` config always
+/// gets 400 `{"error": "page too large"}`, before any sorting work is done.
 pub async fn get_tasks(
     State(repo): State<AppState>,
+    headers: HeaderMap,
     Query(params): Query<ListParams>,
-) -> Json<serde_json::Value> {
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     log_info(&format!("get_tasks called params={:?}", params));
 
     // defaults and validation
+    if params.page.is_some_and(|p| p > repo.config().max_page) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "page too large"})),
+        ));
+    }
+    if params.strict_pagination.unwrap_or(false) {
+        check_pagination(params.page, params.per_page)
+            .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+    }
     let page = params.page.unwrap_or(1).max(1);
     let per_page_requested = params.per_page.unwrap_or(20).max(1);
-    let per_page_cap = 100usize;
-    let per_page = per_page_requested.min(per_page_cap);
-
-    // determine sort field and order
-    let mut items = repo.list();
-    if let Some(s) = params.sort.as_deref() {
-        let desc = s.ends_with(":desc");
-        if s.starts_with("created_at") {
-            items.sort_by(|a, b| {
-                if desc {
-                    b.created_at.cmp(&a.created_at)
-                } else {
-                    a.created_at.cmp(&b.created_at)
-                }
-            });
-        } else if s.starts_with("priority") {
-            items.sort_by(|a, b| {
-                if desc {
-                    b.priority.sort_value().cmp(&a.priority.sort_value())
-                } else {
-                    a.priority.sort_value().cmp(&b.priority.sort_value())
-                }
-            });
-        }
-    } else {
-        // default: sort by created_at ascending
-        items.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    let per_page = per_page_requested.min(MAX_PER_PAGE);
+
+    let now = request_now(&repo, &headers);
+    let filter = list_filter(&repo, &params, now);
+    let sort = TaskSort {
+        pinned_first: true,
+        ..TaskSort::parse(params.sort.as_deref().unwrap_or("created_at"))
+    };
+    let page = repo.list_paginated(&filter, sort, page, per_page);
+    if !params.with_sla.unwrap_or(false) {
+        return Ok(Json(json!(page)));
     }
 
-    // apply completed filter if present
-    if let Some(completed_val) = params.completed {
-        items.retain(|t| t.completed == completed_val);
+    let sla = &repo.config().sla;
+    let breached: Vec<bool> = page.items.iter().map(|t| sla.is_breached(t, now)).collect();
+    let mut body = json!(page);
+    if let Some(items) = body["items"].as_array_mut() {
+        for (item, b) in items.iter_mut().zip(breached) {
+            item["sla_breached"] = json!(b);
+        }
     }
+    Ok(Json(body))
+}
 
-    // filter by tag if provided
-    // Tag filter available via dedicated endpoint: GET /tasks/search/by_tag
+/// The repository filter for `GET /tasks` and `GET /tasks/count` query params, with
+/// expiry judged at `now`.
+fn list_filter(
+    repo: &AppState,
+    params: &ListParams,
+    now: chrono::DateTime<chrono::Utc>,
+) -> TaskFilter {
+    let mode = repo.config().tag_normalization;
+    TaskFilter {
+        completed: params.completed,
+        // hide expired tasks unless explicitly requested
+        hide_expired_at: if params.include_expired.unwrap_or(false) {
+            None
+        } else {
+            Some(now)
+        },
+        tag: params.tag.as_deref().map(|t| mode.apply(t)),
+        not_tag: params.not_tag.as_deref().map(|t| mode.apply(t)),
+        title_prefix: params.title_prefix.clone().filter(|p| !p.is_empty()),
+        pinned: params.pinned_only.filter(|p| *p),
+        archived: (params.include_archived != Some(true)).then_some(false),
+        // templates are only listed by `GET /tasks/templates`
+        template: Some(false),
+        created_by: params
+            .created_by
+            .as_deref()
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(str::to_string),
+        priority: params.priority.clone(),
+        created_after: params.created_after,
+        created_before: params.created_before,
+    }
+}
 
-    let total = items.len();
-    // pagination: page is 1-based
-    let start = per_page * (page.saturating_sub(1));
-    let end = usize::min(start + per_page, total);
-    let page_items = if start >= total {
-        Vec::new()
-    } else {
-        items[start..end].to_vec()
-    };
+/// Strict-mode bounds for `GET /tasks` paging parameters.
+fn check_pagination(page: Option<usize>, per_page: Option<usize>) -> Result<(), String> {
+    if page == Some(0) {
+        return Err("page must be at least 1".into());
+    }
+    match per_page {
+        Some(0) => Err("per_page must be at least 1".into()),
+        Some(n) if n > MAX_PER_PAGE => Err(format!("per_page must be at most {}", MAX_PER_PAGE)),
+        _ => Ok(()),
+    }
+}
 
-    Json(json!({
-        "items": page_items,
-        "total": total,
-        "page": page,
-        "per_page": per_page
-    }))
+/// Query params for GET /tasks/{id}
+#[derive(Debug, Deserialize, Default)]
+pub struct GetParams {
+    /// Return the task even if it has expired.
+    pub include_expired: Option<bool>,
 }
 
 /// Get a task by id: GET /tasks/{id}
+/// Expired tasks return 404 unless `include_expired=true`.
 pub async fn get_task(
     Path(id): Path<String>,
     State(repo): State<AppState>,
+    Query(params): Query<GetParams>,
 ) -> (StatusCode, Json<serde_json::Value>) {
     log_info(&format!("get_task called id={}", id));
-    match Uuid::parse_str(&id) {
-        Ok(uuid) => match repo.get(&uuid) {
-            Some(t) => (StatusCode::OK, Json(json!({"task": t}))),
-            None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
-        },
-        Err(_) => (
+    match visible_task(&repo, &id, &params) {
+        Ok(t) => (StatusCode::OK, Json(json!({"task": t}))),
+        Err(e) => e,
+    }
+}
+
+/// Get a task as JSON or CSV: GET /tasks/{id} and GET /tasks/{id}.csv
+/// A `.csv` suffix or an `Accept` header naming `text/csv` returns the task as a header
+/// row plus one data row (the `GET /tasks/export?format=csv` columns). Errors stay JSON.
+pub async fn get_task_negotiated(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    Query(params): Query<GetParams>,
+    headers: HeaderMap,
+) -> Response {
+    let (id, csv) = match id.strip_suffix(".csv") {
+        Some(base) => (base.to_string(), true),
+        None => {
+            let accepts_csv = headers
+                .get(header::ACCEPT)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.to_lowercase().contains("text/csv"));
+            (id, accepts_csv)
+        }
+    };
+    if !csv {
+        return get_task(Path(id), State(repo), Query(params))
+            .await
+            .into_response();
+    }
+    log_info(&format!("get_task called id={} format=csv", id));
+    let task = match visible_task(&repo, &id, &params) {
+        Ok(t) => t,
+        Err(e) => return e.into_response(),
+    };
+    let body = || -> Result<Vec<u8>, csv::Error> {
+        let mut w = csv::Writer::from_writer(Vec::new());
+        w.write_record(CSV_EXPORT_HEADER)?;
+        w.write_record(csv_export_record(task))?;
+        w.into_inner().map_err(|e| csv::Error::from(e.into_error()))
+    };
+    match body() {
+        Ok(bytes) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"))],
+            bytes,
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+/// Look up a task for the single-task GET routes: 400 for a bad uuid, 404 if missing or
+/// expired (unless `include_expired=true`).
+fn visible_task(
+    repo: &AppState,
+    id: &str,
+    params: &GetParams,
+) -> Result<Task, (StatusCode, Json<serde_json::Value>)> {
+    let include_expired = params.include_expired.unwrap_or(false);
+    let uuid = Uuid::parse_str(id).map_err(|_| {
+        (
             StatusCode::BAD_REQUEST,
             Json(json!({"error": "invalid uuid"})),
-        ),
+        )
+    })?;
+    match repo.get(&uuid) {
+        Some(t) if include_expired || !t.is_expired(chrono::Utc::now()) => Ok(t),
+        _ => Err((StatusCode::NOT_FOUND, Json(json!({"error": "not found"})))),
+    }
+}
+
+/// Get a task by sequence number: GET /tasks/seq/{n}
+/// Same response as `GET /tasks/{id}`; expired tasks are hidden.
+pub async fn get_task_by_seq(
+    Path(n): Path<String>,
+    State(repo): State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("get_task_by_seq called n={}", n));
+    let Ok(seq) = n.parse::<u64>() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid seq"})),
+        );
+    };
+    match repo.get_by_seq(seq) {
+        Some(t) if !t.is_expired(chrono::Utc::now()) => (StatusCode::OK, Json(json!({"task": t}))),
+        _ => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Check a task exists: HEAD /tasks/{id}
+/// Returns 200 with the task's `ETag` and no body, or 404; the task is not serialized.
+/// Like GET, expired tasks are 404 unless `include_expired=true`.
+pub async fn head_task(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    Query(params): Query<GetParams>,
+) -> Response {
+    log_info(&format!("head_task called id={}", id));
+    let Ok(uuid) = Uuid::parse_str(&id) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    let etag = if params.include_expired.unwrap_or(false) {
+        repo.etag(&uuid)
+    } else {
+        repo.etag_unexpired(&uuid, chrono::Utc::now())
+    };
+    match etag {
+        Some(tag) => match HeaderValue::from_str(&tag) {
+            Ok(v) => (StatusCode::OK, [(header::ETAG, v)]).into_response(),
+            Err(_) => StatusCode::OK.into_response(),
+        },
+        None => StatusCode::NOT_FOUND.into_response(),
     }
 }
 
 /// Update a task: PUT /tasks/{id}
+/// Invalid fields get 400 with `{"errors": [{field, message}, ...]}`, all reported at once.
+/// With `STRICT_SUBTASK_COMPLETION`, completing a task with open subtasks is a 409.
 pub async fn update_task(
     Path(id): Path<String>,
     State(repo): State<AppState>,
     Json(payload): Json<TaskUpdate>,
 ) -> (StatusCode, Json<serde_json::Value>) {
     log_info(&format!("update_task called id={}", id));
+    if let Err(errors) = payload.validate() {
+        return (StatusCode::BAD_REQUEST, Json(json!({"errors": errors})));
+    }
     match Uuid::parse_str(&id) {
-        Ok(uuid) => match repo.update(&uuid, payload.clone()) {
-            Some(t) => (StatusCode::OK, Json(json!({"task": t}))),
-            None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
-        },
+        Ok(uuid) => {
+            let completes = payload.completed == Some(true);
+            let res = repo.try_modify(&uuid, |t, store| {
+                t.apply_update(payload);
+                if completes {
+                    check_subtasks_done(&repo, store, t)?;
+                }
+                check_critical_description(&repo, &t.priority, &t.description).map_err(bad_edit)
+            });
+            match res {
+                Some(Ok(t)) => (StatusCode::OK, Json(json!({"task": t}))),
+                Some(Err(e)) => e,
+                None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+            }
+        }
         Err(_) => (
             StatusCode::BAD_REQUEST,
             Json(json!({"error": "invalid uuid"})),
@@ -147,69 +538,217 @@ pub async fn update_task(
 }
 
 /// Delete a task: DELETE /tasks/{id}
+/// An optional `If-Match` header makes the delete conditional: it must name the task's
+/// current `ETag` (or its `updated_at` as RFC 3339, or `*`), otherwise 412 is returned
+/// and the task is kept.
 pub async fn delete_task(
     Path(id): Path<String>,
     State(repo): State<AppState>,
+    headers: HeaderMap,
 ) -> (StatusCode, Json<serde_json::Value>) {
     log_info(&format!("delete_task called id={}", id));
-    match Uuid::parse_str(&id) {
-        Ok(uuid) => {
-            if repo.remove(&uuid) {
-                (StatusCode::NO_CONTENT, Json(json!({})))
-            } else {
-                (StatusCode::NOT_FOUND, Json(json!({"error": "not found"})))
-            }
-        }
-        Err(_) => (
+    let Ok(uuid) = Uuid::parse_str(&id) else {
+        return (
             StatusCode::BAD_REQUEST,
             Json(json!({"error": "invalid uuid"})),
+        );
+    };
+    let if_match = headers
+        .get(header::IF_MATCH)
+        .map(|v| v.to_str().unwrap_or_default().to_string());
+    let removed = repo.remove_if(&uuid, |t| {
+        if_match.as_deref().is_none_or(|m| if_match_matches(m, t))
+    });
+    match removed {
+        Some(true) => (StatusCode::NO_CONTENT, Json(json!({}))),
+        Some(false) => (
+            StatusCode::PRECONDITION_FAILED,
+            Json(json!({"error": "precondition failed", "etag": repo.etag(&uuid)})),
         ),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Whether an `If-Match` value names the current version of `task`: `*`, one of a
+/// comma-separated list of ETags, or the task's `updated_at` as RFC 3339.
+fn if_match_matches(value: &str, task: &Task) -> bool {
+    let value = value.trim();
+    if value == "*" {
+        return true;
+    }
+    if let Ok(at) = chrono::DateTime::parse_from_rfc3339(value) {
+        return at == task.updated_at;
+    }
+    let etag = task.etag();
+    value.split(',').any(|v| v.trim() == etag)
+}
+
+/// Remove expired tasks: POST /tasks/gc
+/// Hard-deletes every task past its `expires_at` and returns `{"removed": N}`.
+pub async fn gc_expired(State(repo): State<AppState>) -> Json<serde_json::Value> {
+    log_info("gc_expired called");
+    let removed = repo.remove_expired(chrono::Utc::now());
+    Json(json!({"removed": removed}))
+}
+
+/// Remove every task: DELETE /tasks/all
+/// Admin-only; returns 403 unless `ALLOW_DELETE_ALL` is set. Returns `{"deleted": N}`.
+pub async fn delete_all_tasks(
+    State(repo): State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info("delete_all_tasks called");
+    if !repo.config().allow_delete_all {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "delete all is disabled"})),
+        );
     }
+    let deleted = repo.drain().len();
+    (StatusCode::OK, Json(json!({"deleted": deleted})))
 }
 
 /// Count tasks: GET /tasks/count
-pub async fn count_tasks(State(repo): State<AppState>) -> Json<serde_json::Value> {
-    log_info("count_tasks called");
-    let n = repo.count();
+/// Takes the same filter params as `GET /tasks` (paging and sort are ignored), so
+/// `count` always equals the list's `total` for the same query.
+pub async fn count_tasks(
+    State(repo): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<ListParams>,
+) -> Json<serde_json::Value> {
+    log_info(&format!("count_tasks called params={:?}", params));
+    let now = request_now(&repo, &headers);
+    let n = repo.count_where(&list_filter(&repo, &params, now));
     Json(json!({"count": n}))
 }
 
 /// Bulk delete tasks: DELETE /tasks
 /// Accepts a JSON array of UUID strings and removes any matching tasks.
-/// Returns JSON {"deleted": N} where N is the number of tasks deleted.
+/// Returns JSON {"deleted": N, "not_found": [...], "invalid": [...]} where N is the number
+/// of tasks deleted, `not_found` lists well-formed ids with no task and `invalid` lists
+/// strings that are not UUIDs.
 pub async fn bulk_delete_tasks(
     State(repo): State<AppState>,
     Json(payload): Json<Vec<String>>,
 ) -> (StatusCode, Json<serde_json::Value>) {
     log_info("bulk_delete_tasks called");
+    if let Err(e) = check_bulk_len(&repo, payload.len()) {
+        return e;
+    }
 
-    // parse valid UUIDs, ignore invalid entries
+    // split into parseable UUIDs and invalid entries
     let mut ids = Vec::with_capacity(payload.len());
+    let mut invalid: Vec<&String> = Vec::new();
     for s in payload.iter() {
-        if let Ok(u) = Uuid::parse_str(s) {
-            ids.push(u);
+        match Uuid::parse_str(s) {
+            Ok(u) => ids.push(u),
+            Err(_) => invalid.push(s),
         }
     }
 
-    let removed = if ids.is_empty() {
-        0
+    let removed: std::collections::HashSet<Uuid> = if ids.is_empty() {
+        Default::default()
     } else {
-        repo.remove_many(&ids)
+        repo.remove_many_ids(&ids).into_iter().collect()
     };
+    let not_found: Vec<String> = ids
+        .iter()
+        .filter(|id| !removed.contains(id))
+        .map(|id| id.to_string())
+        .collect();
 
-    (StatusCode::OK, Json(json!({"deleted": removed})))
+    (
+        StatusCode::OK,
+        Json(json!({
+            "deleted": removed.len(),
+            "not_found": not_found,
+            "invalid": invalid,
+        })),
+    )
+}
+
+/// Bulk archive: POST /tasks/archive
+/// Accepts a JSON array of UUID strings and archives every matching task, hiding it
+/// from the default `GET /tasks` view. Invalid UUIDs and unknown ids are skipped.
+/// Returns `{"archived": N}` counting tasks that were not archived before.
+pub async fn archive_tasks(
+    State(repo): State<AppState>,
+    Json(payload): Json<Vec<String>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("archive_tasks called ids={}", payload.len()));
+    if let Err(e) = check_bulk_len(&repo, payload.len()) {
+        return e;
+    }
+    let n = repo.set_archived_many(&parse_ids_lenient(&payload), true);
+    (StatusCode::OK, Json(json!({"archived": n})))
+}
+
+/// Bulk unarchive: POST /tasks/unarchive
+/// The reverse of `archive_tasks`; returns `{"unarchived": N}`.
+pub async fn unarchive_tasks(
+    State(repo): State<AppState>,
+    Json(payload): Json<Vec<String>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("unarchive_tasks called ids={}", payload.len()));
+    if let Err(e) = check_bulk_len(&repo, payload.len()) {
+        return e;
+    }
+    let n = repo.set_archived_many(&parse_ids_lenient(&payload), false);
+    (StatusCode::OK, Json(json!({"unarchived": n})))
+}
+
+/// Parse id strings, dropping any that are not UUIDs.
+fn parse_ids_lenient(ids: &[String]) -> Vec<Uuid> {
+    ids.iter().filter_map(|s| Uuid::parse_str(s).ok()).collect()
+}
+
+/// Bulk create: POST /tasks/bulk
+/// Accepts a JSON array of TaskCreate. Each entry is validated independently; valid
+/// entries are created and invalid ones reported by index. Always returns 201 with
+/// `{"created": [...], "errors": [{"index": i, "error": ...}]}`.
+pub async fn bulk_create_tasks(
+    State(repo): State<AppState>,
+    Json(payload): Json<Vec<TaskCreate>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!(
+        "bulk_create_tasks called payload_len={}",
+        payload.len()
+    ));
+    if let Err(e) = check_bulk_len(&repo, payload.len()) {
+        return e;
+    }
+    let mut valid: Vec<TaskCreate> = Vec::with_capacity(payload.len());
+    let mut errors: Vec<serde_json::Value> = Vec::new();
+    for (i, it) in payload.into_iter().enumerate() {
+        match it.validate() {
+            Ok(_) => valid.push(it),
+            Err(e) => errors.push(json!({"index": i, "error": join_field_errors(&e)})),
+        }
+    }
+    apply_create_defaults(&repo, &mut valid);
+    let created = match repo.insert_many(&valid) {
+        Ok(created) => created,
+        Err(e) => return repo_error(e),
+    };
+    (
+        StatusCode::CREATED,
+        Json(json!({"created": created, "errors": errors})),
+    )
 }
 
 /// Import tasks from a JSON array POST /tasks/import (application/json)
 pub async fn import_tasks_json(
     State(repo): State<AppState>,
-    Json(payload): Json<Vec<TaskCreate>>,
+    Json(mut payload): Json<Vec<TaskCreate>>,
 ) -> (StatusCode, Json<serde_json::Value>) {
     log_info(&format!(
         "import_tasks_json called payload_len={}",
         payload.len()
     ));
-    let created = repo.insert_many(&payload);
+    apply_create_defaults(&repo, &mut payload);
+    let created = match repo.insert_many(&payload) {
+        Ok(created) => created,
+        Err(e) => return repo_error(e),
+    };
     (
         StatusCode::CREATED,
         Json(json!({"imported": created.len(), "tasks": created})),
@@ -218,19 +757,16 @@ pub async fn import_tasks_json(
 
 /// Import tasks from CSV POST /tasks/import/csv (text/csv)
 /// Expects header row with `title,description` and optional additional columns ignored by the CSV deserializer.
+/// The body is decoded according to the `charset` param (UTF-8 by default).
 pub async fn import_tasks_csv(
     State(repo): State<AppState>,
+    Query(params): Query<ImportParams>,
     body: Bytes,
 ) -> (StatusCode, Json<serde_json::Value>) {
     log_info("import_tasks_csv called");
-    let s = match std::str::from_utf8(&body) {
+    let s = match params.charset().and_then(|c| c.decode(&body)) {
         Ok(v) => v,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({"error": "invalid utf8 in body"})),
-            );
-        }
+        Err(e) => return e,
     };
 
     let mut reader = ReaderBuilder::new()
@@ -250,22 +786,171 @@ pub async fn import_tasks_csv(
         }
     }
 
-    let created = repo.insert_many(&creates);
+    apply_create_defaults(&repo, &mut creates);
+    let created = match repo.insert_many(&creates) {
+        Ok(created) => created,
+        Err(e) => return repo_error(e),
+    };
     (
         StatusCode::CREATED,
         Json(json!({"imported": created.len(), "tasks": created})),
     )
 }
 
+/// Query params for the import endpoints.
+#[derive(Debug, Deserialize, Default)]
+pub struct ImportParams {
+    /// CSV field delimiter: a single ASCII character such as `;` or a tab (default `,`).
+    pub delimiter: Option<String>,
+    /// Encoding of CSV/NDJSON bodies: `utf-8` (default) or `latin1`.
+    pub charset: Option<String>,
+    /// Skip rows whose title (trimmed, case-insensitive) already exists or appeared
+    /// earlier in the same import (`POST /tasks/import` only).
+    #[serde(default)]
+    pub dedupe: bool,
+}
+
+/// Text encoding accepted for CSV and NDJSON import bodies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImportCharset {
+    /// Invalid UTF-8 is rejected with 400.
+    #[default]
+    Utf8,
+    /// ISO-8859-1: every byte maps to the code point of the same value, so any input
+    /// decodes losslessly. For legacy exports.
+    Latin1,
+}
+
+impl ImportCharset {
+    /// Decode an import body to text, or a 400 response if it is not valid in this charset.
+    pub fn decode<'a>(
+        &self,
+        body: &'a [u8],
+    ) -> Result<std::borrow::Cow<'a, str>, (StatusCode, Json<serde_json::Value>)> {
+        match self {
+            ImportCharset::Utf8 => std::str::from_utf8(body).map(Into::into).map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": "invalid utf8 in body"})),
+                )
+            }),
+            ImportCharset::Latin1 => Ok(body.iter().map(|&b| b as char).collect::<String>().into()),
+        }
+    }
+}
+
+impl ImportParams {
+    /// The CSV delimiter byte, or a 400 response if it is not a single ASCII character.
+    fn csv_delimiter(&self) -> Result<u8, (StatusCode, Json<serde_json::Value>)> {
+        match self.delimiter.as_deref() {
+            None => Ok(b','),
+            Some(d) if d.len() == 1 && d.is_ascii() => Ok(d.as_bytes()[0]),
+            Some(d) => Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": format!("invalid delimiter {:?}: must be a single ASCII character", d)
+                })),
+            )),
+        }
+    }
+
+    /// The body charset, or a 400 response for an unknown `charset` value.
+    fn charset(&self) -> Result<ImportCharset, (StatusCode, Json<serde_json::Value>)> {
+        let Some(raw) = self.charset.as_deref() else {
+            return Ok(ImportCharset::default());
+        };
+        match raw.trim().to_lowercase().as_str() {
+            "utf-8" | "utf8" => Ok(ImportCharset::Utf8),
+            "latin1" | "latin-1" | "iso-8859-1" => Ok(ImportCharset::Latin1),
+            _ => Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": format!("invalid charset {:?}: expected utf-8 or latin1", raw)
+                })),
+            )),
+        }
+    }
+}
+
+/// One CSV import row. Only `title` is required; a missing `description`
+/// column defaults to an empty string so title-only files can be imported.
+#[derive(Debug, Deserialize)]
+struct CsvTaskRow {
+    title: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// Parse CSV content (with header row) into validated creation DTOs.
+/// Returns the valid rows plus per-row errors (`row` is 1-based, excluding the header).
+fn parse_csv_rows(content: &str, delimiter: u8) -> (Vec<TaskCreate>, Vec<serde_json::Value>) {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(true)
+        .delimiter(delimiter)
+        .from_reader(content.as_bytes());
+    let mut valid: Vec<TaskCreate> = Vec::new();
+    let mut errors: Vec<serde_json::Value> = Vec::new();
+    for (i, dec) in reader.deserialize::<CsvTaskRow>().enumerate() {
+        match dec {
+            Ok(row) => {
+                let tc = TaskCreate {
+                    title: row.title,
+                    description: row.description,
+                    ..Default::default()
+                };
+                match tc.validate() {
+                    Ok(_) => valid.push(tc),
+                    Err(e) => errors.push(json!({"row": i + 1, "error": join_field_errors(&e)})),
+                }
+            }
+            Err(e) => {
+                errors.push(json!({"row": i + 1, "error": format!("csv parse error: {}", e)}))
+            }
+        }
+    }
+    (valid, errors)
+}
+
+/// Parse newline-delimited JSON into validated creation DTOs, skipping blank lines.
+/// Errors report the 1-based `line` number.
+fn parse_ndjson_rows(content: &str) -> (Vec<TaskCreate>, Vec<serde_json::Value>) {
+    let mut valid: Vec<TaskCreate> = Vec::new();
+    let mut errors: Vec<serde_json::Value> = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<TaskCreate>(line) {
+            Ok(tc) => match tc.validate() {
+                Ok(_) => valid.push(tc),
+                Err(e) => errors.push(json!({"line": i + 1, "error": join_field_errors(&e)})),
+            },
+            Err(e) => {
+                errors.push(json!({"line": i + 1, "error": format!("json parse error: {}", e)}))
+            }
+        }
+    }
+    (valid, errors)
+}
+
 /// Import tasks by uploading a multipart/form-data file (field name `file`).
 /// This is a simple, non-streaming parser: the entire request body is read into memory.
 /// It enforces a size limit to avoid OOM for very large uploads.
 pub async fn import_tasks_file(
     State(repo): State<AppState>,
     headers: HeaderMap,
+    Query(params): Query<ImportParams>,
     body: Bytes,
 ) -> (StatusCode, Json<serde_json::Value>) {
     log_info("import_tasks_file called");
+    let delimiter = match params.csv_delimiter() {
+        Ok(d) => d,
+        Err(e) => return e,
+    };
+    let charset = match params.charset() {
+        Ok(c) => c,
+        Err(e) => return e,
+    };
 
     const MAX_BYTES: usize = 5 * 1024 * 1024; // 5 MB
     if body.len() > MAX_BYTES {
@@ -295,14 +980,9 @@ pub async fn import_tasks_file(
     }
 
     // crude split by boundary; each part begins with `--{boundary}`
-    let raw = match std::str::from_utf8(&body) {
+    let raw = match charset.decode(&body) {
         Ok(s) => s,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({"error": "invalid utf8 in body"})),
-            );
-        }
+        Err(e) => return e,
     };
 
     let marker = format!("--{}", boundary.trim());
@@ -341,27 +1021,16 @@ pub async fn import_tasks_file(
     };
 
     // parse CSV from file_content
-    let mut reader = ReaderBuilder::new()
-        .has_headers(true)
-        .from_reader(file_content.as_bytes());
-    let mut valid: Vec<TaskCreate> = Vec::new();
-    let mut errors: Vec<serde_json::Value> = Vec::new();
-    for (i, dec) in reader.deserialize::<TaskCreate>().enumerate() {
-        match dec {
-            Ok(tc) => match tc.validate() {
-                Ok(_) => valid.push(tc),
-                Err(e) => errors.push(json!({"row": i + 1, "error": e})),
-            },
-            Err(e) => {
-                errors.push(json!({"row": i + 1, "error": format!("csv parse error: {}", e)}))
-            }
-        }
-    }
+    let (mut valid, errors) = parse_csv_rows(file_content, delimiter);
+    apply_create_defaults(&repo, &mut valid);
 
     let created = if valid.is_empty() {
         Vec::new()
     } else {
-        repo.insert_many(&valid)
+        match repo.insert_many(&valid) {
+            Ok(created) => created,
+            Err(e) => return repo_error(e),
+        }
     };
     let imported = created.len();
     let failed = errors.len();
@@ -371,32 +1040,136 @@ pub async fn import_tasks_file(
         Json(json!({"imported": imported, "failed": failed, "errors": errors, "tasks": created})),
     )
 }
+/// Media types accepted by `import_tasks`.
+pub const IMPORT_CONTENT_TYPES: [&str; 3] =
+    ["application/json", "text/csv", "application/x-ndjson"];
+
 /// Unified import: POST /tasks/import
-/// Accepts either `application/json` (array of TaskCreate) or `text/csv` (with header).
+/// Accepts `application/json` (array of TaskCreate), `text/csv` (with header;
+/// `title` is required, `description` is optional) or `application/x-ndjson`
+/// (one TaskCreate object per line). Other media types get 415 with the accepted list.
+/// Without a content type (or with `application/octet-stream`) the format is sniffed from
+/// the body, and 400 is returned if it cannot be told; `IMPORT_STRICT_CONTENT_TYPE`
+/// turns sniffing off.
 /// Returns a partial-success summary: { imported, failed, errors, tasks } with 201.
+///
+/// An optional `X-Import-Id` header makes retries idempotent: if an import with the same
+/// id already succeeded in this process, its summary is returned with 200 and nothing
+/// is inserted again. While an import with that id is still running, repeats get 409.
 pub async fn import_tasks(
     State(repo): State<AppState>,
     headers: HeaderMap,
+    Query(params): Query<ImportParams>,
     body: Bytes,
 ) -> (StatusCode, Json<serde_json::Value>) {
     log_info("import_tasks called");
+    let delimiter = match params.csv_delimiter() {
+        Ok(d) => d,
+        Err(e) => return e,
+    };
+    let charset = match params.charset() {
+        Ok(c) => c,
+        Err(e) => return e,
+    };
+
+    let import_id = headers
+        .get("x-import-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string);
+    if let Some(id) = &import_id {
+        match repo.claim_import(id) {
+            ImportClaim::Done(summary) => return (StatusCode::OK, Json(summary)),
+            ImportClaim::Pending => {
+                return (
+                    StatusCode::CONFLICT,
+                    Json(json!({"error": "import already in progress", "import_id": id})),
+                );
+            }
+            ImportClaim::Claimed => {}
+        }
+    }
+
+    let (code, Json(summary)) =
+        run_import(&repo, &headers, &body, delimiter, charset, params.dedupe);
+    if let Some(id) = import_id {
+        if code == StatusCode::CREATED {
+            repo.remember_import(id, summary.clone());
+        } else {
+            repo.release_import(&id);
+        }
+    }
+    (code, Json(summary))
+}
+
+/// Parse and store one import body; see `import_tasks`.
+fn run_import(
+    repo: &AppState,
+    headers: &HeaderMap,
+    body: &Bytes,
+    delimiter: u8,
+    charset: ImportCharset,
+    dedupe: bool,
+) -> (StatusCode, Json<serde_json::Value>) {
+    // reject empty bodies up front with a clear message instead of a parser error
+    if body.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "empty import body"})),
+        );
+    }
 
+    // media type without parameters such as `; charset=utf-8`
     let ct = headers
         .get("content-type")
         .and_then(|v| v.to_str().ok())
-        .unwrap_or("");
+        .unwrap_or("")
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+    let sniffable = ct.is_empty() || ct == "application/octet-stream";
+    let media_type = if sniffable && !repo.config().import_strict_content_type {
+        match sniff_import_type(body, delimiter) {
+            Some(t) => t,
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "cannot detect import format; send a JSON array, NDJSON or CSV with a title header, or set Content-Type",
+                        "accepted": IMPORT_CONTENT_TYPES,
+                    })),
+                );
+            }
+        }
+    } else {
+        ct.as_str()
+    };
+    if !IMPORT_CONTENT_TYPES.contains(&media_type) {
+        return (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(json!({
+                "error": "unsupported content-type",
+                "accepted": IMPORT_CONTENT_TYPES,
+            })),
+        );
+    }
 
     let mut valid: Vec<TaskCreate> = Vec::new();
     let mut errors: Vec<serde_json::Value> = Vec::new();
 
-    if ct.contains("json") || ct.is_empty() {
-        // try JSON
-        match serde_json::from_slice::<Vec<TaskCreate>>(&body) {
+    if media_type == "application/json" {
+        match serde_json::from_slice::<Vec<TaskCreate>>(body) {
             Ok(items) => {
+                if let Err(e) = check_bulk_len(repo, items.len()) {
+                    return e;
+                }
                 for (i, it) in items.into_iter().enumerate() {
                     match it.validate() {
                         Ok(_) => valid.push(it),
-                        Err(e) => errors.push(json!({"index": i, "error": e})),
+                        Err(e) => errors.push(json!({"index": i, "error": join_field_errors(&e)})),
                     }
                 }
             }
@@ -407,58 +1180,100 @@ pub async fn import_tasks(
                 );
             }
         }
-    } else if ct.contains("csv") {
-        // CSV path
-        let s = match std::str::from_utf8(&body) {
+    } else {
+        // CSV and NDJSON are both line-oriented text
+        let s = match charset.decode(body) {
             Ok(v) => v,
-            Err(_) => {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({"error": "invalid utf8 in body"})),
-                );
-            }
+            Err(e) => return e,
         };
 
-        let mut reader = ReaderBuilder::new()
-            .has_headers(true)
-            .from_reader(s.as_bytes());
-        for (i, dec) in reader.deserialize::<TaskCreate>().enumerate() {
-            match dec {
-                Ok(tc) => match tc.validate() {
-                    Ok(_) => valid.push(tc),
-                    Err(e) => errors.push(json!({"row": i + 1, "error": e})),
-                },
-                Err(e) => {
-                    errors.push(json!({"row": i + 1, "error": format!("csv parse error: {}", e)}))
-                }
-            }
-        }
-    } else {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": "unsupported content-type"})),
-        );
+        let (rows, row_errors) = if media_type == "text/csv" {
+            parse_csv_rows(&s, delimiter)
+        } else {
+            parse_ndjson_rows(&s)
+        };
+        valid = rows;
+        errors = row_errors;
     }
 
     // persist valid rows
+    apply_create_defaults(repo, &mut valid);
+    let skipped = if dedupe {
+        dedupe_by_title(repo, &mut valid)
+    } else {
+        Vec::new()
+    };
     let created = if valid.is_empty() {
         Vec::new()
     } else {
-        repo.insert_many(&valid)
+        match repo.insert_many(&valid) {
+            Ok(created) => created,
+            Err(e) => return repo_error(e),
+        }
     };
 
     let imported = created.len();
     let failed = errors.len();
 
-    (
-        StatusCode::CREATED,
-        Json(json!({
-            "imported": imported,
-            "failed": failed,
-            "errors": errors,
-            "tasks": created
-        })),
-    )
+    let mut summary = json!({
+        "imported": imported,
+        "failed": failed,
+        "errors": errors,
+        "tasks": created
+    });
+    if dedupe {
+        summary["skipped"] = json!(skipped.len());
+        summary["skipped_rows"] = json!(skipped);
+    }
+    (StatusCode::CREATED, Json(summary))
+}
+
+/// Guess the media type of an import sent without a usable `Content-Type`: a leading
+/// `[` is a JSON array, a leading `{` is NDJSON, and a first line with a `title` column
+/// (split on `delimiter`) is CSV.
+fn sniff_import_type(body: &[u8], delimiter: u8) -> Option<&'static str> {
+    let body = body.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(body);
+    let start = body.iter().position(|b| !b.is_ascii_whitespace())?;
+    match body[start] {
+        b'[' => return Some("application/json"),
+        b'{' => return Some("application/x-ndjson"),
+        _ => {}
+    }
+    let rest = &body[start..];
+    let first_line = rest.split(|b| *b == b'\n').next().unwrap_or(rest);
+    let has_title = first_line
+        .split(|b| *b == delimiter)
+        .map(|f| {
+            String::from_utf8_lossy(f)
+                .trim()
+                .trim_matches('"')
+                .to_lowercase()
+        })
+        .any(|f| f == "title");
+    has_title.then_some("text/csv")
+}
+
+/// Drop rows whose title (trimmed, lowercased) is already stored or was seen earlier in
+/// `rows`. Returns `{"title", "reason"}` for each dropped row, in input order.
+fn dedupe_by_title(repo: &AppState, rows: &mut Vec<TaskCreate>) -> Vec<serde_json::Value> {
+    use std::collections::HashSet;
+    let key = |title: &str| title.trim().to_lowercase();
+    let existing: HashSet<String> = repo.list().iter().map(|t| key(&t.title)).collect();
+    let mut seen = HashSet::new();
+    let mut skipped = Vec::new();
+    rows.retain(|c| {
+        let k = key(&c.title);
+        let reason = if existing.contains(&k) {
+            "title already exists"
+        } else if !seen.insert(k) {
+            "duplicate title in import"
+        } else {
+            return true;
+        };
+        skipped.push(json!({"title": c.title, "reason": reason}));
+        false
+    });
+    skipped
 }
 
 // ------------------------
@@ -470,24 +1285,42 @@ pub struct TagsPayload {
     pub tags: Vec<String>,
 }
 
-fn validate_tags(tags: &[String]) -> Result<(), String> {
+/// Maximum number of entries accepted in one tag list, checked before any per-tag work.
+pub const MAX_TAGS_PER_TASK: usize = 100;
+
+/// Reject oversized tag lists up front so pathological payloads cost nothing to refuse.
+fn check_tag_count(tags: &[String]) -> Result<(), String> {
+    if tags.len() > MAX_TAGS_PER_TASK {
+        return Err(format!("too many tags (max {})", MAX_TAGS_PER_TASK));
+    }
+    Ok(())
+}
+
+/// Check tags against `MAX_TAG_LEN` and reject entries that are empty, or become empty
+/// under the configured normalization (e.g. `" - "` in kebab mode).
+fn validate_tags(tags: &[String], config: &AppConfig) -> Result<(), String> {
     for t in tags.iter() {
         if t.trim().is_empty() {
             return Err("tags must not contain empty entries".into());
         }
-        if t.len() > 64 {
-            return Err("tag too long (max 64 chars)".into());
+        if t.len() > config.max_tag_len {
+            return Err(format!("tag too long (max {} chars)", config.max_tag_len));
+        }
+        if config.tag_normalization.apply(t).is_empty() {
+            return Err(format!("tag '{}' is empty after normalization", t));
         }
     }
     Ok(())
 }
 
-fn normalize_tags(tags: &[String]) -> Vec<String> {
+/// Normalize tags with the configured mode (see `TagNormalization`), dropping
+/// empties and duplicates while keeping first-seen order.
+fn normalize_tags(tags: &[String], mode: TagNormalization) -> Vec<String> {
     use std::collections::HashSet;
     let mut seen = HashSet::new();
     let mut out = Vec::new();
     for t in tags.iter() {
-        let norm = t.trim().to_lowercase();
+        let norm = mode.apply(t);
         if !norm.is_empty() && seen.insert(norm.clone()) {
             out.push(norm);
         }
@@ -495,38 +1328,45 @@ fn normalize_tags(tags: &[String]) -> Vec<String> {
     out
 }
 
+/// Query params for PUT /tasks/{id}/tags
+#[derive(Debug, Deserialize, Default)]
+pub struct SetTagsParams {
+    /// Reject tags that collapse into one after normalization instead of deduping them.
+    pub strict_tags: Option<bool>,
+}
+
 /// Replace tags on a task: PUT /tasks/{id}/tags
+/// Duplicates after normalization (e.g. `Feature` and `feature`) are collapsed, or
+/// rejected with 400 when `strict_tags=true`.
 pub async fn set_tags(
     Path(id): Path<String>,
     State(repo): State<AppState>,
+    Query(params): Query<SetTagsParams>,
     Json(payload): Json<TagsPayload>,
 ) -> (StatusCode, Json<serde_json::Value>) {
     log_info(&format!("set_tags called id={}", id));
 
-    if let Err(e) = validate_tags(&payload.tags) {
+    if let Err(e) =
+        check_tag_count(&payload.tags).and_then(|_| validate_tags(&payload.tags, repo.config()))
+    {
         return (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
     }
 
-    let tags = normalize_tags(&payload.tags);
+    let tags = normalize_tags(&payload.tags, repo.config().tag_normalization);
+    // every input tag is non-empty here, so a shorter result means duplicates were dropped
+    if params.strict_tags.unwrap_or(false) && tags.len() != payload.tags.len() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "duplicate tags after normalization"})),
+        );
+    }
 
     match Uuid::parse_str(&id) {
         Ok(uuid) => {
-            let mut t = match repo.get(&uuid) {
-                Some(existing) => existing,
+            let t = match repo.modify(&uuid, |t| t.tags = tags) {
+                Some(t) => t,
                 None => return (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
             };
-            t.tags = tags;
-            // persist by calling update with no field changes other than tags
-            let _ = repo.update(
-                &uuid,
-                TaskUpdate {
-                    title: None,
-                    description: None,
-                    completed: None,
-                },
-            );
-            // Directly overwrite stored task with updated tags to avoid changing TaskUpdate DTO
-            repo.insert(t.clone());
             (StatusCode::OK, Json(json!({"task": t})))
         }
         Err(_) => (
@@ -554,141 +1394,1520 @@ pub async fn get_tags(
     }
 }
 
-/// Query tasks by tag: GET /tasks/search/by_tag?tag=...
-#[derive(Debug, Deserialize)]
-pub struct TagQuery {
-    pub tag: String,
-}
-
-pub async fn get_tasks_by_tag(
+/// Check whether a task has a tag: GET /tasks/{id}/tags/{tag}
+/// Returns `{"exists": bool}`; matching is case-insensitive.
+pub async fn tag_exists(
+    Path((id, tag)): Path<(String, String)>,
     State(repo): State<AppState>,
-    Query(q): Query<TagQuery>,
-) -> Json<serde_json::Value> {
-    log_info(&format!("get_tasks_by_tag called tag={}", q.tag));
-    let tag = q.tag.to_lowercase();
-    let mut items = repo.list();
-    items.retain(|t| t.tags.iter().any(|x| x.eq_ignore_ascii_case(&tag)));
-    Json(json!({"items": items, "total": items.len()}))
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("tag_exists called id={} tag={}", id, tag));
+    match Uuid::parse_str(&id) {
+        Ok(uuid) => match repo.get(&uuid) {
+            Some(t) => {
+                let mode = repo.config().tag_normalization;
+                let wanted = mode.apply(&tag);
+                let exists = t.tags.iter().any(|x| mode.apply(x) == wanted);
+                (StatusCode::OK, Json(json!({"exists": exists})))
+            }
+            None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+        },
+        Err(_) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid uuid"})),
+        ),
+    }
 }
 
-// ------------------------
-// Task statistics/analytics
-// ------------------------
-
-/// Statistics summary: GET /tasks/stats
-/// Returns aggregated metrics about the task repository:
-/// - total, completed, incomplete counts
-/// - tag_distribution: top N tags with counts (sorted descending)
-/// - oldest_created_at, newest_created_at (ISO timestamps)
-pub async fn get_stats(State(repo): State<AppState>) -> Json<serde_json::Value> {
-    log_info("get_stats called");
-    let items = repo.list();
-    let total = items.len();
-    let completed = items.iter().filter(|t| t.completed).count();
-    let incomplete = total - completed;
+/// Payload for POST /tasks/tags/unassign
+#[derive(Debug, Deserialize, serde::Serialize, Clone)]
+pub struct UnassignTagsPayload {
+    pub ids: Vec<String>,
+    pub tags: Vec<String>,
+}
 
-    // Build tag frequency map
-    use std::collections::HashMap;
-    let mut tag_counts: HashMap<String, usize> = HashMap::new();
-    for task in items.iter() {
-        for tag in task.tags.iter() {
-            *tag_counts.entry(tag.clone()).or_insert(0) += 1;
-        }
+/// Remove tags from many tasks: POST /tasks/tags/unassign
+/// Tags are normalized before matching and invalid ids are ignored.
+/// Returns `{"updated": N}` where N counts tasks that actually lost a tag.
+pub async fn unassign_tags(
+    State(repo): State<AppState>,
+    Json(payload): Json<UnassignTagsPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!(
+        "unassign_tags called ids={} tags={}",
+        payload.ids.len(),
+        payload.tags.len()
+    ));
+    if let Err(e) = check_bulk_len(&repo, payload.ids.len()) {
+        return e;
     }
 
-    // Sort tags by frequency (descending), then alphabetically for ties
-    let mut tag_vec: Vec<(String, usize)> = tag_counts.into_iter().collect();
-    tag_vec.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    if let Err(e) = validate_tags(&payload.tags, repo.config()) {
+        return (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
+    }
+    let tags = normalize_tags(&payload.tags, repo.config().tag_normalization);
 
-    // Limit to top 10 tags
-    let top_tags: Vec<serde_json::Value> = tag_vec
+    // parse valid UUIDs, ignore invalid entries
+    let ids: Vec<Uuid> = payload
+        .ids
         .iter()
-        .take(10)
-        .map(|(tag, count)| json!({"tag": tag, "count": count}))
+        .filter_map(|s| Uuid::parse_str(s).ok())
         .collect();
 
-    // Find oldest and newest by created_at
-    let oldest_opt = items.iter().min_by_key(|t| t.created_at);
-    let newest_opt = items.iter().max_by_key(|t| t.created_at);
+    let updated = repo.remove_tags_many(&ids, &tags);
+    (StatusCode::OK, Json(json!({"updated": updated})))
+}
 
-    let oldest_created = oldest_opt.map(|t| t.created_at.to_rfc3339());
-    let newest_created = newest_opt.map(|t| t.created_at.to_rfc3339());
+/// Replace tags on many tasks: POST /tasks/tags/set
+/// Accepts the same `{"ids": [...], "tags": [...]}` body as unassign. Tags are validated
+/// and normalized once before anything changes; each listed task's tag set is then
+/// replaced (not merged) under one write lock. Invalid or unknown ids are ignored.
+/// Returns `{"updated": N}`.
+pub async fn set_tags_many(
+    State(repo): State<AppState>,
+    Json(payload): Json<UnassignTagsPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!(
+        "set_tags_many called ids={} tags={}",
+        payload.ids.len(),
+        payload.tags.len()
+    ));
+    if let Err(e) = check_bulk_len(&repo, payload.ids.len()) {
+        return e;
+    }
 
-    Json(json!({
-        "total": total,
-        "completed": completed,
-        "incomplete": incomplete,
-        "tag_distribution": top_tags,
-        "oldest_created_at": oldest_created,
-        "newest_created_at": newest_created,
-    }))
+    if let Err(e) =
+        check_tag_count(&payload.tags).and_then(|_| validate_tags(&payload.tags, repo.config()))
+    {
+        return (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
+    }
+    let tags = normalize_tags(&payload.tags, repo.config().tag_normalization);
+
+    let ids: Vec<Uuid> = payload
+        .ids
+        .iter()
+        .filter_map(|s| Uuid::parse_str(s).ok())
+        .collect();
+
+    let updated = repo.set_tags_many(&ids, &tags);
+    (StatusCode::OK, Json(json!({"updated": updated})))
 }
 
-/// Payload for setting task priority
-#[derive(Debug, Deserialize)]
-pub struct PriorityPayload {
-    pub priority: String,
+/// Payload for POST /tasks/tags/remove-matching; exactly one field must be set.
+#[derive(Debug, Default, Deserialize, serde::Serialize, Clone)]
+pub struct RemoveMatchingPayload {
+    /// Remove tags starting with this, e.g. `tmp/`.
+    pub prefix: Option<String>,
+    /// Remove tags matching this glob (`*` any run of chars, `?` one char).
+    pub glob: Option<String>,
 }
 
-/// Set task priority: PUT /tasks/{id}/priority
-pub async fn set_priority(
-    Path(id): Path<String>,
+/// Bulk-remove tags by pattern: POST /tasks/tags/remove-matching
+/// Accepts `{"prefix": "tmp/"}` or `{"glob": "tmp/*"}` and strips every matching tag from
+/// every task. Matching is case-insensitive. Returns `{"updated": N, "removed_instances": M}`
+/// where N counts tasks that lost a tag and M the tags removed in total.
+pub async fn remove_matching_tags(
     State(repo): State<AppState>,
-    Json(payload): Json<PriorityPayload>,
-) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
-    let task_id =
-        Uuid::parse_str(&id).map_err(|_| (StatusCode::BAD_REQUEST, "invalid UUID".to_string()))?;
-
-    // Parse and validate priority
-    let priority = crate::models::task::Priority::parse(&payload.priority)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
-
-    // Get task, update priority field, and save back
-    let mut task = repo
-        .get(&task_id)
-        .ok_or((StatusCode::NOT_FOUND, "task not found".to_string()))?;
+    Json(payload): Json<RemoveMatchingPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!(
+        "remove_matching_tags called payload={:?}",
+        payload
+    ));
+    let lower = |s: &str| s.trim().to_lowercase();
+    let (updated, removed) = match (&payload.prefix, &payload.glob) {
+        (Some(p), None) if !p.trim().is_empty() => {
+            let prefix = lower(p);
+            repo.remove_tags_where(|t| t.to_lowercase().starts_with(&prefix))
+        }
+        (None, Some(g)) if !g.trim().is_empty() => {
+            let glob = lower(g);
+            repo.remove_tags_where(|t| glob_match(&glob, &t.to_lowercase()))
+        }
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "exactly one non-empty 'prefix' or 'glob' is required"})),
+            );
+        }
+    };
+    (
+        StatusCode::OK,
+        Json(json!({"updated": updated, "removed_instances": removed})),
+    )
+}
 
-    task.priority = priority;
-    task.updated_at = chrono::Utc::now();
+/// Selection part of POST /tasks/update-by-filter; at least one field is required.
+#[derive(Debug, Default, Deserialize, serde::Serialize, Clone)]
+pub struct UpdateFilter {
+    pub completed: Option<bool>,
+    pub priority: Option<String>,
+    pub tag: Option<String>,
+}
 
-    // Use repository's insert to overwrite the task
-    repo.insert(task.clone());
+/// Changes applied by POST /tasks/update-by-filter.
+#[derive(Debug, Default, Deserialize, serde::Serialize, Clone)]
+pub struct FilterUpdate {
+    pub completed: Option<bool>,
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub add_tags: Vec<String>,
+}
 
-    Ok((
-        StatusCode::OK,
-        Json(json!({
-            "id": task.id.to_string(),
-            "priority": task.priority,
-        })),
-    ))
+/// Payload for POST /tasks/update-by-filter
+#[derive(Debug, Default, Deserialize, serde::Serialize, Clone)]
+pub struct UpdateByFilterPayload {
+    pub filter: UpdateFilter,
+    pub update: FilterUpdate,
 }
 
-/// Get task priority: GET /tasks/{id}/priority
-pub async fn get_priority(
-    Path(id): Path<String>,
+/// Update every task matching a filter: POST /tasks/update-by-filter
+/// Filters (`completed`, `priority`, `tag`) are ANDed and an empty filter is rejected
+/// with 400 so a typo cannot touch every task. The update is applied under one write
+/// lock; if it would break the `CRITICAL_MIN_DESC` rule for any match, nothing changes.
+/// Returns `{"updated": N}`.
+pub async fn update_by_filter(
     State(repo): State<AppState>,
-) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    let task_id =
-        Uuid::parse_str(&id).map_err(|_| (StatusCode::BAD_REQUEST, "invalid UUID".to_string()))?;
+    Json(payload): Json<UpdateByFilterPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info("update_by_filter called");
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
+    let UpdateByFilterPayload { filter, update } = payload;
 
-    let task = repo
-        .get(&task_id)
-        .ok_or((StatusCode::NOT_FOUND, "task not found".to_string()))?;
+    if filter.completed.is_none() && filter.priority.is_none() && filter.tag.is_none() {
+        return bad_request("filter must contain at least one field".into());
+    }
+    let want_priority = match filter.priority.as_deref().map(Priority::parse).transpose() {
+        Ok(p) => p,
+        Err(e) => return bad_request(e),
+    };
+    let new_priority = match update.priority.as_deref().map(Priority::parse).transpose() {
+        Ok(p) => p,
+        Err(e) => return bad_request(e),
+    };
+    if let Err(e) = validate_tags(&update.add_tags, repo.config()) {
+        return bad_request(e);
+    }
+    let mode = repo.config().tag_normalization;
+    let want_tag = filter.tag.as_deref().map(|t| mode.apply(t));
+    let add_tags = normalize_tags(&update.add_tags, mode);
+
+    let matches = |t: &Task| {
+        filter.completed.is_none_or(|c| t.completed == c)
+            && want_priority.as_ref().is_none_or(|p| &t.priority == p)
+            && want_tag
+                .as_ref()
+                .is_none_or(|w| t.tags.iter().any(|x| x.eq_ignore_ascii_case(w)))
+    };
+    let result = repo.modify_where(matches, |t, store| {
+        // subtasks completed by this same update do not count as open
+        if update.completed == Some(true)
+            && repo.config().strict_subtask_completion
+            && store.open_subtasks(t).any(|c| !matches(c))
+        {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(json!({"error": "incomplete subtasks", "id": t.id})),
+            ));
+        }
+        if let Some(c) = update.completed {
+            t.apply_update(TaskUpdate {
+                completed: Some(c),
+                ..Default::default()
+            });
+        }
+        if let Some(p) = &new_priority {
+            t.priority = p.clone();
+        }
+        for tag in &add_tags {
+            if !t.tags.iter().any(|x| x.eq_ignore_ascii_case(tag)) {
+                t.tags.push(tag.clone());
+            }
+        }
+        check_critical_description(&repo, &t.priority, &t.description).map_err(bad_edit)
+    });
+    match result {
+        Ok(updated) => (StatusCode::OK, Json(json!({"updated": updated}))),
+        Err(e) => e,
+    }
+}
 
-    Ok(Json(json!({
-        "id": task.id.to_string(),
-        "priority": task.priority,
-    })))
+/// Filter for POST /tasks/reset-completed.
+#[derive(Debug, Default, Deserialize, serde::Serialize, Clone)]
+pub struct ResetCompletedFilter {
+    pub tag: Option<String>,
+    pub priority: Option<String>,
+    /// RFC 3339 timestamp; only tasks created strictly before it match.
+    pub before: Option<String>,
 }
 
-/// Search tasks by priority: GET /tasks/search/by_priority?priority=high
-pub async fn get_tasks_by_priority(
+/// Reopen completed tasks in bulk: POST /tasks/reset-completed
+/// Sets `completed=false` and clears `completed_at` on every completed task matching all
+/// given filter fields. An empty filter is rejected with 400 so nothing is reset by
+/// accident. Returns `{"reset": N}`.
+pub async fn reset_completed(
     State(repo): State<AppState>,
-    Query(params): Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<Vec<Task>>, (StatusCode, String)> {
-    let priority_str = params.get("priority").ok_or((
-        StatusCode::BAD_REQUEST,
-        "missing 'priority' query parameter".to_string(),
+    Json(filter): Json<ResetCompletedFilter>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("reset_completed called filter={:?}", filter));
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
+
+    if filter.tag.is_none() && filter.priority.is_none() && filter.before.is_none() {
+        return bad_request("filter must contain at least one field".into());
+    }
+    let want_priority = match filter.priority.as_deref().map(Priority::parse).transpose() {
+        Ok(p) => p,
+        Err(e) => return bad_request(e),
+    };
+    let before = match filter
+        .before
+        .as_deref()
+        .map(chrono::DateTime::parse_from_rfc3339)
+        .transpose()
+    {
+        Ok(b) => b.map(|dt| dt.with_timezone(&chrono::Utc)),
+        Err(_) => return bad_request("invalid 'before' timestamp (expected RFC 3339)".into()),
+    };
+    let want_tag = filter
+        .tag
+        .as_deref()
+        .map(|t| repo.config().tag_normalization.apply(t));
+
+    let reset = repo.modify_where(
+        |t| {
+            t.completed
+                && want_priority.as_ref().is_none_or(|p| &t.priority == p)
+                && before.is_none_or(|b| t.created_at < b)
+                && want_tag
+                    .as_ref()
+                    .is_none_or(|w| t.tags.iter().any(|x| x.eq_ignore_ascii_case(w)))
+        },
+        |t, _| {
+            t.apply_update(TaskUpdate {
+                completed: Some(false),
+                ..Default::default()
+            });
+            Ok::<_, RepoError>(())
+        },
+    );
+    match reset {
+        Ok(reset) => (StatusCode::OK, Json(json!({"reset": reset}))),
+        Err(e) => repo_error(e),
+    }
+}
+
+/// Migrate stored tags: POST /tasks/tags/normalize
+/// Re-applies the configured normalization (trim, lowercase, dedupe) to the tags of every
+/// task, e.g. after switching `TAG_NORMALIZATION`. Returns `{"updated": N}` counting
+/// tasks whose tags changed.
+pub async fn normalize_all_tags(State(repo): State<AppState>) -> Json<serde_json::Value> {
+    log_info("normalize_all_tags called");
+    let mode = repo.config().tag_normalization;
+    let updated = repo.rewrite_tags(|tags| normalize_tags(tags, mode));
+    Json(json!({"updated": updated}))
+}
+
+/// Preview tag normalization: POST /tasks/tags/normalize-preview
+/// Accepts `{"tags": [...]}` and returns `{"normalized": [...], "rejected": [{"tag",
+/// "reason"}]}`: tags that would fail validation are listed with the reason, the rest
+/// are shown as they would be stored. No task is touched.
+pub async fn preview_tag_normalization(
+    State(repo): State<AppState>,
+    Json(payload): Json<TagsPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!(
+        "preview_tag_normalization called tags={}",
+        payload.tags.len()
+    ));
+    if let Err(e) = check_tag_count(&payload.tags) {
+        return (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
+    }
+    let config = repo.config();
+    let mut accepted = Vec::new();
+    let mut rejected = Vec::new();
+    for tag in payload.tags {
+        match validate_tags(std::slice::from_ref(&tag), config) {
+            Ok(()) => accepted.push(tag),
+            Err(reason) => rejected.push(json!({"tag": tag, "reason": reason})),
+        }
+    }
+    let normalized = normalize_tags(&accepted, repo.config().tag_normalization);
+    (
+        StatusCode::OK,
+        Json(json!({"normalized": normalized, "rejected": rejected})),
+    )
+}
+
+/// Query params for GET /tasks/tags/cooccurrence
+#[derive(Debug, Deserialize, Default)]
+pub struct CooccurrenceParams {
+    /// Maximum number of pairs returned (default 10).
+    pub top: Option<usize>,
+}
+
+/// Tag co-occurrence: GET /tasks/tags/cooccurrence?top=N
+/// Counts every unordered pair of distinct tags carried by the same task and returns the
+/// `top` most frequent as `{"pairs": [{"a", "b", "count"}]}`, sorted by count descending
+/// (ties by `a`, then `b`). Within a pair `a < b`.
+pub async fn get_tag_cooccurrence(
+    State(repo): State<AppState>,
+    Query(params): Query<CooccurrenceParams>,
+) -> Json<serde_json::Value> {
+    log_info(&format!("get_tag_cooccurrence called params={:?}", params));
+    let top = params.top.unwrap_or(10);
+    let tasks = repo.list();
+    Json(offload(&repo, move || tag_cooccurrence(tasks, top)).await)
+}
+
+fn tag_cooccurrence(tasks: Vec<Task>, top: usize) -> serde_json::Value {
+    let mut counts: HashMap<(String, String), usize> = HashMap::new();
+    for t in tasks {
+        let mut tags: Vec<String> = t.tags.iter().map(|x| x.to_lowercase()).collect();
+        tags.sort();
+        tags.dedup();
+        for (i, a) in tags.iter().enumerate() {
+            for b in &tags[i + 1..] {
+                *counts.entry((a.clone(), b.clone())).or_default() += 1;
+            }
+        }
+    }
+
+    let mut pairs: Vec<((String, String), usize)> = counts.into_iter().collect();
+    pairs.sort_by(|(ka, ca), (kb, cb)| cb.cmp(ca).then_with(|| ka.cmp(kb)));
+    let pairs: Vec<serde_json::Value> = pairs
+        .into_iter()
+        .take(top)
+        .map(|((a, b), count)| json!({"a": a, "b": b, "count": count}))
+        .collect();
+    json!({"pairs": pairs})
+}
+
+/// Run a CPU-heavy, read-only aggregation on tokio's blocking pool so it does not stall
+/// the async workers. At most `STATS_WORKERS` run at once. Callers pass an owned
+/// snapshot, so no repository lock is held while `f` runs.
+async fn offload<T, F>(repo: &AppState, f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let _permit = repo.heavy_permit().await;
+    match tokio::task::spawn_blocking(f).await {
+        Ok(v) => v,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+/// Set tag metadata: PUT /tasks/tags/{tag}/meta
+/// Accepts `{"color": "#ff0000", "description": "..."}`; the tag is normalized and
+/// need not be used by any task.
+pub async fn set_tag_meta(
+    Path(tag): Path<String>,
+    State(repo): State<AppState>,
+    Json(payload): Json<TagMeta>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("set_tag_meta called tag={}", tag));
+    if let Err(e) = validate_tags(std::slice::from_ref(&tag), repo.config()) {
+        return (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
+    }
+    if let Err(e) = payload.validate() {
+        return (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
+    }
+    let tag = repo.config().tag_normalization.apply(&tag);
+    repo.set_tag_meta(&tag, payload.clone());
+    (
+        StatusCode::OK,
+        Json(json!({"tag": tag, "color": payload.color, "description": payload.description})),
+    )
+}
+
+/// Get tag metadata: GET /tasks/tags/{tag}/meta
+pub async fn get_tag_meta(
+    Path(tag): Path<String>,
+    State(repo): State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("get_tag_meta called tag={}", tag));
+    let tag = repo.config().tag_normalization.apply(&tag);
+    match repo.tag_meta(&tag) {
+        Some(meta) => (
+            StatusCode::OK,
+            Json(json!({"tag": tag, "color": meta.color, "description": meta.description})),
+        ),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Query tasks by tag: GET /tasks/search/by_tag?tag=...[&contains=true]
+#[derive(Debug, Deserialize)]
+pub struct TagQuery {
+    pub tag: String,
+    /// Match tasks with any tag containing `tag` instead of an exact tag match.
+    pub contains: Option<bool>,
+}
+
+pub async fn get_tasks_by_tag(
+    State(repo): State<AppState>,
+    Query(q): Query<TagQuery>,
+) -> Json<serde_json::Value> {
+    log_info(&format!("get_tasks_by_tag called tag={}", q.tag));
+    let tag = repo.config().tag_normalization.apply(&q.tag);
+    let items = if q.contains.unwrap_or(false) {
+        repo.list_by_tag_substring(&tag)
+    } else {
+        repo.list_by_tag(&tag)
+    };
+    Json(json!({"items": items, "total": items.len()}))
+}
+
+/// Query params for GET /tasks/duplicates
+#[derive(Debug, Deserialize, Default)]
+pub struct DuplicatesParams {
+    /// Also group titles within this many character edits of each other (default 0: exact).
+    pub threshold: Option<usize>,
+}
+
+/// Find likely duplicates: GET /tasks/duplicates?threshold=N
+/// Groups tasks by normalized title (trimmed, lowercased) and returns only groups with
+/// more than one task: `{"groups": [{"title": "...", "ids": [...]}]}`. Groups and ids are
+/// ordered oldest first; a group's title is that of its oldest task.
+pub async fn find_duplicates(
+    State(repo): State<AppState>,
+    Query(params): Query<DuplicatesParams>,
+) -> Json<serde_json::Value> {
+    log_info(&format!("find_duplicates called params={:?}", params));
+    let threshold = params.threshold.unwrap_or(0);
+    let tasks = repo.list_sorted_by_created_at(false);
+    Json(offload(&repo, move || duplicate_groups(&tasks, threshold)).await)
+}
+
+/// Duplicate groups over `tasks`, which must be sorted oldest first.
+fn duplicate_groups(tasks: &[Task], threshold: usize) -> serde_json::Value {
+    // exact groups, in order of each title's first appearance
+    let mut groups: Vec<(String, Vec<Uuid>)> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for t in tasks.iter() {
+        let key = t.title.trim().to_lowercase();
+        let i = *index.entry(key.clone()).or_insert_with(|| {
+            groups.push((key, Vec::new()));
+            groups.len() - 1
+        });
+        groups[i].1.push(t.id);
+    }
+
+    // near duplicates: fold each group into the earliest group within `threshold` edits
+    if threshold > 0 {
+        let mut parent: Vec<usize> = (0..groups.len()).collect();
+        for i in 0..groups.len() {
+            for j in 0..i {
+                if levenshtein(&groups[i].0, &groups[j].0) <= threshold {
+                    parent[i] = parent[j];
+                    break;
+                }
+            }
+        }
+        for i in (0..groups.len()).rev() {
+            if parent[i] != i {
+                let ids = std::mem::take(&mut groups[i].1);
+                groups[parent[i]].1.extend(ids);
+            }
+        }
+        let position: HashMap<Uuid, usize> =
+            tasks.iter().enumerate().map(|(i, t)| (t.id, i)).collect();
+        for (_, ids) in groups.iter_mut() {
+            ids.sort_by_key(|id| position[id]);
+        }
+    }
+
+    let groups: Vec<serde_json::Value> = groups
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|(title, ids)| json!({"title": title, "ids": ids}))
+        .collect();
+    json!({"groups": groups})
+}
+
+// ------------------------
+// Task statistics/analytics
+// ------------------------
+
+/// Statistics summary: GET /tasks/stats
+/// Returns aggregated metrics about the task repository:
+/// - total, completed, incomplete counts
+/// - tag_distribution: top N tags with counts (sorted descending)
+/// - oldest_created_at, newest_created_at (ISO timestamps)
+/// - expired: tasks past `expires_at` (honors `X-Now`, see `request_now`)
+///
+/// Results are cached in the repository and recomputed only after a task changes; the
+/// recomputation runs off the async runtime (see `offload`).
+pub async fn get_stats(
+    State(repo): State<AppState>,
+    headers: HeaderMap,
+) -> Json<serde_json::Value> {
+    log_info("get_stats called");
+    let r = repo.clone();
+    let mut stats = offload(&repo, move || r.cached_stats(|| compute_stats(&r.list()))).await;
+    // time-dependent, so counted per request rather than cached
+    stats["expired"] = json!(repo.count_expired(request_now(&repo, &headers)));
+    Json(stats)
+}
+
+fn compute_stats(items: &[Task]) -> serde_json::Value {
+    let total = items.len();
+    let completed = items.iter().filter(|t| t.completed).count();
+    let incomplete = total - completed;
+
+    // Build tag frequency map
+    let mut tag_counts: HashMap<String, usize> = HashMap::new();
+    for task in items.iter() {
+        for tag in task.tags.iter() {
+            *tag_counts.entry(tag.clone()).or_insert(0) += 1;
+        }
+    }
+
+    // Sort tags by frequency (descending), then alphabetically for ties
+    let mut tag_vec: Vec<(String, usize)> = tag_counts.into_iter().collect();
+    tag_vec.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    // Limit to top 10 tags
+    let top_tags: Vec<serde_json::Value> = tag_vec
+        .iter()
+        .take(10)
+        .map(|(tag, count)| json!({"tag": tag, "count": count}))
+        .collect();
+
+    // Find oldest and newest by created_at
+    let oldest_opt = items.iter().min_by_key(|t| t.created_at);
+    let newest_opt = items.iter().max_by_key(|t| t.created_at);
+
+    let oldest_created = oldest_opt.map(|t| t.created_at.to_rfc3339());
+    let newest_created = newest_opt.map(|t| t.created_at.to_rfc3339());
+
+    let total_estimated: u64 = items
+        .iter()
+        .filter_map(|t| t.estimate_minutes)
+        .map(u64::from)
+        .sum();
+    let total_logged: u64 = items.iter().map(|t| u64::from(t.logged_minutes)).sum();
+
+    json!({
+        "total": total,
+        "completed": completed,
+        "incomplete": incomplete,
+        "tag_distribution": top_tags,
+        "oldest_created_at": oldest_created,
+        "newest_created_at": newest_created,
+        "total_estimated_minutes": total_estimated,
+        "total_logged_minutes": total_logged,
+    })
+}
+
+/// Parse a `YYYY-MM-DD` query date.
+fn parse_day(s: &str) -> Result<chrono::NaiveDate, String> {
+    chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("invalid date '{}' (expected YYYY-MM-DD)", s))
+}
+
+/// Most ISO weeks `GET /tasks/stats/weekly` reports in one response (about 10 years).
+pub const MAX_WEEKLY_WEEKS: i64 = 520;
+
+/// Query params for GET /tasks/stats/weekly
+#[derive(Debug, Deserialize, Default)]
+pub struct WeeklyParams {
+    /// First day of the range (`YYYY-MM-DD`, inclusive). Defaults to the start of the
+    /// ISO week 7 weeks before `to`, giving 8 weeks in total.
+    pub from: Option<String>,
+    /// Last day of the range (`YYYY-MM-DD`, inclusive). Defaults to today.
+    pub to: Option<String>,
+}
+
+/// Weekly statistics: GET /tasks/stats/weekly?from=YYYY-MM-DD&to=YYYY-MM-DD
+/// Returns `{"weeks": [{"week": "2024-W03", "created": N, "completed": M}, ...]}` with
+/// one entry per ISO week overlapping the range (including empty weeks), oldest first.
+/// `created` counts `created_at` and `completed` counts `completed_at` within the range.
+/// Ranges touching more than `MAX_WEEKLY_WEEKS` weeks get 400.
+pub async fn get_weekly_stats(
+    State(repo): State<AppState>,
+    Query(params): Query<WeeklyParams>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    use chrono::{Datelike, Duration, NaiveDate};
+    log_info(&format!("get_weekly_stats called params={:?}", params));
+
+    let today = chrono::Utc::now().date_naive();
+    let to = match params.to.as_deref().map(parse_day).transpose() {
+        Ok(d) => d.unwrap_or(today),
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(json!({"error": e}))),
+    };
+    let out_of_range = || {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "date out of range"})),
+        )
+    };
+    let week_start = |d: NaiveDate| {
+        d.checked_sub_signed(Duration::days(d.weekday().num_days_from_monday() as i64))
+    };
+    let from = match params.from.as_deref().map(parse_day).transpose() {
+        Ok(Some(d)) => d,
+        // default: the 8 ISO weeks ending with the week containing `to`
+        Ok(None) => match week_start(to).and_then(|m| m.checked_sub_signed(Duration::weeks(7))) {
+            Some(d) => d,
+            None => return out_of_range(),
+        },
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(json!({"error": e}))),
+    };
+    if from > to {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "'from' must not be after 'to'"})),
+        );
+    }
+
+    // one bucket per ISO week touching the range, in chronological order
+    let Some(first_monday) = week_start(from) else {
+        return out_of_range();
+    };
+    if (to - first_monday).num_days() / 7 >= MAX_WEEKLY_WEEKS {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": format!("range too long (max {} weeks)", MAX_WEEKLY_WEEKS)
+            })),
+        );
+    }
+    let mut buckets: Vec<(NaiveDate, usize, usize)> = Vec::new();
+    let mut monday = Some(first_monday);
+    while let Some(m) = monday.filter(|m| *m <= to) {
+        buckets.push((m, 0, 0));
+        monday = m.checked_add_signed(Duration::weeks(1));
+    }
+    let bucket_of = |d: NaiveDate| {
+        if d < from || d > to {
+            return None;
+        }
+        Some(((d - first_monday).num_days() / 7) as usize)
+    };
+
+    for t in repo.list() {
+        if let Some(i) = bucket_of(t.created_at.date_naive()) {
+            buckets[i].1 += 1;
+        }
+        if let Some(i) = t.completed_at.and_then(|c| bucket_of(c.date_naive())) {
+            buckets[i].2 += 1;
+        }
+    }
+
+    let weeks: Vec<serde_json::Value> = buckets
+        .iter()
+        .map(|(monday, created, completed)| {
+            let w = monday.iso_week();
+            json!({
+                "week": format!("{}-W{:02}", w.year(), w.week()),
+                "created": created,
+                "completed": completed,
+            })
+        })
+        .collect();
+    (StatusCode::OK, Json(json!({"weeks": weeks})))
+}
+
+/// Longest range `GET /tasks/activity/daily` accepts, in days.
+pub const MAX_DAILY_SPAN_DAYS: i64 = 366;
+
+/// Query params for GET /tasks/activity/daily
+#[derive(Debug, Deserialize, Default)]
+pub struct DailyActivityParams {
+    /// First day of the range (`YYYY-MM-DD`, inclusive). Defaults to 29 days before `to`.
+    pub from: Option<String>,
+    /// Last day of the range (`YYYY-MM-DD`, inclusive). Defaults to today.
+    pub to: Option<String>,
+    /// Also list days without activity, with zero counts.
+    #[serde(default)]
+    pub include_empty: bool,
+}
+
+/// Daily activity: GET /tasks/activity/daily?from=YYYY-MM-DD&to=YYYY-MM-DD
+/// Returns `{"days": [{"date": "2024-01-15", "created": N, "updated": M, "completed": K}, ...]}`
+/// oldest first, counting `created_at`, `updated_at` (only when later than `created_at`)
+/// and `completed_at` per UTC day within the range. Days with no activity are left out
+/// unless `include_empty=true`. Ranges longer than `MAX_DAILY_SPAN_DAYS` get 400.
+pub async fn get_daily_activity(
+    State(repo): State<AppState>,
+    Query(params): Query<DailyActivityParams>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    use chrono::{Duration, NaiveDate};
+    use std::collections::BTreeMap;
+    log_info(&format!("get_daily_activity called params={:?}", params));
+
+    let to = match params.to.as_deref().map(parse_day).transpose() {
+        Ok(d) => d.unwrap_or_else(|| chrono::Utc::now().date_naive()),
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(json!({"error": e}))),
+    };
+    let from = match params.from.as_deref().map(parse_day).transpose() {
+        Ok(Some(d)) => d,
+        Ok(None) => match to.checked_sub_signed(Duration::days(29)) {
+            Some(d) => d,
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": "date out of range"})),
+                );
+            }
+        },
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(json!({"error": e}))),
+    };
+    if from > to {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "'from' must not be after 'to'"})),
+        );
+    }
+    if (to - from).num_days() >= MAX_DAILY_SPAN_DAYS {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": format!("range too long (max {} days)", MAX_DAILY_SPAN_DAYS)
+            })),
+        );
+    }
+
+    // date -> [created, updated, completed]
+    let mut days: BTreeMap<NaiveDate, [usize; 3]> = BTreeMap::new();
+    if params.include_empty {
+        let mut d = Some(from);
+        while let Some(day) = d.filter(|day| *day <= to) {
+            days.insert(day, [0; 3]);
+            d = day.succ_opt();
+        }
+    }
+    let mut count = |d: NaiveDate, i: usize| {
+        if d >= from && d <= to {
+            days.entry(d).or_default()[i] += 1;
+        }
+    };
+    for t in repo.list() {
+        count(t.created_at.date_naive(), 0);
+        if t.updated_at > t.created_at {
+            count(t.updated_at.date_naive(), 1);
+        }
+        if let Some(c) = t.completed_at {
+            count(c.date_naive(), 2);
+        }
+    }
+
+    let days: Vec<serde_json::Value> = days
+        .iter()
+        .map(|(date, [created, updated, completed])| {
+            json!({
+                "date": date.format("%Y-%m-%d").to_string(),
+                "created": created,
+                "updated": updated,
+                "completed": completed,
+            })
+        })
+        .collect();
+    (StatusCode::OK, Json(json!({"days": days})))
+}
+
+/// Payload for setting task priority
+#[derive(Debug, Deserialize)]
+pub struct PriorityPayload {
+    pub priority: String,
+}
+
+/// Set task priority: PUT /tasks/{id}/priority
+pub async fn set_priority(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    Json(payload): Json<PriorityPayload>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
+    let task_id =
+        Uuid::parse_str(&id).map_err(|_| (StatusCode::BAD_REQUEST, "invalid UUID".to_string()))?;
+
+    // Parse and validate priority
+    let priority = crate::models::task::Priority::parse(&payload.priority)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // Update the priority in place under the repository lock
+    let task = repo
+        .try_modify(&task_id, |t, _| {
+            t.priority = priority;
+            check_critical_description(&repo, &t.priority, &t.description)
+                .map_err(|e| (StatusCode::BAD_REQUEST, e))
+        })
+        .ok_or((StatusCode::NOT_FOUND, "task not found".to_string()))??;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "id": task.id.to_string(),
+            "priority": task.priority,
+        })),
+    ))
+}
+
+/// Raise task priority one level: POST /tasks/{id}/priority/bump
+/// `critical` stays `critical`. Returns `{"task": ...}`; raising to critical still has
+/// to satisfy `CRITICAL_MIN_DESC`.
+pub async fn bump_priority(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    shift_priority(&id, &repo, Priority::next)
+}
+
+/// Lower task priority one level: POST /tasks/{id}/priority/lower
+/// `low` stays `low`. Returns `{"task": ...}`.
+pub async fn lower_priority(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    shift_priority(&id, &repo, Priority::prev)
+}
+
+fn shift_priority(
+    id: &str,
+    repo: &AppState,
+    shift: fn(&Priority) -> Priority,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    log_info(&format!("shift_priority called id={}", id));
+    let task_id =
+        Uuid::parse_str(id).map_err(|_| (StatusCode::BAD_REQUEST, "invalid UUID".to_string()))?;
+    let task = repo
+        .try_modify(&task_id, |t, _| {
+            t.priority = shift(&t.priority);
+            check_critical_description(repo, &t.priority, &t.description)
+                .map_err(|e| (StatusCode::BAD_REQUEST, e))
+        })
+        .ok_or((StatusCode::NOT_FOUND, "task not found".to_string()))??;
+    Ok(Json(json!({"task": task})))
+}
+
+/// Pin a task to the top of listings: POST /tasks/{id}/pin
+pub async fn pin_task(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    set_pinned(&id, &repo, true)
+}
+
+/// Unpin a task: POST /tasks/{id}/unpin
+pub async fn unpin_task(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    set_pinned(&id, &repo, false)
+}
+
+fn set_pinned(id: &str, repo: &AppState, pinned: bool) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("set_pinned called id={} pinned={}", id, pinned));
+    let Ok(uuid) = Uuid::parse_str(id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid uuid"})),
+        );
+    };
+    match repo.modify(&uuid, |t| t.pinned = pinned) {
+        Some(t) => (StatusCode::OK, Json(json!({"task": t}))),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Get task priority: GET /tasks/{id}/priority
+pub async fn get_priority(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let task_id =
+        Uuid::parse_str(&id).map_err(|_| (StatusCode::BAD_REQUEST, "invalid UUID".to_string()))?;
+
+    let task = repo
+        .get(&task_id)
+        .ok_or((StatusCode::NOT_FOUND, "task not found".to_string()))?;
+
+    Ok(Json(json!({
+        "id": task.id.to_string(),
+        "priority": task.priority,
+    })))
+}
+
+/// Payload for POST /tasks/{id}/log-time and PUT /tasks/{id}/estimate.
+#[derive(Debug, Deserialize, serde::Serialize, Clone, Default)]
+pub struct MinutesPayload {
+    /// Must be positive; `null` is only accepted by the estimate endpoint, where it clears
+    /// the estimate.
+    pub minutes: Option<u32>,
+}
+
+/// Log time on a task: POST /tasks/{id}/log-time
+/// Accepts `{"minutes": N}` with N > 0 and adds it to `logged_minutes`. Returns
+/// `{"task": ...}`.
+pub async fn log_time(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    Json(payload): Json<MinutesPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!(
+        "log_time called id={} minutes={:?}",
+        id, payload.minutes
+    ));
+    let Ok(uuid) = Uuid::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid uuid"})),
+        );
+    };
+    let Some(minutes) = payload.minutes.filter(|m| *m > 0) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "minutes must be a positive integer"})),
+        );
+    };
+    let result = repo.try_modify(&uuid, |t, _| {
+        t.logged_minutes = t
+            .logged_minutes
+            .checked_add(minutes)
+            .ok_or_else(|| bad_edit("logged time overflow".into()))?;
+        Ok(())
+    });
+    match result {
+        Some(Ok(t)) => (StatusCode::OK, Json(json!({"task": t}))),
+        Some(Err(e)) => e,
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Set or clear a task's estimate: PUT /tasks/{id}/estimate
+/// Accepts `{"minutes": N}` with N > 0, or `{"minutes": null}` to clear it. Returns
+/// `{"task": ...}`.
+pub async fn set_estimate(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    Json(payload): Json<MinutesPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!(
+        "set_estimate called id={} minutes={:?}",
+        id, payload.minutes
+    ));
+    let Ok(uuid) = Uuid::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid uuid"})),
+        );
+    };
+    if payload.minutes == Some(0) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "minutes must be a positive integer"})),
+        );
+    }
+    match repo.modify(&uuid, |t| t.estimate_minutes = payload.minutes) {
+        Some(t) => (StatusCode::OK, Json(json!({"task": t}))),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Payload for PUT /tasks/{id}/color; `null` clears the color.
+#[derive(Debug, Deserialize, serde::Serialize, Clone, Default)]
+pub struct ColorPayload {
+    pub color: Option<String>,
+}
+
+/// Set or clear a task's display color: PUT /tasks/{id}/color
+/// Accepts `{"color": "#rrggbb"}` or `{"color": null}`; other values get 400.
+pub async fn set_color(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    Json(payload): Json<ColorPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("set_color called id={}", id));
+    let Ok(uuid) = Uuid::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid uuid"})),
+        );
+    };
+    if let Some(color) = &payload.color {
+        let mut errors = Vec::new();
+        check_color(color, &mut errors);
+        if !errors.is_empty() {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": join_field_errors(&errors)})),
+            );
+        }
+    }
+    match repo.modify(&uuid, |t| t.color = payload.color) {
+        Some(t) => (StatusCode::OK, Json(json!({"task": t}))),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Payload for POST /tasks/{id}/reminders
+#[derive(Debug, Deserialize, serde::Serialize, Clone, Default)]
+pub struct ReminderPayload {
+    /// RFC 3339 timestamp.
+    pub at: String,
+}
+
+/// Add a reminder: POST /tasks/{id}/reminders
+/// Accepts `{"at": "<rfc3339>"}` and returns `{"task": ...}`; adding a time that is
+/// already set is a no-op. Unparseable timestamps get 400.
+pub async fn add_reminder(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    Json(payload): Json<ReminderPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("add_reminder called id={} at={}", id, payload.at));
+    let Ok(uuid) = Uuid::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid uuid"})),
+        );
+    };
+    let Ok(at) = chrono::DateTime::parse_from_rfc3339(payload.at.trim()) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid reminder timestamp (expected RFC 3339)"})),
+        );
+    };
+    match repo.modify(&uuid, |t| {
+        t.add_reminder(at.with_timezone(&chrono::Utc));
+    }) {
+        Some(t) => (StatusCode::OK, Json(json!({"task": t}))),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// List a task's reminders: GET /tasks/{id}/reminders
+/// Returns `{"reminders": [...]}`, earliest first.
+pub async fn get_reminders(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let Ok(uuid) = Uuid::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid uuid"})),
+        );
+    };
+    match repo.get(&uuid) {
+        Some(t) => (StatusCode::OK, Json(json!({"reminders": t.reminders}))),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Query params for GET /tasks/orphans
+#[derive(Debug, Deserialize, Default)]
+pub struct OrphansQuery {
+    /// Clear the dangling `parent_id` of every orphan found.
+    #[serde(default)]
+    pub fix: bool,
+}
+
+/// Subtasks whose parent is gone: GET /tasks/orphans?fix=true
+/// Returns `{"items": [...], "fixed": N}`, oldest first. Items show the dangling
+/// `parent_id`; with `fix=true` it is cleared on the stored tasks and `fixed` counts them.
+/// Fixing is a mutation, so it is refused with 503 in read-only mode.
+pub async fn get_orphans(
+    State(repo): State<AppState>,
+    Query(q): Query<OrphansQuery>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("get_orphans called fix={}", q.fix));
+    if q.fix && repo.config().read_only {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "read-only mode"})),
+        );
+    }
+    let items = repo.orphans(q.fix);
+    let fixed = if q.fix { items.len() } else { 0 };
+    (
+        StatusCode::OK,
+        Json(json!({"items": items, "fixed": fixed})),
+    )
+}
+
+/// Query params for GET /tasks/reminders/due
+#[derive(Debug, Deserialize, Default)]
+pub struct RemindersDueQuery {
+    /// RFC 3339 cutoff; defaults to now.
+    pub before: Option<String>,
+}
+
+/// Tasks with a reminder due: GET /tasks/reminders/due?before=<rfc3339>
+/// Returns `{"items": [...]}` with every task that has a reminder at or before `before`
+/// (default now, honoring `X-Now` when allowed), earliest reminder first. Meant for a
+/// notifier to poll.
+pub async fn get_due_reminders(
+    State(repo): State<AppState>,
+    headers: HeaderMap,
+    Query(q): Query<RemindersDueQuery>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("get_due_reminders called before={:?}", q.before));
+    let before = match q.before.as_deref() {
+        Some(s) => match chrono::DateTime::parse_from_rfc3339(s) {
+            Ok(dt) => dt.with_timezone(&chrono::Utc),
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": "invalid 'before' timestamp (expected RFC 3339)"})),
+                );
+            }
+        },
+        None => request_now(&repo, &headers),
+    };
+    (
+        StatusCode::OK,
+        Json(json!({"items": repo.list_reminders_due(before)})),
+    )
+}
+
+/// Payload for setting task status
+#[derive(Debug, Deserialize)]
+pub struct StatusPayload {
+    pub status: String,
+}
+
+/// Set task status: PUT /tasks/{id}/status
+/// Accepts `todo`, `in_progress`, `blocked` or `done`; `completed` follows the status.
+/// With `STRICT_SUBTASK_COMPLETION`, `done` is a 409 while any subtask is open.
+pub async fn set_status(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    Json(payload): Json<StatusPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("set_status called id={}", id));
+    let uuid = match Uuid::parse_str(&id) {
+        Ok(u) => u,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "invalid uuid"})),
+            );
+        }
+    };
+
+    let status = match crate::models::task::Status::parse(&payload.status) {
+        Ok(s) => s,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(json!({"error": e}))),
+    };
+    let task = repo.try_modify(&uuid, |t, store| {
+        if status == crate::models::task::Status::Done {
+            check_subtasks_done(&repo, store, t)?;
+        }
+        t.set_status(status);
+        Ok(())
+    });
+    match task {
+        Some(Ok(task)) => (StatusCode::OK, Json(json!({"task": task}))),
+        Some(Err(e)) => e,
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Payload for PUT /tasks/{id}/full; every field is optional.
+#[derive(Debug, Deserialize, serde::Serialize, Clone, Default)]
+pub struct FullUpdatePayload {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub completed: Option<bool>,
+    pub tags: Option<Vec<String>>,
+    pub priority: Option<String>,
+}
+
+/// Update core fields, tags and priority together: PUT /tasks/{id}/full
+/// Tags and priority are validated first; then every provided change is applied under
+/// one repository write lock and the updated task is returned.
+pub async fn update_task_full(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    Json(payload): Json<FullUpdatePayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("update_task_full called id={}", id));
+    let uuid = match Uuid::parse_str(&id) {
+        Ok(u) => u,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "invalid uuid"})),
+            );
+        }
+    };
+
+    let tags = match &payload.tags {
+        Some(tags) => {
+            if let Err(e) = check_tag_count(tags).and_then(|_| validate_tags(tags, repo.config())) {
+                return (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
+            }
+            Some(normalize_tags(tags, repo.config().tag_normalization))
+        }
+        None => None,
+    };
+    let priority = match payload.priority.as_deref().map(Priority::parse) {
+        Some(Err(e)) => return (StatusCode::BAD_REQUEST, Json(json!({"error": e}))),
+        Some(Ok(p)) => Some(p),
+        None => None,
+    };
+    let completes = payload.completed == Some(true);
+    let upd = TaskUpdate {
+        title: payload.title,
+        description: payload.description,
+        completed: payload.completed,
+        color: None,
+    };
+    let updated = repo.try_modify(&uuid, |t, store| {
+        if completes {
+            check_subtasks_done(&repo, store, t)?;
+        }
+        t.apply_update(upd);
+        if let Some(tags) = tags {
+            t.tags = tags;
+        }
+        if let Some(p) = priority {
+            t.priority = p;
+        }
+        check_critical_description(&repo, &t.priority, &t.description).map_err(bad_edit)
+    });
+    match updated {
+        Some(Ok(t)) => (StatusCode::OK, Json(json!({"task": t}))),
+        Some(Err(e)) => e,
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Media type accepted by `patch_task`.
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+
+/// Task fields a JSON Patch may touch; `/tags/N` and `/tags/-` address single tags.
+pub const PATCHABLE_FIELDS: [&str; 6] = [
+    "title",
+    "description",
+    "completed",
+    "priority",
+    "tags",
+    "pinned",
+];
+
+/// Apply a JSON Patch (RFC 6902): PATCH /tasks/{id}
+/// Requires `Content-Type: application/json-patch+json` (415 otherwise) and a body
+/// like `[{"op": "replace", "path": "/title", "value": "x"}]`. Supports `add`,
+/// `replace` and `remove`. Paths outside `PATCHABLE_FIELDS` (e.g. `/id`,
+/// `/created_at`), failing ops, and results that are not a valid task get 400; the
+/// task is only stored if every op succeeds.
+pub async fn patch_task(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("patch_task called id={}", id));
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
+    let media_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|ct| ct.split(';').next())
+        .map(|ct| ct.trim().to_lowercase());
+    if media_type.as_deref() != Some(JSON_PATCH_CONTENT_TYPE) {
+        return (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(json!({
+                "error": "unsupported content-type",
+                "accepted": [JSON_PATCH_CONTENT_TYPE],
+            })),
+        );
+    }
+    let Ok(uuid) = Uuid::parse_str(&id) else {
+        return bad_request("invalid uuid".into());
+    };
+    let ops: Vec<PatchOp> = match serde_json::from_slice(&body) {
+        Ok(ops) => ops,
+        Err(e) => return bad_request(format!("invalid patch document: {}", e)),
+    };
+    for op in &ops {
+        let allowed = match op.tokens() {
+            Ok(tokens) => match tokens.as_slice() {
+                [field] => PATCHABLE_FIELDS.contains(&field.as_str()),
+                [field, _] => field == "tags",
+                _ => false,
+            },
+            Err(e) => return bad_request(e),
+        };
+        if !allowed {
+            return bad_request(format!("path not allowed: '{}'", op.path));
+        }
+    }
+    let completes = ops
+        .iter()
+        .any(|op| op.path == "/completed" && op.value == Some(json!(true)));
+
+    let config = repo.config();
+    let res = repo.try_modify(&uuid, |t, store| {
+        if completes {
+            check_subtasks_done(&repo, store, t)?;
+        }
+        let mut doc = serde_json::to_value(&*t).map_err(|e| bad_edit(e.to_string()))?;
+        json_patch::apply(&mut doc, &ops).map_err(bad_edit)?;
+        let patched: Task =
+            serde_json::from_value(doc).map_err(|e| bad_edit(format!("invalid task: {}", e)))?;
+        let upd = TaskUpdate {
+            title: Some(patched.title),
+            description: Some(patched.description),
+            completed: Some(patched.completed),
+            color: None,
+        };
+        upd.validate()
+            .map_err(|e| bad_edit(join_field_errors(&e)))?;
+        check_tag_count(&patched.tags).map_err(bad_edit)?;
+        validate_tags(&patched.tags, config).map_err(bad_edit)?;
+        t.apply_update(upd);
+        t.tags = normalize_tags(&patched.tags, config.tag_normalization);
+        t.priority = patched.priority;
+        t.pinned = patched.pinned;
+        check_critical_description(&repo, &t.priority, &t.description).map_err(bad_edit)
+    });
+    match res {
+        Some(Ok(t)) => (StatusCode::OK, Json(json!({"task": t}))),
+        Some(Err(e)) => e,
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Payload for POST /tasks/{id}/split
+#[derive(Debug, Deserialize, serde::Serialize, Clone)]
+pub struct SplitPayload {
+    pub titles: Vec<String>,
+}
+
+/// Split a task into subtasks: POST /tasks/{id}/split
+/// Creates one task per title copying the parent's tags and priority (with `parent_id`
+/// set), records them in the parent's `subtask_ids`, and returns 201 with `{"parent": ..., "children": [...]}`.
+/// The parent is kept.
+pub async fn split_task(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+    Json(payload): Json<SplitPayload>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!(
+        "split_task called id={} parts={}",
+        id,
+        payload.titles.len()
+    ));
+    let uuid = match Uuid::parse_str(&id) {
+        Ok(u) => u,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "invalid uuid"})),
+            );
+        }
+    };
+    if payload.titles.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "titles must not be empty"})),
+        );
+    }
+    if let Err(e) = check_bulk_len(&repo, payload.titles.len()) {
+        return e;
+    }
+    if payload.titles.iter().any(|t| t.trim().is_empty()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "title must not be empty"})),
+        );
+    }
+
+    match repo.split(&uuid, &payload.titles) {
+        Some((parent, children)) => (
+            StatusCode::CREATED,
+            Json(json!({"parent": parent, "children": children})),
+        ),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Ancestry of a task: GET /tasks/{id}/breadcrumb
+/// Returns `[{id, title}, ...]` from the root ancestor down to the task itself, following
+/// `parent_id`. A task without a parent yields a single entry; a cycle in the links is
+/// reported as 409.
+pub async fn get_breadcrumb(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("get_breadcrumb called id={}", id));
+    let uuid = match Uuid::parse_str(&id) {
+        Ok(u) => u,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "invalid uuid"})),
+            );
+        }
+    };
+    match repo.ancestry(&uuid) {
+        Some(Ok(chain)) => {
+            let crumbs: Vec<serde_json::Value> = chain
+                .iter()
+                .map(|t| json!({"id": t.id, "title": t.title}))
+                .collect();
+            (StatusCode::OK, Json(json!(crumbs)))
+        }
+        Some(Err(ids)) => (
+            StatusCode::CONFLICT,
+            Json(json!({"error": "parent cycle", "ids": ids})),
+        ),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// Query params for GET /tasks/grouped/by_priority
+#[derive(Debug, Deserialize, Default)]
+pub struct GroupedParams {
+    pub completed: Option<bool>,
+}
+
+/// Tasks grouped by priority: GET /tasks/grouped/by_priority?completed=...
+/// Returns `{"critical": [...], "high": [...], "medium": [...], "low": [...]}`; every
+/// bucket is present and sorted by `created_at` descending. Expired tasks are hidden.
+pub async fn get_tasks_grouped_by_priority(
+    State(repo): State<AppState>,
+    Query(params): Query<GroupedParams>,
+) -> Json<serde_json::Value> {
+    log_info(&format!(
+        "get_tasks_grouped_by_priority called params={:?}",
+        params
+    ));
+    let filter = TaskFilter {
+        completed: params.completed,
+        hide_expired_at: Some(chrono::Utc::now()),
+        ..Default::default()
+    };
+    let sort = TaskSort {
+        field: SortField::CreatedAt,
+        desc: true,
+        pinned_first: false,
+    };
+    let tasks = repo.list_paginated(&filter, sort, 1, usize::MAX).items;
+
+    let mut groups = serde_json::Map::new();
+    for (key, p) in [
+        ("critical", Priority::Critical),
+        ("high", Priority::High),
+        ("medium", Priority::Medium),
+        ("low", Priority::Low),
+    ] {
+        let bucket: Vec<&Task> = tasks.iter().filter(|t| t.priority == p).collect();
+        groups.insert(key.to_string(), json!(bucket));
+    }
+    Json(serde_json::Value::Object(groups))
+}
+
+/// Search tasks by priority: GET /tasks/search/by_priority?priority=high
+pub async fn get_tasks_by_priority(
+    State(repo): State<AppState>,
+    Query(params): Query<std::collections::HashMap<String, String>>,
+) -> Result<Json<Vec<Task>>, (StatusCode, String)> {
+    let priority_str = params.get("priority").ok_or((
+        StatusCode::BAD_REQUEST,
+        "missing 'priority' query parameter".to_string(),
     ))?;
 
     let priority = crate::models::task::Priority::parse(priority_str)
@@ -703,4 +2922,474 @@ pub async fn get_tasks_by_priority(
     Ok(Json(filtered))
 }
 
+/// Execution plan: GET /tasks/plan
+/// Returns task ids ordered so each task follows everything it `depends_on`.
+/// Returns 409 with the ids involved if the dependencies form a cycle.
+pub async fn get_plan(State(repo): State<AppState>) -> (StatusCode, Json<serde_json::Value>) {
+    log_info("get_plan called");
+    match repo.topo_order() {
+        Ok(order) => (StatusCode::OK, Json(json!({"order": order}))),
+        Err(ids) => (
+            StatusCode::CONFLICT,
+            Json(json!({"error": "dependency cycle", "ids": ids})),
+        ),
+    }
+}
+
+/// Query params for GET /tasks/changes
+#[derive(Debug, Deserialize)]
+pub struct ChangesQuery {
+    /// RFC 3339 timestamp; only tasks updated strictly after it are returned.
+    /// When absent every task is returned (initial sync).
+    pub since: Option<String>,
+}
+
+/// Incremental sync: GET /tasks/changes?since=<rfc3339>
+/// Returns `{ items, now }` with items sorted by `updated_at` ascending. Clients pass
+/// `now` back as the next `since`. Deletions are not reported, only upserts.
+pub async fn get_changes(
+    State(repo): State<AppState>,
+    Query(q): Query<ChangesQuery>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("get_changes called since={:?}", q.since));
+    let since = match q.since.as_deref() {
+        Some(s) => match chrono::DateTime::parse_from_rfc3339(s) {
+            Ok(dt) => dt.with_timezone(&chrono::Utc),
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": "invalid 'since' timestamp (expected RFC 3339)"})),
+                );
+            }
+        },
+        None => chrono::DateTime::<chrono::Utc>::MIN_UTC,
+    };
+    // capture `now` before reading so nothing updated during the read is skipped next time
+    let now = chrono::Utc::now();
+    let items = repo.list_updated_since(since);
+    (
+        StatusCode::OK,
+        Json(json!({"items": items, "now": now.to_rfc3339()})),
+    )
+}
+
+/// Turn a task into a template: POST /tasks/{id}/convert-to-template
+/// The task is hidden from `GET /tasks` and listed by `GET /tasks/templates` from then on.
+/// Returns `{"task": ...}`; converting a template again is a no-op.
+pub async fn convert_to_template(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("convert_to_template called id={}", id));
+    let Ok(uuid) = Uuid::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid uuid"})),
+        );
+    };
+    match repo.modify(&uuid, |t| t.is_template = true) {
+        Some(t) => (StatusCode::OK, Json(json!({"task": t}))),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "not found"}))),
+    }
+}
+
+/// List templates: GET /tasks/templates
+/// Returns `{"items": [...]}`, oldest first.
+pub async fn get_templates(State(repo): State<AppState>) -> Json<serde_json::Value> {
+    log_info("get_templates called");
+    let filter = TaskFilter {
+        template: Some(true),
+        ..Default::default()
+    };
+    let sort = TaskSort {
+        field: SortField::CreatedAt,
+        desc: false,
+        pinned_first: false,
+    };
+    Json(json!({"items": repo.list_paginated(&filter, sort, 1, usize::MAX).items}))
+}
+
+/// Create a task from a template: POST /tasks/templates/{id}/instantiate
+/// The new task copies the template's title, description, tags and priority and gets a
+/// fresh id and timestamps. Returns 201 with the task, like `POST /tasks`; ids that are
+/// not templates get 404.
+pub async fn instantiate_template(
+    Path(id): Path<String>,
+    State(repo): State<AppState>,
+) -> Result<(StatusCode, Json<Task>), (StatusCode, Json<serde_json::Value>)> {
+    log_info(&format!("instantiate_template called id={}", id));
+    let uuid = Uuid::parse_str(&id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid uuid"})),
+        )
+    })?;
+    let template = repo.get(&uuid).filter(|t| t.is_template).ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({"error": "template not found"})),
+    ))?;
+    let task = repo.insert(template.instantiate()).map_err(repo_error)?;
+    Ok((StatusCode::CREATED, Json(task)))
+}
+
+/// Query params for GET /tasks/activity
+#[derive(Debug, Deserialize, Default)]
+pub struct ActivityParams {
+    pub page: Option<usize>,
+    pub per_page: Option<usize>,
+}
+
+/// Activity feed: GET /tasks/activity?page=&per_page=
+/// Change events across all tasks, oldest first, as a page of
+/// `{type, task_id, at, detail}` entries where `type` is `created`, `updated`, `deleted`
+/// or `tagged`. Deleted tasks still appear. Paging is clamped like `GET /tasks`.
+pub async fn get_activity(
+    State(repo): State<AppState>,
+    Query(params): Query<ActivityParams>,
+) -> Json<serde_json::Value> {
+    log_info(&format!("get_activity called params={:?}", params));
+    let page = params.page.unwrap_or(1).max(1);
+    let per_page = params.per_page.unwrap_or(20).clamp(1, MAX_PER_PAGE);
+    Json(json!(repo.activity_page(page, per_page)))
+}
+
+/// Default and maximum `limit` for GET /tasks/recent.
+pub const DEFAULT_RECENT_LIMIT: usize = 10;
+pub const MAX_RECENT_LIMIT: usize = 100;
+
+/// Query params for GET /tasks/recent
+#[derive(Debug, Deserialize, Default)]
+pub struct RecentParams {
+    pub limit: Option<usize>,
+}
+
+/// Most recently updated tasks: GET /tasks/recent?limit=N
+/// `limit` defaults to 10 and is capped at 100.
+pub async fn get_recent_tasks(
+    State(repo): State<AppState>,
+    Query(params): Query<RecentParams>,
+) -> Json<serde_json::Value> {
+    log_info(&format!("get_recent_tasks called limit={:?}", params.limit));
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_RECENT_LIMIT)
+        .min(MAX_RECENT_LIMIT);
+    Json(json!({"items": repo.recent_updated(limit)}))
+}
+
+/// Full dump: GET /tasks/dump
+/// Every stored task exactly as held in memory (including expired and archived ones),
+/// sorted by `created_at` ascending. Feed the result to `POST /tasks/load` to restore it.
+pub async fn dump_tasks(State(repo): State<AppState>) -> Json<Vec<Task>> {
+    log_info("dump_tasks called");
+    Json(repo.list_sorted_by_created_at(false))
+}
+
+/// Query params for POST /tasks/compact
+#[derive(Debug, Deserialize, Default)]
+pub struct CompactParams {
+    /// Write the file indented instead of compact.
+    #[serde(default)]
+    pub indent: bool,
+}
+
+/// Rewrite the data file: POST /tasks/compact
+/// Replaces `DATA_FILE` with the current in-memory tasks in the `GET /tasks/dump`
+/// format and returns `{"tasks": N, "bytes": B}`. Without `DATA_FILE` the repository is
+/// memory-only and 400 is returned.
+pub async fn compact_tasks(
+    State(repo): State<AppState>,
+    Query(params): Query<CompactParams>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("compact_tasks called indent={}", params.indent));
+    let Some(path) = repo.config().data_file.clone() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "repository is not file-backed"})),
+        );
+    };
+    let written = tokio::task::spawn_blocking(move || {
+        crate::persistence::compact(&repo, &path, params.indent)
+    })
+    .await;
+    match written {
+        Ok(Ok((n, bytes))) => (StatusCode::OK, Json(json!({"tasks": n, "bytes": bytes}))),
+        Ok(Err(e)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("cannot write data file: {}", e)})),
+        ),
+        Err(_) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": "compaction failed"})),
+        ),
+    }
+}
+
+/// Query params for POST /tasks/load
+#[derive(Debug, Deserialize, Default)]
+pub struct LoadParams {
+    /// `replace` (default), `merge` or `skip`.
+    pub mode: Option<String>,
+}
+
+/// Restore a dump: POST /tasks/load?mode=replace|merge|skip
+/// Accepts a JSON array of full tasks (as returned by `GET /tasks/dump`) and stores them
+/// verbatim, keeping ids and timestamps. `replace` wipes the repository first, `merge`
+/// upserts (a loaded task overwrites a stored one with the same id) and `skip` keeps the
+/// stored task on an id collision. Entries that do not parse as tasks are rejected by
+/// the extractor; duplicate ids in the payload return 400.
+/// Returns `{"loaded": N, "skipped": M, "replaced": K}`.
+pub async fn load_tasks(
+    State(repo): State<AppState>,
+    Query(params): Query<LoadParams>,
+    Json(payload): Json<Vec<Task>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!(
+        "load_tasks called tasks={} mode={:?}",
+        payload.len(),
+        params.mode
+    ));
+    let mode = params
+        .mode
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .unwrap_or_else(|| "replace".into());
+    if !matches!(mode.as_str(), "replace" | "merge" | "skip") {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid mode (expected replace, merge or skip)"})),
+        );
+    }
+    let mut seen = std::collections::HashSet::with_capacity(payload.len());
+    if let Some(dup) = payload.iter().find(|t| !seen.insert(t.id)) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("duplicate id: {}", dup.id)})),
+        );
+    }
+    let report = match mode.as_str() {
+        "merge" => repo.merge(payload),
+        "skip" => repo.load_missing(payload),
+        _ => repo.restore(payload),
+    };
+    (StatusCode::OK, Json(json!(report)))
+}
+
+/// Query params for GET /tasks/export
+#[derive(Debug, Deserialize, Default)]
+pub struct ExportParams {
+    /// `json` (default) or `csv`.
+    pub format: Option<String>,
+    pub completed: Option<bool>,
+    /// Maximum number of tasks to emit; also capped by the `EXPORT_MAX` config.
+    pub limit: Option<usize>,
+}
+
+/// Export tasks: GET /tasks/export?format=json|csv
+/// Tasks are filtered by `completed`, sorted by `created_at` ascending, then capped by
+/// `limit`/`EXPORT_MAX`. When the cap drops tasks the response carries `X-Truncated: true`.
+pub async fn export_tasks(
+    State(repo): State<AppState>,
+    Query(params): Query<ExportParams>,
+) -> Response {
+    log_info(&format!("export_tasks called params={:?}", params));
+
+    let format = params.format.as_deref().unwrap_or("json").to_lowercase();
+    if format != "json" && format != "csv" {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "invalid format (expected json or csv)"})),
+        )
+            .into_response();
+    }
+
+    let mut items = repo.list_sorted_by_created_at(false);
+    if let Some(completed_val) = params.completed {
+        items.retain(|t| t.completed == completed_val);
+    }
+
+    let cap = match (params.limit, repo.config().export_max) {
+        (Some(l), Some(m)) => Some(l.min(m)),
+        (l, m) => l.or(m),
+    };
+    let truncated = cap.is_some_and(|c| items.len() > c);
+    if let Some(c) = cap {
+        items.truncate(c);
+    }
+
+    let (content_type, filename, body) = if format == "csv" {
+        ("text/csv", "tasks.csv", csv_export_body(items))
+    } else {
+        ("application/json", "tasks.json", json_export_body(items))
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    if let Ok(v) = HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename)) {
+        headers.insert(header::CONTENT_DISPOSITION, v);
+    }
+    if truncated {
+        headers.insert("x-truncated", HeaderValue::from_static("true"));
+    }
+    (StatusCode::OK, headers, body).into_response()
+}
+
+/// Stream tasks as a JSON array, serializing one task per chunk so the full document
+/// is never held in memory.
+fn json_export_body(items: Vec<Task>) -> Body {
+    let tasks = items.into_iter().enumerate().map(|(i, t)| {
+        let mut chunk = if i == 0 { Vec::new() } else { vec![b','] };
+        serde_json::to_writer(&mut chunk, &t)?;
+        Ok::<_, serde_json::Error>(chunk)
+    });
+    let chunks = std::iter::once(Ok(b"[".to_vec()))
+        .chain(tasks)
+        .chain(std::iter::once(Ok(b"]".to_vec())));
+    Body::from_stream(stream::iter(chunks))
+}
+
+/// Column names of the CSV task export.
+const CSV_EXPORT_HEADER: [&str; 8] = [
+    "id",
+    "title",
+    "description",
+    "completed",
+    "priority",
+    "tags",
+    "created_at",
+    "updated_at",
+];
+
+/// One CSV export row for `t`, matching `CSV_EXPORT_HEADER`. Tags are joined with `;`.
+fn csv_export_record(t: Task) -> [String; 8] {
+    let priority = serde_json::to_value(&t.priority)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+    [
+        t.id.to_string(),
+        t.title,
+        t.description,
+        t.completed.to_string(),
+        priority,
+        t.tags.join(";"),
+        t.created_at.to_rfc3339(),
+        t.updated_at.to_rfc3339(),
+    ]
+}
+
+/// Stream tasks as CSV with a header row, one row per chunk. Tags are joined with `;`.
+fn csv_export_body(items: Vec<Task>) -> Body {
+    let header = CSV_EXPORT_HEADER.map(String::from);
+    let rows = items.into_iter().map(csv_export_record);
+    let chunks = std::iter::once(header).chain(rows).map(|record| {
+        let mut w = csv::Writer::from_writer(Vec::new());
+        w.write_record(&record)?;
+        w.into_inner().map_err(|e| csv::Error::from(e.into_error()))
+    });
+    Body::from_stream(stream::iter(chunks))
+}
+
+/// Task field list: GET /tasks/schema
+/// Returns `[{name, type, required, mutable}, ...]` for every task field, plus `values`
+/// for enums and `items` for arrays. `required` refers to a `POST /tasks` body.
+pub async fn get_schema() -> Json<serde_json::Value> {
+    log_info("get_schema called");
+    Json(json!(crate::models::task::task_schema()))
+}
+
+/// Query params for GET /tasks/search
+#[derive(Debug, Deserialize, Default)]
+pub struct SearchParams {
+    pub q: Option<String>,
+    /// Rank by trigram similarity instead of requiring a substring match.
+    #[serde(default)]
+    pub fuzzy: bool,
+    /// Minimum fuzzy score (0.0-1.0); defaults to `FUZZY_THRESHOLD`.
+    pub threshold: Option<f64>,
+    /// Most items returned; defaults to and is capped at `MAX_PER_PAGE`.
+    pub limit: Option<usize>,
+}
+
+/// Text search: GET /tasks/search?q=...&fuzzy=true
+/// By default returns `{"items": [...]}` with tasks whose title or description contains
+/// `q` (case-insensitive), oldest first. With `fuzzy=true` each task is scored by the
+/// trigram similarity of `q` to its title and to its description (the higher counts);
+/// tasks at or above the threshold are returned best first, each with a `score`.
+/// Expired, archived and template tasks are left out, as in `GET /tasks`. At most `limit`
+/// items are returned (clamped to 1..=`MAX_PER_PAGE`).
+pub async fn search_tasks(
+    State(repo): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("search_tasks called params={:?}", params));
+    let Some(q) = params.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "missing 'q' query parameter"})),
+        );
+    };
+    let threshold = params.threshold.unwrap_or(repo.config().fuzzy_threshold);
+    if !(0.0..=1.0).contains(&threshold) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "threshold must be between 0 and 1"})),
+        );
+    }
+    let filter = list_filter(&repo, &ListParams::default(), chrono::Utc::now());
+    let limit = params.limit.unwrap_or(MAX_PER_PAGE).clamp(1, MAX_PER_PAGE);
+
+    if !params.fuzzy {
+        let needle = q.to_lowercase();
+        let hits = repo.search(&filter, limit, |t| {
+            (t.title.to_lowercase().contains(&needle)
+                || t.description.to_lowercase().contains(&needle))
+            .then_some(0.0)
+        });
+        let items: Vec<Task> = hits.into_iter().map(|(t, _)| t).collect();
+        return (StatusCode::OK, Json(json!({"items": items})));
+    }
+
+    let query = trigrams(q);
+    let hits = repo.search(&filter, limit, |t| {
+        let score = trigram_similarity_to(&query, &t.title)
+            .max(trigram_similarity_to(&query, &t.description));
+        (score > 0.0 && score >= threshold).then_some(score)
+    });
+    let items: Vec<serde_json::Value> = hits
+        .into_iter()
+        .map(|(t, score)| {
+            let mut v = json!(t);
+            v["score"] = json!(score);
+            v
+        })
+        .collect();
+    (StatusCode::OK, Json(json!({"items": items})))
+}
+
+/// Query params for GET /tasks/random
+#[derive(Debug, Deserialize, Default)]
+pub struct RandomParams {
+    pub completed: Option<bool>,
+    /// Optional RNG seed for a reproducible pick.
+    pub seed: Option<u64>,
+}
+
+/// Random sample: GET /tasks/random
+/// Returns one uniformly chosen task (optionally filtered by `completed`), or 404 when none match.
+pub async fn random_task(
+    State(repo): State<AppState>,
+    Query(params): Query<RandomParams>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    log_info(&format!("random_task called params={:?}", params));
+    let picked = match params.seed {
+        Some(seed) => repo.random(params.completed, &mut StdRng::seed_from_u64(seed)),
+        None => repo.random(params.completed, &mut rand::thread_rng()),
+    };
+    match picked {
+        Some(t) => (StatusCode::OK, Json(json!({"task": t}))),
+        None => (StatusCode::NOT_FOUND, Json(json!({"error": "no tasks"}))),
+    }
+}
+
 // unit tests moved to `tests/handler_tests.rs` as integration tests
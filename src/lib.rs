@@ -4,9 +4,14 @@
 //! tests under `tests/` can import `rust_api_hub` and exercise the public
 //! functions and types.
 
+pub mod audit;
+pub mod config;
 pub mod handlers;
+pub mod middleware;
 pub mod models;
+pub mod persistence;
 pub mod routes;
+pub mod seed;
 pub mod utils;
 
 // Optionally re-export commonly used types here in the future.
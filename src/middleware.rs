@@ -0,0 +1,475 @@
+//! Router-level middleware.
+//!
+//! Cross-cutting response/request tweaks live here so handlers keep returning plain
+//! `Json` values.
+
+use crate::audit::AuditEntry;
+use crate::handlers::task_handler::{request_actor, request_now};
+use crate::models::repository::TaskRepository;
+use crate::models::task::{TaskCreate, TaskUpdate};
+use axum::Json;
+use axum::body::{Body, Bytes, HttpBody, to_bytes};
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use futures_util::StreamExt;
+use serde::Serialize;
+use serde_json::json;
+use std::net::{IpAddr, SocketAddr};
+
+/// Re-serialize JSON responses with indentation when the client asks for it via
+/// `?pretty=true` or an `X-Pretty: true` header. Handy when reading output with curl.
+pub async fn pretty_json(req: Request, next: Next) -> Response {
+    let wants_pretty = query_flag(req.uri().query(), "pretty")
+        || req
+            .headers()
+            .get("x-pretty")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(is_truthy);
+
+    let resp = next.run(req).await;
+    if !wants_pretty || !is_json(&resp) {
+        return resp;
+    }
+
+    let (mut parts, body) = resp.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(b) => b,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    let pretty = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|v| serde_json::to_vec_pretty(&v).ok());
+    match pretty {
+        Some(p) => {
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(p))
+        }
+        None => Response::from_parts(parts, Body::from(bytes)),
+    }
+}
+
+/// Largest request body a middleware buffers, matching axum's default `DefaultBodyLimit`.
+pub const REQUEST_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Buffer a request body of at most `limit` bytes. Longer bodies get
+/// `413 {"error": "payload too large"}` without reading the rest; unreadable ones get 400.
+async fn read_limited(body: Body, limit: usize) -> Result<Bytes, Response> {
+    let mut stream = body.into_data_stream();
+    let mut buf = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let Ok(chunk) = chunk else {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "failed to read request body"})),
+            )
+                .into_response());
+        };
+        if buf.len() + chunk.len() > limit {
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(json!({"error": "payload too large"})),
+            )
+                .into_response());
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    Ok(Bytes::from(buf))
+}
+
+/// Reject unknown fields in create/update payloads when the client sends
+/// `X-Strict: true`. Returns 400 with `{"error": "unknown fields", "fields": [...]}`
+/// listing every unexpected key. Without the header payloads stay lenient. Bodies over
+/// `REQUEST_BODY_LIMIT` get 413 as they would without strict mode.
+pub async fn strict_json(req: Request, next: Next) -> Response {
+    let strict = req
+        .headers()
+        .get("x-strict")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(is_truthy);
+    if !strict {
+        return next.run(req).await;
+    }
+    let known = match strict_fields(req.method(), req.uri().path()) {
+        Some(k) => k,
+        None => return next.run(req).await,
+    };
+
+    let (parts, body) = req.into_parts();
+    let bytes = match read_limited(body, REQUEST_BODY_LIMIT).await {
+        Ok(b) => b,
+        Err(resp) => return resp,
+    };
+    // Non-object bodies are left for the handler's own JSON rejection.
+    if let Ok(serde_json::Value::Object(obj)) = serde_json::from_slice(&bytes) {
+        let unknown: Vec<&String> = obj.keys().filter(|k| !known.contains(k)).collect();
+        if !unknown.is_empty() {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "unknown fields", "fields": unknown})),
+            )
+                .into_response();
+        }
+    }
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+/// Accepted payload keys for the routes strict mode covers: `POST /tasks` and `PUT /tasks/{id}`.
+fn strict_fields(method: &Method, path: &str) -> Option<Vec<String>> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, segments.as_slice()) {
+        (&Method::POST, ["tasks"]) => Some(field_names::<TaskCreate>()),
+        (&Method::PUT, ["tasks", _]) => Some(field_names::<TaskUpdate>()),
+        _ => None,
+    }
+}
+
+/// Field names of a DTO, taken from its serialized default value so they track the struct.
+fn field_names<T: Serialize + Default>() -> Vec<String> {
+    match serde_json::to_value(T::default()) {
+        Ok(serde_json::Value::Object(obj)) => obj.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Fail requests whose handler runs longer than `REQUEST_TIMEOUT_MS` with
+/// `408 {"error": "request timeout"}`. The clock stops once the response head is ready,
+/// so streamed bodies such as `GET /tasks/export` are not cut off.
+pub async fn request_timeout(
+    State(repo): State<TaskRepository>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let ms = repo.config().request_timeout_ms;
+    if ms == 0 {
+        return next.run(req).await;
+    }
+    match tokio::time::timeout(std::time::Duration::from_millis(ms), next.run(req)).await {
+        Ok(resp) => resp,
+        Err(_) => (
+            StatusCode::REQUEST_TIMEOUT,
+            Json(json!({"error": "request timeout"})),
+        )
+            .into_response(),
+    }
+}
+
+/// Per-client fixed-window rate limiting enabled by `RATE_LIMIT`. Clients are keyed by
+/// their peer address (the server must be run with
+/// `into_make_service_with_connect_info::<SocketAddr>()`); `X-Forwarded-For` is only
+/// believed when the peer is one of `TRUSTED_PROXIES`. Every
+/// response carries `X-RateLimit-Limit`, `X-RateLimit-Remaining` and `X-RateLimit-Reset`
+/// (epoch seconds); over the limit the request gets
+/// `429 {"error": "rate limit exceeded"}` with `Retry-After` as well.
+pub async fn rate_limit(State(repo): State<TaskRepository>, req: Request, next: Next) -> Response {
+    let config = repo.config();
+    let Some(limit) = config.rate_limit else {
+        return next.run(req).await;
+    };
+    let client = rate_limit_client(&req, &config.trusted_proxies);
+    let window = std::time::Duration::from_secs(config.rate_limit_window_secs.max(1));
+    let status = repo.rate_limit_hit(&client, limit, window);
+    let mut resp = if status.allowed {
+        next.run(req).await
+    } else {
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({"error": "rate limit exceeded"})),
+        )
+            .into_response()
+    };
+    let headers = resp.headers_mut();
+    headers.insert("x-ratelimit-limit", HeaderValue::from(status.limit));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from(status.remaining));
+    headers.insert("x-ratelimit-reset", HeaderValue::from(status.reset));
+    if !status.allowed {
+        headers.insert(header::RETRY_AFTER, HeaderValue::from(status.retry_after));
+    }
+    resp
+}
+
+/// Rate-limit key for a request: the peer address, or behind a trusted proxy the
+/// rightmost `X-Forwarded-For` hop that is not itself a trusted proxy. Requests without
+/// connection info (e.g. served in-process) share one bucket.
+fn rate_limit_client(req: &Request, trusted: &[IpAddr]) -> String {
+    let Some(ConnectInfo(peer)) = req.extensions().get::<ConnectInfo<SocketAddr>>() else {
+        return "unknown".to_string();
+    };
+    let peer = peer.ip();
+    if !trusted.contains(&peer) {
+        return peer.to_string();
+    }
+    let forwarded = req
+        .headers()
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .collect::<Vec<_>>();
+    let mut client = peer;
+    for hop in forwarded.into_iter().rev() {
+        match hop.parse::<IpAddr>() {
+            Ok(ip) => {
+                client = ip;
+                if !trusted.contains(&ip) {
+                    break;
+                }
+            }
+            // anything left of a malformed hop cannot be trusted
+            Err(_) => break,
+        }
+    }
+    client.to_string()
+}
+
+/// Block mutations while `READ_ONLY` is set: POST/PUT/PATCH/DELETE get
+/// `503 {"error": "read-only mode"}`; GET, HEAD and other safe methods pass through.
+pub async fn read_only(State(repo): State<TaskRepository>, req: Request, next: Next) -> Response {
+    let mutating = matches!(
+        *req.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    if repo.config().read_only && mutating {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "read-only mode"})),
+        )
+            .into_response();
+    }
+    next.run(req).await
+}
+
+/// Conditional GET for `GET /tasks`. The ETag hashes the repository version with the
+/// query string (filters, paging, sort) and the inputs of time-based filtering and
+/// `with_sla` annotation, so a
+/// matching `If-None-Match` gets `304 Not Modified` without rebuilding the list.
+pub async fn list_etag(State(repo): State<TaskRepository>, req: Request, next: Next) -> Response {
+    if req.method() != Method::GET || req.uri().path() != "/tasks" {
+        return next.run(req).await;
+    }
+    let etag = list_etag_value(&repo, &req);
+    let matches = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|t| t.trim() == etag || t.trim() == "*"));
+    if matches {
+        let mut resp = StatusCode::NOT_MODIFIED.into_response();
+        if let Ok(v) = header::HeaderValue::from_str(&etag) {
+            resp.headers_mut().insert(header::ETAG, v);
+        }
+        return resp;
+    }
+    let mut resp = next.run(req).await;
+    if resp.status() == StatusCode::OK
+        && let Ok(v) = header::HeaderValue::from_str(&etag)
+    {
+        resp.headers_mut().insert(header::ETAG, v);
+    }
+    resp
+}
+
+fn list_etag_value(repo: &TaskRepository, req: &Request) -> String {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+    let version = repo.version();
+    let mut h = DefaultHasher::new();
+    req.uri().query().unwrap_or("").hash(&mut h);
+    req.headers()
+        .get("x-now")
+        .map(|v| v.as_bytes())
+        .unwrap_or_default()
+        .hash(&mut h);
+    // tasks can expire without a write, which changes the default listing
+    repo.count_expired(chrono::Utc::now()).hash(&mut h);
+    // likewise tasks age past their SLA, which flips `sla_breached` in the body
+    if query_flag(req.uri().query(), "with_sla") {
+        repo.count_sla_breached(request_now(repo, req.headers()))
+            .hash(&mut h);
+    }
+    format!("\"{}-{:016x}\"", version, h.finish())
+}
+
+/// Mirror the number of matching tasks in an `X-Total-Count` header on list-style
+/// reads (`GET /tasks`, `GET /tasks/search/by_tag`, `GET /tasks/grouped/by_priority`)
+/// for clients that read headers instead of the body. The count is the body's `total`
+/// field, or for grouped responses the size of all groups combined.
+pub async fn total_count(req: Request, next: Next) -> Response {
+    let counted = req.method() == Method::GET
+        && matches!(
+            req.uri().path(),
+            "/tasks" | "/tasks/search/by_tag" | "/tasks/grouped/by_priority"
+        );
+    let resp = next.run(req).await;
+    if !counted || resp.status() != StatusCode::OK || !is_json(&resp) {
+        return resp;
+    }
+
+    let (mut parts, body) = resp.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(b) => b,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    if let Ok(serde_json::Value::Object(obj)) = serde_json::from_slice(&bytes) {
+        let total = match obj.get("total").and_then(|t| t.as_u64()) {
+            Some(t) => t as usize,
+            None => obj
+                .values()
+                .filter_map(|v| v.as_array())
+                .map(Vec::len)
+                .sum(),
+        };
+        parts
+            .headers
+            .insert("x-total-count", header::HeaderValue::from(total));
+    }
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// Record successful task mutations in the audit log when `AUDIT_LOG_PATH` is set.
+/// Every change the repository makes while a request runs (creates, updates, deletes and
+/// tag changes, whatever the route) becomes one line once the request succeeds, so new
+/// routes are covered without being listed here. The actor is the trimmed `X-Actor`
+/// header. The file is written off the async workers.
+pub async fn audit_log(State(repo): State<TaskRepository>, req: Request, next: Next) -> Response {
+    let Some(path) = repo.config().audit_log_path.clone() else {
+        return next.run(req).await;
+    };
+    let actor = request_actor(req.headers());
+    let (resp, trail) = crate::audit::collect(next.run(req)).await;
+    if !resp.status().is_success() || trail.is_empty() {
+        return resp;
+    }
+    let entries: Vec<AuditEntry> = trail
+        .into_iter()
+        .map(|(op, id)| AuditEntry::new(op, id, actor.clone()))
+        .collect();
+    if let Err(e) = tokio::task::spawn_blocking(move || crate::audit::append(&path, &entries)).await
+    {
+        log::warn!("audit log writer failed: {}", e);
+    }
+    resp
+}
+
+/// Debug aid enabled by `LOG_BODIES`: log the request and response bodies of
+/// POST/PUT/PATCH/DELETE requests at debug level. Bodies are buffered and re-emitted
+/// unchanged; the logged copy has `LOG_REDACT_FIELDS` values replaced with
+/// `[REDACTED]` and is cut to `LOG_BODY_MAX_BYTES`. Request bodies over the route's
+/// usual limit get 413; responses are only buffered when their size is known and within
+/// `REQUEST_BODY_LIMIT`, so streamed bodies pass through unlogged.
+pub async fn log_bodies(State(repo): State<TaskRepository>, req: Request, next: Next) -> Response {
+    let config = repo.config();
+    let mutating = matches!(
+        *req.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    if !config.log_bodies || !mutating {
+        return next.run(req).await;
+    }
+    let target = format!("{} {}", req.method(), req.uri().path());
+
+    let (parts, body) = req.into_parts();
+    // bulk routes may accept more than the default limit
+    let limit = REQUEST_BODY_LIMIT.max(config.bulk_max_bytes);
+    let bytes = match read_limited(body, limit).await {
+        Ok(b) => b,
+        Err(resp) => return resp,
+    };
+    log::debug!(
+        "request body {}: {}",
+        target,
+        loggable_body(&bytes, &config.log_redact_fields, config.log_body_max_bytes)
+    );
+    let resp = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+
+    let buffered = resp
+        .body()
+        .size_hint()
+        .exact()
+        .is_some_and(|n| n <= REQUEST_BODY_LIMIT as u64);
+    if !buffered {
+        log::debug!(
+            "response body {} {}: (not logged: streamed or too large)",
+            target,
+            resp.status().as_u16()
+        );
+        return resp;
+    }
+    let (parts, body) = resp.into_parts();
+    let bytes = match to_bytes(body, REQUEST_BODY_LIMIT).await {
+        Ok(b) => b,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    log::debug!(
+        "response body {} {}: {}",
+        target,
+        parts.status.as_u16(),
+        loggable_body(&bytes, &config.log_redact_fields, config.log_body_max_bytes)
+    );
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// Render a body for the log: JSON gets `redact` fields masked, anything else is shown
+/// as (lossy) text. The result is cut to at most `max_bytes` on a char boundary.
+fn loggable_body(bytes: &[u8], redact: &[String], max_bytes: usize) -> String {
+    let mut text = match serde_json::from_slice::<serde_json::Value>(bytes) {
+        Ok(mut v) if !redact.is_empty() => {
+            redact_fields(&mut v, redact);
+            v.to_string()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    };
+    if text.len() > max_bytes {
+        let mut end = max_bytes;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let cut = text.len() - end;
+        text.truncate(end);
+        text.push_str(&format!("... ({} bytes truncated)", cut));
+    }
+    text
+}
+
+fn redact_fields(v: &mut serde_json::Value, redact: &[String]) {
+    match v {
+        serde_json::Value::Object(obj) => {
+            for (k, field) in obj.iter_mut() {
+                if redact.contains(k) {
+                    *field = json!("[REDACTED]");
+                } else {
+                    redact_fields(field, redact);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                redact_fields(item, redact);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_json(resp: &Response) -> bool {
+    resp.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"))
+}
+
+/// True if `key` appears in the query string with a truthy value (`true`/`1`).
+fn query_flag(query: Option<&str>, key: &str) -> bool {
+    query.unwrap_or("").split('&').any(|pair| {
+        let mut kv = pair.splitn(2, '=');
+        kv.next() == Some(key) && kv.next().is_some_and(is_truthy)
+    })
+}
+
+fn is_truthy(v: &str) -> bool {
+    matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes")
+}
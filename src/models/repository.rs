@@ -1,95 +1,1439 @@
 //! In-memory task repository.
 //! Uses `parking_lot::RwLock` for simple concurrency (faster and smaller than std::sync).
 
+use crate::config::{AppConfig, OverflowPolicy};
 use crate::models::task::TaskCreate;
-use crate::models::task::{Task, TaskUpdate};
-use parking_lot::RwLock;
-use std::collections::HashMap;
+use crate::models::task::{Priority, TagMeta, Task, TaskUpdate};
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use rand::Rng;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use uuid::Uuid;
 
+/// A write refused because it would break an invariant the repository enforces.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RepoError {
+    #[error("description too long (max {max} chars)")]
+    DescriptionTooLong { max: usize },
+}
+
+/// How many activity events are kept; the oldest are dropped first.
+pub const ACTIVITY_CAPACITY: usize = 10_000;
+
+/// Kind of change recorded in the activity feed.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityType {
+    Created,
+    Updated,
+    Deleted,
+    /// An update that changed the task's tags.
+    Tagged,
+}
+
+/// One entry of the activity feed (`GET /tasks/activity`). Deletions keep the task's
+/// id and title, so they remain visible after the task is gone.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ActivityEvent {
+    #[serde(rename = "type")]
+    pub kind: ActivityType,
+    pub task_id: Uuid,
+    pub at: DateTime<Utc>,
+    /// `{"title": ...}`, or `{"tags": [...]}` for `tagged` events.
+    pub detail: serde_json::Value,
+}
+
+impl ActivityEvent {
+    fn new(kind: ActivityType, task: &Task) -> Self {
+        let detail = match kind {
+            ActivityType::Tagged => serde_json::json!({"tags": task.tags}),
+            _ => serde_json::json!({"title": task.title}),
+        };
+        ActivityEvent {
+            kind,
+            task_id: task.id,
+            at: Utc::now(),
+            detail,
+        }
+    }
+}
+
+/// Read-only view of the stored tasks handed to `try_modify` and `modify_where`
+/// closures, valid while the write lock is held.
+#[derive(Clone, Copy)]
+pub struct StoreView<'a> {
+    tasks: &'a HashMap<Uuid, Task>,
+}
+
+impl<'a> StoreView<'a> {
+    /// The stored tasks in `task`'s `subtask_ids` that are not completed. Subtasks that
+    /// were deleted are skipped.
+    pub fn open_subtasks<'t>(&self, task: &'t Task) -> impl Iterator<Item = &'a Task> + 't
+    where
+        'a: 't,
+    {
+        let tasks = self.tasks;
+        task.subtask_ids
+            .iter()
+            .filter_map(move |c| tasks.get(c))
+            .filter(|c| !c.completed)
+    }
+
+    /// True if any of `task`'s subtasks is still open.
+    pub fn has_open_subtasks(&self, task: &Task) -> bool {
+        self.open_subtasks(task).next().is_some()
+    }
+}
+
+/// Callback run on every newly created task before it is stored.
+pub type InsertHook = Arc<dyn Fn(&mut Task) + Send + Sync>;
+
+/// Tasks plus their secondary indexes, kept behind one lock so they never drift apart.
+/// All writes go through `put`/`take`/`modify`, which maintain the indexes and record
+/// each change in the activity feed and the request's audit trail.
+#[derive(Default)]
+struct Store {
+    tasks: HashMap<Uuid, Task>,
+    /// Lowercased tag -> ids of the tasks carrying it.
+    by_tag: HashMap<String, HashSet<Uuid>>,
+    /// Sequence number -> id, for lookups by `Task::seq`.
+    by_seq: HashMap<u64, Uuid>,
+    /// Normalized tag -> display metadata; independent of which tasks use the tag.
+    tag_meta: HashMap<String, TagMeta>,
+    /// Bumped on every task mutation so derived data (e.g. cached stats) can tell it is stale.
+    version: u64,
+    /// Change events, oldest first, capped at `ACTIVITY_CAPACITY`.
+    activity: VecDeque<ActivityEvent>,
+}
+
+impl Store {
+    /// Insert or replace a task, returning the previous version.
+    fn put(&mut self, task: Task) -> Option<Task> {
+        let id = task.id;
+        let tags = task.tags.clone();
+        self.version += 1;
+        if task.seq != 0 {
+            self.by_seq.insert(task.seq, id);
+        }
+        let kind = match self.tasks.get(&id) {
+            None => ActivityType::Created,
+            Some(prev) if prev.tags != tags => ActivityType::Tagged,
+            Some(_) => ActivityType::Updated,
+        };
+        self.record(ActivityEvent::new(kind, &task));
+        let old = self.tasks.insert(id, task);
+        if let Some(prev) = &old {
+            self.unindex_tags(id, &prev.tags);
+            if self.by_seq.get(&prev.seq) == Some(&id) && self.tasks[&id].seq != prev.seq {
+                self.by_seq.remove(&prev.seq);
+            }
+        }
+        self.index_tags(id, &tags);
+        old
+    }
+
+    /// Remove a task by id.
+    fn take(&mut self, id: &Uuid) -> Option<Task> {
+        let old = self.tasks.remove(id)?;
+        self.version += 1;
+        self.unindex_tags(*id, &old.tags);
+        self.by_seq.remove(&old.seq);
+        self.record(ActivityEvent::new(ActivityType::Deleted, &old));
+        Some(old)
+    }
+
+    /// Mutate a task in place, re-indexing its tags if the closure changed them.
+    /// A change is recorded in the activity feed only if the tags or `updated_at`
+    /// changed, so no-op passes of the bulk helpers leave no trace.
+    fn modify<R>(&mut self, id: &Uuid, f: impl FnOnce(&mut Task) -> R) -> Option<R> {
+        let t = self.tasks.get_mut(id)?;
+        let before = t.tags.clone();
+        let updated_before = t.updated_at;
+        let out = f(t);
+        self.version += 1;
+        let event = if t.tags != before {
+            Some(ActivityEvent::new(ActivityType::Tagged, t))
+        } else if t.updated_at != updated_before {
+            Some(ActivityEvent::new(ActivityType::Updated, t))
+        } else {
+            None
+        };
+        if t.tags != before {
+            let after = t.tags.clone();
+            self.unindex_tags(*id, &before);
+            self.index_tags(*id, &after);
+        }
+        if let Some(e) = event {
+            self.record(e);
+        }
+        Some(out)
+    }
+
+    /// Log a change to the activity feed and note it for `audit::collect`.
+    fn record(&mut self, event: ActivityEvent) {
+        crate::audit::note(event.kind.into(), event.task_id);
+        if self.activity.len() == ACTIVITY_CAPACITY {
+            self.activity.pop_front();
+        }
+        self.activity.push_back(event);
+    }
+
+    fn index_tags(&mut self, id: Uuid, tags: &[String]) {
+        for tag in tags {
+            self.by_tag
+                .entry(tag.to_lowercase())
+                .or_default()
+                .insert(id);
+        }
+    }
+
+    fn unindex_tags(&mut self, id: Uuid, tags: &[String]) {
+        for tag in tags {
+            let key = tag.to_lowercase();
+            if let Some(ids) = self.by_tag.get_mut(&key) {
+                ids.remove(&id);
+                if ids.is_empty() {
+                    self.by_tag.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+/// Which tasks `list_paginated` returns.
+#[derive(Clone, Debug, Default)]
+pub struct TaskFilter {
+    /// Only tasks with this completion state.
+    pub completed: Option<bool>,
+    /// Hide tasks already expired at this instant.
+    pub hide_expired_at: Option<DateTime<Utc>>,
+    /// Only tasks carrying this tag (case-insensitive).
+    pub tag: Option<String>,
+    /// Exclude tasks carrying this tag (case-insensitive).
+    pub not_tag: Option<String>,
+    /// Only tasks whose title starts with this, compared lowercased.
+    pub title_prefix: Option<String>,
+    /// Only tasks with this pinned state.
+    pub pinned: Option<bool>,
+    /// Only tasks created by this actor (exact match).
+    pub created_by: Option<String>,
+    /// Only tasks with this archived state.
+    pub archived: Option<bool>,
+    /// Only tasks with this template state.
+    pub template: Option<bool>,
+    /// Only tasks with this priority.
+    pub priority: Option<Priority>,
+    /// Only tasks created at or after this instant.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only tasks created strictly before this instant.
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+impl TaskFilter {
+    fn matches(&self, t: &Task) -> bool {
+        let has_tag = |tag: &String| t.tags.iter().any(|x| x.eq_ignore_ascii_case(tag));
+        self.completed.is_none_or(|c| t.completed == c)
+            && self.pinned.is_none_or(|p| t.pinned == p)
+            && self.archived.is_none_or(|a| t.archived == a)
+            && self.template.is_none_or(|v| t.is_template == v)
+            && self.priority.as_ref().is_none_or(|p| &t.priority == p)
+            && self.created_after.is_none_or(|a| t.created_at >= a)
+            && self.created_before.is_none_or(|b| t.created_at < b)
+            && self
+                .created_by
+                .as_ref()
+                .is_none_or(|a| t.created_by.as_ref() == Some(a))
+            && self.hide_expired_at.is_none_or(|now| !t.is_expired(now))
+            && self.tag.as_ref().is_none_or(has_tag)
+            && !self.not_tag.as_ref().is_some_and(has_tag)
+            && self
+                .title_prefix
+                .as_ref()
+                .is_none_or(|p| t.title.to_lowercase().starts_with(&p.to_lowercase()))
+    }
+}
+
+/// Field a task listing is ordered by.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortField {
+    #[default]
+    CreatedAt,
+    Priority,
+}
+
+/// Sort order for `list_paginated`. Ties are always broken by ascending id.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TaskSort {
+    pub field: SortField,
+    pub desc: bool,
+    /// Put pinned tasks before unpinned ones, applying `field` within each group.
+    pub pinned_first: bool,
+}
+
+impl TaskSort {
+    /// Parse `field[:asc|:desc]` as used by `GET /tasks?sort=`. Unknown fields fall back
+    /// to ascending `created_at`.
+    pub fn parse(s: &str) -> Self {
+        let desc = s.ends_with(":desc");
+        if s.starts_with("priority") {
+            TaskSort {
+                field: SortField::Priority,
+                desc,
+                pinned_first: false,
+            }
+        } else {
+            TaskSort {
+                field: SortField::CreatedAt,
+                desc: desc && s.starts_with("created_at"),
+                pinned_first: false,
+            }
+        }
+    }
+
+    fn compare(&self, a: &Task, b: &Task) -> std::cmp::Ordering {
+        let ord = match self.field {
+            SortField::CreatedAt => a.created_at.cmp(&b.created_at),
+            SortField::Priority => a.priority.sort_value().cmp(&b.priority.sort_value()),
+        };
+        let ord = if self.desc { ord.reverse() } else { ord };
+        let pinned = if self.pinned_first {
+            b.pinned.cmp(&a.pinned)
+        } else {
+            std::cmp::Ordering::Equal
+        };
+        pinned.then(ord).then(a.id.cmp(&b.id))
+    }
+}
+
+/// One page of a listing plus the total number of matching items.
+#[derive(Clone, Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub page: usize,
+    pub per_page: usize,
+}
+
+/// Last computed stats and the store version they were computed at.
+#[derive(Default)]
+struct StatsCache {
+    entry: Option<(u64, serde_json::Value)>,
+    computations: usize,
+}
+
+/// How many import summaries are remembered for `X-Import-Id` deduplication.
+const IMPORT_LOG_CAPACITY: usize = 256;
+
+/// Summaries of recent imports keyed by client-supplied import id, oldest evicted first,
+/// plus the ids of imports still running.
+#[derive(Default)]
+struct ImportLog {
+    order: VecDeque<String>,
+    summaries: HashMap<String, serde_json::Value>,
+    pending: HashSet<String>,
+}
+
+/// Outcome of `TaskRepository::claim_import`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportClaim {
+    /// The id is now reserved for the caller, who must `remember_import` or
+    /// `release_import` it.
+    Claimed,
+    /// Another import with this id is still running.
+    Pending,
+    /// An import with this id already succeeded; here is its summary.
+    Done(serde_json::Value),
+}
+
+/// Once the debounce map holds this many titles, expired entries are pruned on the next
+/// check.
+const DEBOUNCE_PRUNE_AT: usize = 1024;
+
+/// Once the rate-limit map holds this many clients, expired windows are pruned, at most
+/// once per window.
+const RATE_PRUNE_AT: usize = 1024;
+
+/// Most clients tracked by the rate limiter; once full, new clients share one bucket
+/// until pruning frees room.
+pub const RATE_MAX_CLIENTS: usize = 10_000;
+
+/// Key of the bucket shared by clients that arrive while the rate-limit map is full.
+pub const RATE_OVERFLOW_CLIENT: &str = "overflow";
+
+/// One client's request count in its current rate-limit window.
+struct RateWindow {
+    started: std::time::Instant,
+    count: u32,
+}
+
+/// Rate-limit windows by client, plus when expired ones were last pruned.
+struct RateTable {
+    windows: HashMap<String, RateWindow>,
+    pruned_at: std::time::Instant,
+}
+
+/// Outcome of a rate-limit check: whether the request may proceed and the client's
+/// remaining budget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateStatus {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// When the current window ends, as Unix epoch seconds.
+    pub reset: i64,
+    /// Whole seconds until the window ends.
+    pub retry_after: u64,
+}
+
+/// Outcome of loading a dump into the repository (see `restore`, `merge` and
+/// `load_missing`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct LoadReport {
+    /// Tasks from the dump that are now stored.
+    pub loaded: usize,
+    /// Tasks from the dump that were ignored because their id was already taken.
+    pub skipped: usize,
+    /// Stored tasks that were overwritten by a dump task with the same id.
+    pub replaced: usize,
+}
+
 /// Simple thread-safe repository wrapper
 #[derive(Clone)]
 pub struct TaskRepository {
-    inner: Arc<RwLock<HashMap<Uuid, Task>>>,
+    inner: Arc<RwLock<Store>>,
+    on_insert: Option<InsertHook>,
+    config: Arc<AppConfig>,
+    stats: Arc<Mutex<StatsCache>>,
+    /// Slots for heavy aggregations, sized by `STATS_WORKERS`.
+    heavy: Arc<tokio::sync::Semaphore>,
+    imports: Arc<Mutex<ImportLog>>,
+    /// Normalized title -> when a task with it was last created (`CREATE_DEBOUNCE_MS`).
+    recent_creates: Arc<Mutex<HashMap<String, std::time::Instant>>>,
+    /// Client key -> its current rate-limit window (`RATE_LIMIT`).
+    rate_windows: Arc<Mutex<RateTable>>,
+    /// Last `seq` handed out; sequence numbers start at 1.
+    last_seq: Arc<AtomicU64>,
+    /// When the repository was created, i.e. roughly when the server started.
+    started: std::time::Instant,
 }
 
 impl TaskRepository {
     pub fn new() -> Self {
         TaskRepository {
-            inner: Arc::new(RwLock::new(HashMap::new())),
+            inner: Arc::new(RwLock::new(Store::default())),
+            on_insert: None,
+            config: Arc::new(AppConfig::default()),
+            stats: Arc::new(Mutex::new(StatsCache::default())),
+            heavy: Arc::new(tokio::sync::Semaphore::new(
+                AppConfig::default().stats_workers,
+            )),
+            imports: Arc::new(Mutex::new(ImportLog::default())),
+            recent_creates: Arc::new(Mutex::new(HashMap::new())),
+            rate_windows: Arc::new(Mutex::new(RateTable {
+                windows: HashMap::new(),
+                pruned_at: std::time::Instant::now(),
+            })),
+            last_seq: Arc::new(AtomicU64::new(0)),
+            started: std::time::Instant::now(),
         }
     }
 
-    pub fn insert(&self, task: Task) {
+    /// Attach runtime configuration (see `AppConfig`).
+    pub fn with_config(mut self, config: AppConfig) -> Self {
+        self.heavy = Arc::new(tokio::sync::Semaphore::new(config.stats_workers.max(1)));
+        self.config = Arc::new(config);
+        self
+    }
+
+    /// Wait for a free heavy-aggregation slot; the slot is released when the permit drops.
+    pub async fn heavy_permit(&self) -> tokio::sync::OwnedSemaphorePermit {
+        // the semaphore is never closed
+        self.heavy
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("heavy semaphore closed")
+    }
+
+    /// Time since the repository was created.
+    pub fn uptime(&self) -> std::time::Duration {
+        self.started.elapsed()
+    }
+
+    /// Runtime configuration the handlers consult.
+    pub fn config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    /// Install a hook that can enrich each new task (e.g. derive tags from the title).
+    /// The hook runs for tasks added via `insert`/`insert_many`, but not when `insert`
+    /// overwrites an existing task.
+    pub fn with_insert_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut Task) + Send + Sync + 'static,
+    {
+        self.on_insert = Some(Arc::new(hook));
+        self
+    }
+
+    /// Store a task, replacing any task with the same id. Returns the stored task, or an
+    /// error if its description breaks the configured limit.
+    pub fn insert(&self, mut task: Task) -> Result<Task, RepoError> {
+        self.enforce_description(&mut task.description)?;
         let mut m = self.inner.write();
-        m.insert(task.id, task);
+        match m.tasks.get(&task.id) {
+            // a replacement keeps the stored task's sequence number
+            Some(old) if task.seq == 0 => task.seq = old.seq,
+            Some(_) => {}
+            None => self.prepare_new(&mut task),
+        }
+        m.put(task.clone());
+        Ok(task)
+    }
+
+    /// Store a task only if its id is not taken yet; an existing task is never overwritten.
+    /// Returns the stored copy, or `None` if the id already exists. The insert hook runs
+    /// as for `insert`.
+    pub fn insert_if_absent(&self, mut task: Task) -> Result<Option<Task>, RepoError> {
+        self.enforce_description(&mut task.description)?;
+        let mut m = self.inner.write();
+        if m.tasks.contains_key(&task.id) {
+            return Ok(None);
+        }
+        self.prepare_new(&mut task);
+        m.put(task.clone());
+        Ok(Some(task))
+    }
+
+    /// Apply the configured description limit (`REPO_MAX_DESCRIPTION`): over-length
+    /// descriptions are clipped or rejected according to `DESCRIPTION_OVERFLOW`.
+    fn enforce_description(&self, description: &mut String) -> Result<(), RepoError> {
+        let Some(max) = self.config.repo_max_description else {
+            return Ok(());
+        };
+        let Some((cut, _)) = description.char_indices().nth(max) else {
+            return Ok(());
+        };
+        match self.config.description_overflow {
+            OverflowPolicy::Reject => Err(RepoError::DescriptionTooLong { max }),
+            OverflowPolicy::Truncate => {
+                description.truncate(cut);
+                Ok(())
+            }
+        }
+    }
+
+    /// Assign the next sequence number to a task about to be stored for the first time
+    /// and run the insert hook on it.
+    fn prepare_new(&self, task: &mut Task) {
+        task.seq = self.last_seq.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(hook) = &self.on_insert {
+            hook(task);
+        }
     }
 
     pub fn get(&self, id: &Uuid) -> Option<Task> {
         let m = self.inner.read();
-        m.get(id).cloned()
+        m.tasks.get(id).cloned()
+    }
+
+    /// Look a task up by its per-process sequence number.
+    pub fn get_by_seq(&self, seq: u64) -> Option<Task> {
+        let m = self.inner.read();
+        m.by_seq.get(&seq).and_then(|id| m.tasks.get(id)).cloned()
+    }
+
+    /// Counter bumped on every task mutation (insert, update, remove, tag changes).
+    pub fn version(&self) -> u64 {
+        self.inner.read().version
+    }
+
+    /// Return the cached stats if no task changed since they were computed, otherwise
+    /// run `compute` and cache its result against the current version.
+    pub fn cached_stats(&self, compute: impl FnOnce() -> serde_json::Value) -> serde_json::Value {
+        let mut cache = self.stats.lock();
+        // read the version first: a write racing with `compute` leaves the entry stale
+        let version = self.version();
+        if let Some((v, stats)) = &cache.entry
+            && *v == version
+        {
+            return stats.clone();
+        }
+        let stats = compute();
+        cache.computations += 1;
+        cache.entry = Some((version, stats.clone()));
+        stats
+    }
+
+    /// How many times `cached_stats` had to recompute.
+    pub fn stats_computations(&self) -> usize {
+        self.stats.lock().computations
+    }
+
+    /// Claim a create of a task titled `title` (compared trimmed and lowercased). Returns
+    /// false if the same title was claimed less than `window` ago; otherwise records now
+    /// and returns true. Stale entries are pruned once the map grows large.
+    pub fn debounce_create(&self, title: &str, window: std::time::Duration) -> bool {
+        let key = title.trim().to_lowercase();
+        let now = std::time::Instant::now();
+        let mut recent = self.recent_creates.lock();
+        if recent.len() >= DEBOUNCE_PRUNE_AT {
+            recent.retain(|_, at| now.duration_since(*at) < window);
+        }
+        if recent
+            .get(&key)
+            .is_some_and(|at| now.duration_since(*at) < window)
+        {
+            return false;
+        }
+        recent.insert(key, now);
+        true
+    }
+
+    /// Give back a claim made by `debounce_create` for a create that then failed, so a
+    /// corrected retry with the same title is not turned away.
+    pub fn release_create(&self, title: &str) {
+        self.recent_creates
+            .lock()
+            .remove(&title.trim().to_lowercase());
+    }
+
+    /// Count one request from `client` against a fixed window of `window` allowing
+    /// `limit` requests. Requests over the limit are not counted. At most
+    /// `RATE_MAX_CLIENTS` clients are tracked; new ones beyond that share the
+    /// `RATE_OVERFLOW_CLIENT` bucket.
+    pub fn rate_limit_hit(
+        &self,
+        client: &str,
+        limit: u32,
+        window: std::time::Duration,
+    ) -> RateStatus {
+        let now = std::time::Instant::now();
+        let mut table = self.rate_windows.lock();
+        if table.windows.len() >= RATE_PRUNE_AT && now.duration_since(table.pruned_at) >= window {
+            table
+                .windows
+                .retain(|_, w| now.duration_since(w.started) < window);
+            table.pruned_at = now;
+        }
+        let client =
+            if table.windows.len() >= RATE_MAX_CLIENTS && !table.windows.contains_key(client) {
+                RATE_OVERFLOW_CLIENT
+            } else {
+                client
+            };
+        let w = table
+            .windows
+            .entry(client.to_string())
+            .or_insert(RateWindow {
+                started: now,
+                count: 0,
+            });
+        if now.duration_since(w.started) >= window {
+            *w = RateWindow {
+                started: now,
+                count: 0,
+            };
+        }
+        let allowed = w.count < limit;
+        if allowed {
+            w.count += 1;
+        }
+        let left = window.saturating_sub(now.duration_since(w.started));
+        let retry_after = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+        RateStatus {
+            allowed,
+            limit,
+            remaining: limit - w.count,
+            reset: Utc::now().timestamp() + retry_after as i64,
+            retry_after,
+        }
+    }
+
+    /// Number of clients currently tracked by the rate limiter.
+    pub fn rate_limit_clients(&self) -> usize {
+        self.rate_windows.lock().windows.len()
+    }
+
+    /// Number of titles currently tracked for create debouncing.
+    pub fn debounce_entries(&self) -> usize {
+        self.recent_creates.lock().len()
+    }
+
+    /// Summary of a previous import with this id, if it is still remembered.
+    pub fn import_summary(&self, import_id: &str) -> Option<serde_json::Value> {
+        self.imports.lock().summaries.get(import_id).cloned()
+    }
+
+    /// Reserve `import_id` before running an import, checked under the same lock as the
+    /// remembered summaries so concurrent retries cannot both run.
+    pub fn claim_import(&self, import_id: &str) -> ImportClaim {
+        let mut log = self.imports.lock();
+        if let Some(summary) = log.summaries.get(import_id) {
+            return ImportClaim::Done(summary.clone());
+        }
+        if !log.pending.insert(import_id.to_string()) {
+            return ImportClaim::Pending;
+        }
+        ImportClaim::Claimed
+    }
+
+    /// Drop the reservation of an import that failed, so it can be retried.
+    pub fn release_import(&self, import_id: &str) {
+        self.imports.lock().pending.remove(import_id);
+    }
+
+    /// Remember the summary of a successful import (ending its reservation), evicting the
+    /// oldest entry when full.
+    pub fn remember_import(&self, import_id: String, summary: serde_json::Value) {
+        let mut log = self.imports.lock();
+        log.pending.remove(&import_id);
+        if log.summaries.insert(import_id.clone(), summary).is_none() {
+            log.order.push_back(import_id);
+        }
+        while log.order.len() > IMPORT_LOG_CAPACITY {
+            if let Some(old) = log.order.pop_front() {
+                log.summaries.remove(&old);
+            }
+        }
+    }
+
+    /// True if a task with `id` is stored.
+    pub fn contains(&self, id: &Uuid) -> bool {
+        self.inner.read().tasks.contains_key(id)
+    }
+
+    /// ETag of the stored task, if present, without cloning it.
+    pub fn etag(&self, id: &Uuid) -> Option<String> {
+        self.inner.read().tasks.get(id).map(Task::etag)
+    }
+
+    /// Like `etag`, but `None` for a task that has expired at `at`.
+    pub fn etag_unexpired(&self, id: &Uuid, at: DateTime<Utc>) -> Option<String> {
+        self.inner
+            .read()
+            .tasks
+            .get(id)
+            .filter(|t| !t.is_expired(at))
+            .map(Task::etag)
     }
 
     pub fn list(&self) -> Vec<Task> {
         let m = self.inner.read();
-        m.values().cloned().collect()
+        m.tasks.values().cloned().collect()
+    }
+
+    /// Filter, sort and slice tasks under one read lock, cloning only the requested page.
+    /// `page` is 1-based (0 is treated as 1).
+    pub fn list_paginated(
+        &self,
+        filter: &TaskFilter,
+        sort: TaskSort,
+        page: usize,
+        per_page: usize,
+    ) -> Page<Task> {
+        let page = page.max(1);
+        let m = self.inner.read();
+        let mut matched: Vec<&Task> = m.tasks.values().filter(|t| filter.matches(t)).collect();
+        matched.sort_by(|a, b| sort.compare(a, b));
+        let total = matched.len();
+        let items = matched
+            .into_iter()
+            .skip(per_page.saturating_mul(page - 1))
+            .take(per_page)
+            .cloned()
+            .collect();
+        Page {
+            items,
+            total,
+            page,
+            per_page,
+        }
+    }
+
+    /// One page of the activity feed, oldest event first.
+    pub fn activity_page(&self, page: usize, per_page: usize) -> Page<ActivityEvent> {
+        let page = page.max(1);
+        let m = self.inner.read();
+        let items = m
+            .activity
+            .iter()
+            .skip(per_page.saturating_mul(page - 1))
+            .take(per_page)
+            .cloned()
+            .collect();
+        Page {
+            items,
+            total: m.activity.len(),
+            page,
+            per_page,
+        }
+    }
+
+    /// Return tasks carrying `tag` (case-insensitive), oldest first.
+    /// Resolved through the tag index rather than scanning every task.
+    pub fn list_by_tag(&self, tag: &str) -> Vec<Task> {
+        let m = self.inner.read();
+        let mut items: Vec<Task> = m
+            .by_tag
+            .get(&tag.to_lowercase())
+            .into_iter()
+            .flatten()
+            .filter_map(|id| m.tasks.get(id).cloned())
+            .collect();
+        items.sort_by_key(|t| (t.created_at, t.id));
+        items
+    }
+
+    /// Return tasks with any tag containing `needle` (case-insensitive), oldest first.
+    /// Scans the tag index keys rather than every task.
+    pub fn list_by_tag_substring(&self, needle: &str) -> Vec<Task> {
+        let needle = needle.to_lowercase();
+        let m = self.inner.read();
+        let ids: HashSet<Uuid> = m
+            .by_tag
+            .iter()
+            .filter(|(tag, _)| tag.contains(&needle))
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect();
+        let mut items: Vec<Task> = ids
+            .iter()
+            .filter_map(|id| m.tasks.get(id).cloned())
+            .collect();
+        items.sort_by_key(|t| (t.created_at, t.id));
+        items
+    }
+
+    /// Pick one task uniformly at random, optionally restricted by `completed`.
+    /// Candidates are ordered by (`created_at`, id) before indexing so a seeded RNG
+    /// gives the same pick for the same data.
+    pub fn random<R: Rng>(&self, completed: Option<bool>, rng: &mut R) -> Option<Task> {
+        let m = self.inner.read();
+        let mut candidates: Vec<&Task> = m
+            .tasks
+            .values()
+            .filter(|t| completed.is_none_or(|c| t.completed == c))
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        candidates.sort_by_key(|t| (t.created_at, t.id));
+        let idx = rng.gen_range(0..candidates.len());
+        Some(candidates[idx].clone())
     }
 
     /// Return tasks sorted by `created_at`. If `desc` is true, newest first.
+    /// Tasks sharing a timestamp are always ordered by ascending id so paging is stable.
     pub fn list_sorted_by_created_at(&self, desc: bool) -> Vec<Task> {
         let mut items = self.list();
         if desc {
-            items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            items.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
         } else {
-            items.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+            items.sort_by_key(|t| (t.created_at, t.id));
         }
         items
     }
 
-    pub fn update(&self, id: &Uuid, upd: TaskUpdate) -> Option<Task> {
+    /// Return tasks whose `updated_at` is strictly after `since`, oldest update first.
+    pub fn list_updated_since(&self, since: DateTime<Utc>) -> Vec<Task> {
+        let m = self.inner.read();
+        let mut items: Vec<Task> = m
+            .tasks
+            .values()
+            .filter(|t| t.updated_at > since)
+            .cloned()
+            .collect();
+        items.sort_by_key(|t| (t.updated_at, t.id));
+        items
+    }
+
+    /// Return the `n` most recently updated tasks, newest first (ties by ascending id).
+    /// Only ids are held in a bounded heap while scanning; just the winners are cloned.
+    pub fn recent_updated(&self, n: usize) -> Vec<Task> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let m = self.inner.read();
+        // min-heap on (updated_at, Reverse(id)) so the weakest candidate is evicted first
+        let mut heap: BinaryHeap<Reverse<(DateTime<Utc>, Reverse<Uuid>)>> =
+            BinaryHeap::with_capacity(n + 1);
+        for t in m.tasks.values() {
+            heap.push(Reverse((t.updated_at, Reverse(t.id))));
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((_, Reverse(id)))| m.tasks[&id].clone())
+            .collect()
+    }
+
+    /// Tasks with at least one reminder at or before `at`, earliest reminder first
+    /// (ties by id).
+    pub fn list_reminders_due(&self, at: DateTime<Utc>) -> Vec<Task> {
+        let m = self.inner.read();
+        let mut out: Vec<Task> = m
+            .tasks
+            .values()
+            .filter(|t| t.has_reminder_due(at))
+            .cloned()
+            .collect();
+        out.sort_by_key(|t| (t.reminders.iter().min().copied(), t.id));
+        out
+    }
+
+    /// Apply `upd` to a task. Returns `Ok(None)` if the task does not exist.
+    pub fn update(&self, id: &Uuid, mut upd: TaskUpdate) -> Result<Option<Task>, RepoError> {
+        if let Some(d) = upd.description.as_mut() {
+            self.enforce_description(d)?;
+        }
         let mut m = self.inner.write();
-        if let Some(t) = m.get_mut(id) {
-            let updated = t.apply_update(upd);
-            Some(updated)
-        } else {
-            None
+        Ok(m.modify(id, |t| t.apply_update(upd)))
+    }
+
+    /// Apply `f` to a task under the write lock and return the updated task, or `None`
+    /// if it does not exist. `updated_at` is bumped after the closure runs. A changed
+    /// description is clipped under the truncate policy; one the reject policy refuses is
+    /// put back, so use `try_modify` for edits that may set a description.
+    pub fn modify<F: FnOnce(&mut Task)>(&self, id: &Uuid, f: F) -> Option<Task> {
+        let mut m = self.inner.write();
+        m.modify(id, |t| {
+            let before = t.description.clone();
+            f(t);
+            if t.description != before && self.enforce_description(&mut t.description).is_err() {
+                t.description = before;
+            }
+            t.updated_at = Utc::now();
+            t.clone()
+        })
+    }
+
+    /// Like `modify`, but `f` may reject the change: it runs on a copy and the task is
+    /// only replaced (with `updated_at` bumped) when `f` returns `Ok`. `f` also gets a
+    /// view of the stored tasks, so checks against other tasks happen under the same lock.
+    /// A description changed by `f` is held to the configured limit afterwards.
+    /// Returns `None` if the task does not exist.
+    pub fn try_modify<E: From<RepoError>>(
+        &self,
+        id: &Uuid,
+        f: impl FnOnce(&mut Task, StoreView<'_>) -> Result<(), E>,
+    ) -> Option<Result<Task, E>> {
+        let mut m = self.inner.write();
+        let old = m.tasks.get(id)?;
+        let mut t = old.clone();
+        if let Err(e) = f(&mut t, StoreView { tasks: &m.tasks }) {
+            return Some(Err(e));
         }
+        if t.description != old.description
+            && let Err(e) = self.enforce_description(&mut t.description)
+        {
+            return Some(Err(e.into()));
+        }
+        t.updated_at = Utc::now();
+        m.put(t.clone());
+        Some(Ok(t))
+    }
+
+    /// Apply `f` to every task matching `pred` under a single write lock. All changes are
+    /// computed on copies first (with a view of the stored tasks as they were), so if `f`
+    /// rejects any task, or a changed description breaks the configured limit, nothing
+    /// is stored.
+    /// Returns how many tasks were updated (each gets `updated_at` bumped).
+    pub fn modify_where<E: From<RepoError>>(
+        &self,
+        pred: impl Fn(&Task) -> bool,
+        mut f: impl FnMut(&mut Task, StoreView<'_>) -> Result<(), E>,
+    ) -> Result<usize, E> {
+        let mut m = self.inner.write();
+        let mut changed = Vec::new();
+        for old in m.tasks.values().filter(|t| pred(t)) {
+            let mut t = old.clone();
+            f(&mut t, StoreView { tasks: &m.tasks })?;
+            if t.description != old.description {
+                self.enforce_description(&mut t.description)?;
+            }
+            t.updated_at = Utc::now();
+            changed.push(t);
+        }
+        let n = changed.len();
+        for t in changed {
+            m.put(t);
+        }
+        Ok(n)
     }
 
     pub fn remove(&self, id: &Uuid) -> bool {
         let mut m = self.inner.write();
-        m.remove(id).is_some()
+        m.take(id).is_some()
+    }
+
+    /// Remove a task only if `pred` holds for it, checked under the same write lock.
+    /// Returns `None` if the task does not exist, else whether it was removed.
+    pub fn remove_if(&self, id: &Uuid, pred: impl FnOnce(&Task) -> bool) -> Option<bool> {
+        let mut m = self.inner.write();
+        if !pred(m.tasks.get(id)?) {
+            return Some(false);
+        }
+        m.take(id);
+        Some(true)
+    }
+
+    /// Remove and return every task under one write lock, leaving the repository empty.
+    /// Tag metadata is kept.
+    pub fn drain(&self) -> Vec<Task> {
+        let mut m = self.inner.write();
+        m.by_tag.clear();
+        m.by_seq.clear();
+        m.version += 1;
+        let tasks: Vec<Task> = m.tasks.drain().map(|(_, t)| t).collect();
+        for t in &tasks {
+            m.record(ActivityEvent::new(ActivityType::Deleted, t));
+        }
+        tasks
+    }
+
+    /// Replace every stored task with `tasks`, exactly as given (ids, timestamps, `seq`),
+    /// under one write lock. Unlike `insert` this neither runs the insert hook nor
+    /// applies the description limit; it is meant for restoring a `GET /tasks/dump`.
+    /// Tasks without a `seq`, or repeating one an earlier task in the dump already took, get
+    /// a fresh one, as in `load_into`. Tag metadata is kept; the activity feed
+    /// starts over. `replaced` counts dump tasks whose id was stored before.
+    pub fn restore(&self, tasks: Vec<Task>) -> LoadReport {
+        let mut m = self.inner.write();
+        let replaced = tasks.iter().filter(|t| m.tasks.contains_key(&t.id)).count();
+        // the feed starts over below, so the wipe only shows up in the audit trail
+        for id in m.tasks.keys() {
+            crate::audit::note(crate::audit::AuditOp::Delete, *id);
+        }
+        m.tasks.clear();
+        m.by_tag.clear();
+        m.by_seq.clear();
+        m.tasks.reserve(tasks.len());
+        let max_seq = tasks.iter().map(|t| t.seq).max().unwrap_or(0);
+        self.last_seq.fetch_max(max_seq, Ordering::Relaxed);
+        for mut t in tasks {
+            let seq_taken = m.by_seq.get(&t.seq).is_some_and(|id| *id != t.id);
+            if t.seq == 0 || seq_taken {
+                t.seq = self.last_seq.fetch_add(1, Ordering::Relaxed) + 1;
+            }
+            m.put(t);
+        }
+        m.version += 1;
+        m.activity.clear();
+        LoadReport {
+            loaded: m.tasks.len(),
+            skipped: 0,
+            replaced,
+        }
+    }
+
+    /// Upsert every task from a dump under one write lock: new ids are added and existing
+    /// ones overwritten verbatim. Other stored tasks are kept.
+    pub fn merge(&self, tasks: Vec<Task>) -> LoadReport {
+        self.load_into(tasks, true)
+    }
+
+    /// Add the tasks from a dump whose ids are not stored yet, under one write lock;
+    /// stored tasks win on collision.
+    pub fn load_missing(&self, tasks: Vec<Task>) -> LoadReport {
+        self.load_into(tasks, false)
+    }
+
+    /// Shared body of `merge` and `load_missing`. A task keeps its `seq` unless it is
+    /// unset or already used by another stored task, in which case it gets a fresh one.
+    fn load_into(&self, tasks: Vec<Task>, overwrite: bool) -> LoadReport {
+        let mut m = self.inner.write();
+        let mut report = LoadReport::default();
+        for mut t in tasks {
+            let exists = m.tasks.contains_key(&t.id);
+            if exists && !overwrite {
+                report.skipped += 1;
+                continue;
+            }
+            let seq_taken = m.by_seq.get(&t.seq).is_some_and(|id| *id != t.id);
+            if t.seq == 0 || seq_taken {
+                t.seq = self.last_seq.fetch_add(1, Ordering::Relaxed) + 1;
+            } else {
+                self.last_seq.fetch_max(t.seq, Ordering::Relaxed);
+            }
+            m.put(t);
+            report.loaded += 1;
+            if exists {
+                report.replaced += 1;
+            }
+        }
+        report
+    }
+
+    /// Remove every task whose `expires_at` is at or before `now`. Returns how many were removed.
+    pub fn remove_expired(&self, now: DateTime<Utc>) -> usize {
+        let mut m = self.inner.write();
+        let expired: Vec<Uuid> = m
+            .tasks
+            .values()
+            .filter(|t| t.is_expired(now))
+            .map(|t| t.id)
+            .collect();
+        for id in expired.iter() {
+            m.take(id);
+        }
+        expired.len()
+    }
+
+    /// Number of tasks whose `expires_at` is at or before `now`.
+    pub fn count_expired(&self, now: DateTime<Utc>) -> usize {
+        let m = self.inner.read();
+        m.tasks.values().filter(|t| t.is_expired(now)).count()
+    }
+
+    /// Number of tasks past their SLA (per the configured policy) at `now`.
+    pub fn count_sla_breached(&self, now: DateTime<Utc>) -> usize {
+        let sla = &self.config.sla;
+        let m = self.inner.read();
+        m.tasks.values().filter(|t| sla.is_breached(t, now)).count()
+    }
+
+    /// The first `limit` tasks matching `filter` that `score` accepts, best score first
+    /// (ties oldest first). Scoring runs under one read lock and only the returned tasks
+    /// are cloned.
+    pub fn search(
+        &self,
+        filter: &TaskFilter,
+        limit: usize,
+        score: impl Fn(&Task) -> Option<f64>,
+    ) -> Vec<(Task, f64)> {
+        let m = self.inner.read();
+        let mut hits: Vec<(&Task, f64)> = m
+            .tasks
+            .values()
+            .filter(|t| filter.matches(t))
+            .filter_map(|t| score(t).map(|s| (t, s)))
+            .collect();
+        hits.sort_by(|(a, sa), (b, sb)| {
+            sb.total_cmp(sa)
+                .then(a.created_at.cmp(&b.created_at))
+                .then(a.id.cmp(&b.id))
+        });
+        hits.into_iter()
+            .take(limit)
+            .map(|(t, s)| (t.clone(), s))
+            .collect()
+    }
+
+    /// Count tasks matching `filter` without cloning any of them.
+    pub fn count_where(&self, filter: &TaskFilter) -> usize {
+        let m = self.inner.read();
+        m.tasks.values().filter(|t| filter.matches(t)).count()
     }
 
     /// Return the number of tasks currently stored.
     pub fn count(&self) -> usize {
         let m = self.inner.read();
-        m.len()
+        m.tasks.len()
     }
 
     /// Remove multiple tasks by id. Returns the number of tasks removed.
     pub fn remove_many(&self, ids: &[Uuid]) -> usize {
+        self.remove_many_ids(ids).len()
+    }
+
+    /// Remove multiple tasks by id under one write lock, returning the ids that matched.
+    pub fn remove_many_ids(&self, ids: &[Uuid]) -> Vec<Uuid> {
+        let mut m = self.inner.write();
+        ids.iter()
+            .filter(|id| m.take(id).is_some())
+            .copied()
+            .collect()
+    }
+
+    /// Insert many TaskCreate objects and return the created Task objects.
+    /// All rows are stored under a single write lock, with map capacity reserved up front.
+    /// If any description breaks the configured limit under the reject policy, nothing
+    /// is stored.
+    pub fn insert_many(&self, creates: &[TaskCreate]) -> Result<Vec<Task>, RepoError> {
+        let mut tasks = Vec::with_capacity(creates.len());
+        for c in creates {
+            let mut t = Task::from_create(c);
+            self.enforce_description(&mut t.description)?;
+            tasks.push(t);
+        }
+        let mut m = self.inner.write();
+        m.tasks.reserve(tasks.len());
+        for t in tasks.iter_mut() {
+            self.prepare_new(t);
+            m.put(t.clone());
+        }
+        Ok(tasks)
+    }
+
+    /// Remove `tags` (matched case-insensitively) from each task in `ids` under a single
+    /// write lock. Returns how many tasks actually lost a tag; only those get `updated_at` bumped.
+    pub fn remove_tags_many(&self, ids: &[Uuid], tags: &[String]) -> usize {
+        let wanted: HashSet<String> = tags.iter().map(|t| t.to_lowercase()).collect();
         let mut m = self.inner.write();
-        let mut removed = 0usize;
+        let mut changed = 0usize;
         for id in ids {
-            if m.remove(id).is_some() {
-                removed += 1;
+            let hit = m.modify(id, |t| {
+                let before = t.tags.len();
+                t.tags.retain(|x| !wanted.contains(&x.to_lowercase()));
+                if t.tags.len() != before {
+                    t.updated_at = Utc::now();
+                    true
+                } else {
+                    false
+                }
+            });
+            if hit == Some(true) {
+                changed += 1;
             }
         }
-        removed
+        changed
     }
 
-    /// Insert many TaskCreate objects and return the created Task objects.
-    pub fn insert_many(&self, creates: &[TaskCreate]) -> Vec<Task> {
-        let mut created = Vec::with_capacity(creates.len());
+    /// Replace the tag set of each task in `ids` with `tags` under a single write lock.
+    /// Unknown ids are skipped. Returns how many tasks were updated (each gets
+    /// `updated_at` bumped).
+    pub fn set_tags_many(&self, ids: &[Uuid], tags: &[String]) -> usize {
         let mut m = self.inner.write();
-        for c in creates {
-            let t = Task::new_full(&c.title, &c.description);
-            m.insert(t.id, t.clone());
-            created.push(t);
+        let mut updated = 0usize;
+        for id in ids {
+            let hit = m.modify(id, |t| {
+                t.tags = tags.to_vec();
+                t.updated_at = Utc::now();
+            });
+            if hit.is_some() {
+                updated += 1;
+            }
+        }
+        updated
+    }
+
+    /// Remove every tag for which `pred` holds from all tasks under a single write lock.
+    /// Returns `(tasks changed, tags removed)`; only changed tasks get `updated_at` bumped.
+    pub fn remove_tags_where(&self, pred: impl Fn(&str) -> bool) -> (usize, usize) {
+        let mut m = self.inner.write();
+        let ids: Vec<Uuid> = m.tasks.keys().copied().collect();
+        let (mut changed, mut removed) = (0usize, 0usize);
+        for id in ids.iter() {
+            let n = m
+                .modify(id, |t| {
+                    let before = t.tags.len();
+                    t.tags.retain(|x| !pred(x));
+                    let n = before - t.tags.len();
+                    if n > 0 {
+                        t.updated_at = Utc::now();
+                    }
+                    n
+                })
+                .unwrap_or(0);
+            if n > 0 {
+                changed += 1;
+                removed += n;
+            }
+        }
+        (changed, removed)
+    }
+
+    /// Set `archived` on every listed task under a single write lock. Unknown ids are
+    /// skipped. Returns how many tasks changed state (each gets `updated_at` bumped).
+    pub fn set_archived_many(&self, ids: &[Uuid], archived: bool) -> usize {
+        let mut m = self.inner.write();
+        let mut changed = 0usize;
+        for id in ids {
+            let hit = m.modify(id, |t| {
+                if t.archived == archived {
+                    return false;
+                }
+                t.archived = archived;
+                t.updated_at = Utc::now();
+                true
+            });
+            if hit == Some(true) {
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Replace every task's tags with `f(tags)` under a single write lock. Returns how many
+    /// tasks' tags actually changed; only those get `updated_at` bumped.
+    pub fn rewrite_tags(&self, f: impl Fn(&[String]) -> Vec<String>) -> usize {
+        let mut m = self.inner.write();
+        let ids: Vec<Uuid> = m.tasks.keys().copied().collect();
+        let mut changed = 0usize;
+        for id in ids.iter() {
+            let hit = m.modify(id, |t| {
+                let tags = f(&t.tags);
+                if tags == t.tags {
+                    return false;
+                }
+                t.tags = tags;
+                t.updated_at = Utc::now();
+                true
+            });
+            if hit == Some(true) {
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Store metadata for a normalized tag, replacing any previous value.
+    pub fn set_tag_meta(&self, tag: &str, meta: TagMeta) {
+        let mut m = self.inner.write();
+        m.tag_meta.insert(tag.to_string(), meta);
+    }
+
+    /// Metadata stored for a normalized tag, if any.
+    pub fn tag_meta(&self, tag: &str) -> Option<TagMeta> {
+        let m = self.inner.read();
+        m.tag_meta.get(tag).cloned()
+    }
+
+    /// Create one subtask per title under a single write lock. Each child copies the
+    /// parent's tags and priority, points back via `parent_id`, and its id is appended
+    /// to the parent's `subtask_ids`.
+    /// Returns the updated parent and the children, or `None` if the parent is missing.
+    pub fn split(&self, id: &Uuid, titles: &[String]) -> Option<(Task, Vec<Task>)> {
+        let mut m = self.inner.write();
+        let parent = m.tasks.get(id)?.clone();
+        let mut children = Vec::with_capacity(titles.len());
+        for title in titles {
+            let mut child = Task::new_full(title, "");
+            child.tags = parent.tags.clone();
+            child.priority = parent.priority.clone();
+            child.parent_id = Some(parent.id);
+            self.prepare_new(&mut child);
+            m.put(child.clone());
+            children.push(child);
+        }
+        let parent = m.modify(id, |t| {
+            t.subtask_ids.extend(children.iter().map(|c| c.id));
+            t.updated_at = Utc::now();
+            t.clone()
+        })?;
+        Some((parent, children))
+    }
+
+    /// True if any stored task in `id`'s `subtask_ids` is not completed. Subtasks that
+    /// were deleted are ignored.
+    pub fn has_open_subtasks(&self, id: &Uuid) -> bool {
+        let m = self.inner.read();
+        m.tasks
+            .get(id)
+            .is_some_and(|t| StoreView { tasks: &m.tasks }.has_open_subtasks(t))
+    }
+
+    /// Follow `parent_id` links from `id` up to the root and return the chain root first,
+    /// ending with the task itself. A link to a task that is no longer stored ends the chain.
+    /// Returns `None` if `id` is missing and `Err` with the ids visited if the links loop.
+    pub fn ancestry(&self, id: &Uuid) -> Option<Result<Vec<Task>, Vec<Uuid>>> {
+        let m = self.inner.read();
+        let mut chain = vec![m.tasks.get(id)?.clone()];
+        let mut seen: HashSet<Uuid> = HashSet::from([*id]);
+        while let Some(parent_id) = chain.last().and_then(|t| t.parent_id) {
+            if !seen.insert(parent_id) {
+                return Some(Err(chain.iter().map(|t| t.id).collect()));
+            }
+            match m.tasks.get(&parent_id) {
+                Some(p) => chain.push(p.clone()),
+                None => break,
+            }
+        }
+        chain.reverse();
+        Some(Ok(chain))
+    }
+
+    /// Tasks whose `parent_id` names a task that is no longer stored, oldest first.
+    /// With `fix` the dangling `parent_id` is cleared (bumping `updated_at`) under the same
+    /// write lock, and the tasks are returned as they were before the fix.
+    pub fn orphans(&self, fix: bool) -> Vec<Task> {
+        let mut m = self.inner.write();
+        let mut out: Vec<Task> = m
+            .tasks
+            .values()
+            .filter(|t| t.parent_id.is_some_and(|p| !m.tasks.contains_key(&p)))
+            .cloned()
+            .collect();
+        out.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        if fix {
+            for t in &out {
+                m.modify(&t.id, |t| {
+                    t.parent_id = None;
+                    t.updated_at = Utc::now();
+                });
+            }
+        }
+        out
+    }
+
+    /// Order task ids so every task comes after the tasks it `depends_on` (Kahn's algorithm).
+    /// Ready tasks are emitted oldest first (by `created_at`, then id) so the plan is stable.
+    /// Dependencies on ids that are not stored are ignored.
+    /// Returns `Err` with the ids involved in a cycle if no valid order exists.
+    pub fn topo_order(&self) -> Result<Vec<Uuid>, Vec<Uuid>> {
+        let store = self.inner.read();
+        let m = &store.tasks;
+
+        let mut in_degree: HashMap<Uuid, usize> = m.keys().map(|id| (*id, 0)).collect();
+        let mut dependents: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for t in m.values() {
+            for dep in t.depends_on.iter() {
+                if m.contains_key(dep) {
+                    dependents.entry(*dep).or_default().push(t.id);
+                    *in_degree.entry(t.id).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<_>> = in_degree
+            .iter()
+            .filter(|(_, d)| **d == 0)
+            .map(|(id, _)| Reverse((m[id].created_at, *id)))
+            .collect();
+
+        let mut order = Vec::with_capacity(m.len());
+        while let Some(Reverse((_, id))) = ready.pop() {
+            order.push(id);
+            for next in dependents.get(&id).into_iter().flatten() {
+                let d = in_degree.get_mut(next).expect("dependent is stored");
+                *d -= 1;
+                if *d == 0 {
+                    ready.push(Reverse((m[next].created_at, *next)));
+                }
+            }
+        }
+
+        if order.len() == m.len() {
+            return Ok(order);
+        }
+
+        // Every task left over sits on or behind a cycle. Peel off the ones that
+        // nothing else left over depends on so only the cycle members remain.
+        let mut remaining: Vec<Uuid> = in_degree
+            .into_iter()
+            .filter(|(_, d)| *d > 0)
+            .map(|(id, _)| id)
+            .collect();
+        loop {
+            let before = remaining.len();
+            let snapshot = remaining.clone();
+            remaining.retain(|id| {
+                dependents
+                    .get(id)
+                    .is_some_and(|ds| ds.iter().any(|d| snapshot.contains(d)))
+            });
+            if remaining.len() == before {
+                break;
+            }
         }
-        created
+        remaining.sort_by_key(|id| (m[id].created_at, *id));
+        Err(remaining)
     }
 }
 
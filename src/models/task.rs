@@ -1,6 +1,7 @@
 //! Task model and DTOs
 //! This file contains multiple unit tests to reach test count and exercise model behavior.
 
+use crate::utils::text::slugify;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -32,6 +33,24 @@ impl Priority {
         }
     }
 
+    /// One level up (`low` -> `medium` -> `high` -> `critical`); `critical` stays put.
+    pub fn next(&self) -> Self {
+        match self {
+            Priority::Low => Priority::Medium,
+            Priority::Medium => Priority::High,
+            Priority::High | Priority::Critical => Priority::Critical,
+        }
+    }
+
+    /// One level down (`critical` -> `high` -> `medium` -> `low`); `low` stays put.
+    pub fn prev(&self) -> Self {
+        match self {
+            Priority::Critical => Priority::High,
+            Priority::High => Priority::Medium,
+            Priority::Medium | Priority::Low => Priority::Low,
+        }
+    }
+
     /// Numeric value for sorting (higher number = higher priority).
     pub fn sort_value(&self) -> u8 {
         match self {
@@ -43,6 +62,33 @@ impl Priority {
     }
 }
 
+/// Workflow status of a task. `completed` is derived from it (`done` <=> completed).
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    #[default]
+    Todo,
+    InProgress,
+    Blocked,
+    Done,
+}
+
+impl Status {
+    /// Parse a status from string (case-insensitive, `-` or `_` separators).
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "todo" => Ok(Status::Todo),
+            "in_progress" => Ok(Status::InProgress),
+            "blocked" => Ok(Status::Blocked),
+            "done" => Ok(Status::Done),
+            _ => Err(format!(
+                "invalid status: '{}'. Valid values: todo, in_progress, blocked, done",
+                s
+            )),
+        }
+    }
+}
+
 /// The domain Task object stored in memory.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Task {
@@ -59,32 +105,331 @@ pub struct Task {
     /// Task priority level.
     #[serde(default)]
     pub priority: Priority,
+    /// Ids of tasks that must be done before this one.
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
+    /// Workflow status; kept consistent with `completed`.
+    #[serde(default)]
+    pub status: Status,
+    /// URL-friendly form of the title; recomputed when the title changes. Not unique.
+    #[serde(default)]
+    pub slug: String,
+    /// When the task was last marked completed; cleared when it is reopened.
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Optional expiry; expired tasks are hidden from reads and removed by `POST /tasks/gc`.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Ids of tasks split off from this one (see `POST /tasks/{id}/split`).
+    #[serde(default)]
+    pub subtask_ids: Vec<Uuid>,
+    /// Task this one was split from, if any (see `GET /tasks/{id}/breadcrumb`).
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    /// Per-process sequential number assigned when the task is first stored; an
+    /// alternative handle for clients that cannot use UUIDs. `id` stays canonical.
+    #[serde(default)]
+    pub seq: u64,
+    /// Pinned tasks are listed before all others by `GET /tasks`.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Actor named by the `X-Actor` header when the task was created via `POST /tasks`.
+    #[serde(default)]
+    pub created_by: Option<String>,
+    /// Archived tasks are hidden from `GET /tasks` unless `include_archived=true`.
+    #[serde(default)]
+    pub archived: bool,
+    /// Display color as `#rrggbb` hex, independent of tag colors.
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Reminder times, kept sorted and free of duplicates (see `POST /tasks/{id}/reminders`).
+    #[serde(default)]
+    pub reminders: Vec<DateTime<Utc>>,
+    /// Estimated effort in minutes (see `PUT /tasks/{id}/estimate`).
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
+    /// Time logged so far in minutes (see `POST /tasks/{id}/log-time`).
+    #[serde(default)]
+    pub logged_minutes: u32,
+    /// Templates are blueprints for new tasks: hidden from `GET /tasks` and listed by
+    /// `GET /tasks/templates` instead.
+    #[serde(default)]
+    pub is_template: bool,
+}
+
+/// Description of one `Task` field for clients that build forms dynamically
+/// (see `GET /tasks/schema`).
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct FieldSchema {
+    pub name: &'static str,
+    /// `string`, `boolean`, `integer`, `uuid`, `datetime`, `enum` or `array`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Must be present in a `POST /tasks` body.
+    pub required: bool,
+    /// Can be changed through the API after creation.
+    pub mutable: bool,
+    /// Allowed values of an `enum` field.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+    /// Element type of an `array` field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<&'static str>,
+}
+
+impl FieldSchema {
+    fn new(name: &'static str, kind: &'static str, required: bool, mutable: bool) -> Self {
+        FieldSchema {
+            name,
+            kind,
+            required,
+            mutable,
+            values: Vec::new(),
+            items: None,
+        }
+    }
+
+    fn array(name: &'static str, items: &'static str, mutable: bool) -> Self {
+        FieldSchema {
+            items: Some(items),
+            ..FieldSchema::new(name, "array", false, mutable)
+        }
+    }
+
+    fn enumeration<T: Serialize>(name: &'static str, variants: &[T]) -> Self {
+        FieldSchema {
+            values: variants
+                .iter()
+                .filter_map(|v| serde_json::to_value(v).ok())
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect(),
+            ..FieldSchema::new(name, "enum", false, true)
+        }
+    }
+}
+
+/// Every field of a serialized `Task`, in declaration order.
+pub fn task_schema() -> Vec<FieldSchema> {
+    vec![
+        FieldSchema::new("id", "uuid", false, false),
+        FieldSchema::new("title", "string", true, true),
+        FieldSchema::new("description", "string", true, true),
+        FieldSchema::new("completed", "boolean", false, true),
+        FieldSchema::new("created_at", "datetime", false, false),
+        FieldSchema::new("updated_at", "datetime", false, false),
+        FieldSchema::array("tags", "string", true),
+        FieldSchema::enumeration(
+            "priority",
+            &[
+                Priority::Low,
+                Priority::Medium,
+                Priority::High,
+                Priority::Critical,
+            ],
+        ),
+        FieldSchema::array("depends_on", "uuid", false),
+        FieldSchema::enumeration(
+            "status",
+            &[
+                Status::Todo,
+                Status::InProgress,
+                Status::Blocked,
+                Status::Done,
+            ],
+        ),
+        FieldSchema::new("slug", "string", false, false),
+        FieldSchema::new("completed_at", "datetime", false, false),
+        FieldSchema::new("expires_at", "datetime", false, false),
+        FieldSchema::array("subtask_ids", "uuid", false),
+        FieldSchema::new("parent_id", "uuid", false, false),
+        FieldSchema::new("seq", "integer", false, false),
+        FieldSchema::new("pinned", "boolean", false, true),
+        FieldSchema::new("created_by", "string", false, false),
+        FieldSchema::new("archived", "boolean", false, true),
+        FieldSchema::new("color", "string", false, true),
+        FieldSchema::array("reminders", "datetime", true),
+        FieldSchema::new("estimate_minutes", "integer", false, true),
+        FieldSchema::new("logged_minutes", "integer", false, true),
+        FieldSchema::new("is_template", "boolean", false, true),
+    ]
 }
 
 /// Input DTO for task creation
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct TaskCreate {
     pub title: String,
     pub description: String,
+    /// Optional expiry timestamp for ephemeral tasks.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Initial priority; `medium` when omitted.
+    #[serde(default)]
+    pub priority: Option<Priority>,
+    /// Client-generated id, honored by `POST /tasks` only (409 if already taken).
+    #[serde(default)]
+    pub id: Option<Uuid>,
+    /// Display color as `#rrggbb` hex.
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Maximum title length in characters.
+pub const MAX_TITLE_LEN: usize = 200;
+/// Maximum description length in characters.
+pub const MAX_DESCRIPTION_LEN: usize = 10_000;
+
+/// One validation failure, tied to the payload field that caused it.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(field: &str, message: impl Into<String>) -> Self {
+        FieldError {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Join validation failures into one message, for per-row import/seed reports.
+pub fn join_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| e.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Check a title: non-blank, at most `MAX_TITLE_LEN` chars, no control characters.
+fn check_title(title: &str, errors: &mut Vec<FieldError>) {
+    if title.trim().is_empty() {
+        errors.push(FieldError::new("title", "title must not be empty"));
+    }
+    if title.chars().count() > MAX_TITLE_LEN {
+        errors.push(FieldError::new(
+            "title",
+            format!("title too long (max {} chars)", MAX_TITLE_LEN),
+        ));
+    }
+    if title.chars().any(char::is_control) {
+        errors.push(FieldError::new(
+            "title",
+            "title must not contain control characters",
+        ));
+    }
+}
+
+/// Check a task color: `#` followed by exactly six hex digits.
+pub fn check_color(color: &str, errors: &mut Vec<FieldError>) {
+    let hex = color.strip_prefix('#').unwrap_or("");
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        errors.push(FieldError::new(
+            "color",
+            format!(
+                "invalid color: '{}'. Expected a hex color like #ff0000",
+                color
+            ),
+        ));
+    }
+}
+
+/// Check a description: at most `MAX_DESCRIPTION_LEN` chars; newlines and tabs are the
+/// only control characters allowed.
+fn check_description(description: &str, errors: &mut Vec<FieldError>) {
+    if description.chars().count() > MAX_DESCRIPTION_LEN {
+        errors.push(FieldError::new(
+            "description",
+            format!("description too long (max {} chars)", MAX_DESCRIPTION_LEN),
+        ));
+    }
+    if description
+        .chars()
+        .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+    {
+        errors.push(FieldError::new(
+            "description",
+            "description must not contain control characters",
+        ));
+    }
 }
 
 impl TaskCreate {
-    /// Basic validation for creation DTOs.
-    /// Returns Err with a short message if invalid.
-    pub fn validate(&self) -> Result<(), String> {
-        if self.title.trim().is_empty() {
-            return Err("title must not be empty".into());
+    /// Validate a creation DTO, collecting every violation rather than stopping at the first.
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+        check_title(&self.title, &mut errors);
+        check_description(&self.description, &mut errors);
+        if let Some(color) = &self.color {
+            check_color(color, &mut errors);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
         }
-        Ok(())
     }
 }
 
 /// Input DTO for task updates
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct TaskUpdate {
     pub title: Option<String>,
     pub description: Option<String>,
     pub completed: Option<bool>,
+    /// New display color; use `PUT /tasks/{id}/color` to clear it.
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+impl TaskUpdate {
+    /// Validate the fields being changed, collecting every violation.
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+        if let Some(title) = &self.title {
+            check_title(title, &mut errors);
+        }
+        if let Some(description) = &self.description {
+            check_description(description, &mut errors);
+        }
+        if let Some(color) = &self.color {
+            check_color(color, &mut errors);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Display metadata for a tag, stored independently of the tasks using it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct TagMeta {
+    /// Hex color such as `#ff0000` or `#f00`.
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl TagMeta {
+    /// Validate the color (if any) as `#rgb` or `#rrggbb` hex.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(c) = &self.color {
+            let hex = c.strip_prefix('#').unwrap_or("");
+            let ok = matches!(hex.len(), 3 | 6) && hex.chars().all(|ch| ch.is_ascii_hexdigit());
+            if !ok {
+                return Err(format!(
+                    "invalid color: '{}'. Expected a hex color like #ff0000",
+                    c
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Task {
@@ -100,25 +445,119 @@ impl Task {
             updated_at: now,
             tags: Vec::new(),
             priority: Priority::default(),
+            depends_on: Vec::new(),
+            status: Status::default(),
+            slug: slugify(title),
+            completed_at: None,
+            expires_at: None,
+            subtask_ids: Vec::new(),
+            parent_id: None,
+            seq: 0,
+            pinned: false,
+            created_by: None,
+            archived: false,
+            color: None,
+            reminders: Vec::new(),
+            estimate_minutes: None,
+            logged_minutes: 0,
+            is_template: false,
         }
     }
 
+    /// Build a new task from a creation DTO.
+    pub fn from_create(c: &TaskCreate) -> Self {
+        let mut t = Task::new_full(&c.title, &c.description);
+        t.expires_at = c.expires_at;
+        t.color = c.color.clone();
+        if let Some(p) = &c.priority {
+            t.priority = p.clone();
+        }
+        t
+    }
+
+    /// True if any reminder is at or before `at`.
+    pub fn has_reminder_due(&self, at: DateTime<Utc>) -> bool {
+        self.reminders.iter().any(|r| *r <= at)
+    }
+
+    /// Add a reminder, keeping the list sorted. Returns false if it was already set.
+    pub fn add_reminder(&mut self, at: DateTime<Utc>) -> bool {
+        match self.reminders.binary_search(&at) {
+            Ok(_) => false,
+            Err(i) => {
+                self.reminders.insert(i, at);
+                true
+            }
+        }
+    }
+
+    /// A fresh, non-template task copying this one's title, description, tags and
+    /// priority.
+    pub fn instantiate(&self) -> Task {
+        let mut t = Task::new_full(&self.title, &self.description);
+        t.tags = self.tags.clone();
+        t.priority = self.priority.clone();
+        t
+    }
+
+    /// True if the task has an expiry at or before `now`.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|e| e <= now)
+    }
+
+    /// Entity tag for this version of the task (quoted, as sent in the `ETag` header).
+    /// Changes whenever `updated_at` does.
+    pub fn etag(&self) -> String {
+        format!(
+            "\"{}-{}\"",
+            self.id.simple(),
+            self.updated_at.timestamp_nanos_opt().unwrap_or_default()
+        )
+    }
+
     /// Apply an update to the task in-place and return updated copy
     pub fn apply_update(&mut self, upd: TaskUpdate) -> Task {
         if let Some(t) = upd.title {
+            self.slug = slugify(&t);
             self.title = t;
         }
         if let Some(d) = upd.description {
             self.description = d;
         }
+        if let Some(c) = upd.color {
+            self.color = Some(c);
+        }
         if let Some(c) = upd.completed {
-            self.completed = c;
+            self.set_completed(c);
+            if c {
+                self.status = Status::Done;
+            } else if self.status == Status::Done {
+                self.status = Status::Todo;
+            }
         }
         // record the time of this update
         self.updated_at = Utc::now();
         self.clone()
     }
 
+    /// Set the workflow status, keeping `completed` in sync, and bump `updated_at`.
+    pub fn set_status(&mut self, status: Status) {
+        self.status = status;
+        self.set_completed(status == Status::Done);
+        self.updated_at = Utc::now();
+    }
+
+    /// Update `completed`, stamping `completed_at` when the task becomes done
+    /// and clearing it when reopened.
+    fn set_completed(&mut self, c: bool) {
+        if c && !self.completed {
+            self.completed_at = Some(Utc::now());
+        } else if !c {
+            self.completed_at = None;
+        }
+        self.completed = c;
+    }
+
     /// Return a small JSON representation of the task including ISO timestamps.
     pub fn to_json(&self) -> serde_json::Value {
         json!({
@@ -130,6 +569,22 @@ impl Task {
             "updated_at": self.updated_at.to_rfc3339(),
             "tags": self.tags,
             "priority": self.priority,
+            "depends_on": self.depends_on,
+            "status": self.status,
+            "slug": self.slug,
+            "completed_at": self.completed_at.map(|t| t.to_rfc3339()),
+            "expires_at": self.expires_at.map(|t| t.to_rfc3339()),
+            "subtask_ids": self.subtask_ids,
+            "parent_id": self.parent_id,
+            "seq": self.seq,
+            "pinned": self.pinned,
+            "created_by": self.created_by,
+            "archived": self.archived,
+            "color": self.color,
+            "reminders": self.reminders.iter().map(|r| r.to_rfc3339()).collect::<Vec<_>>(),
+            "estimate_minutes": self.estimate_minutes,
+            "logged_minutes": self.logged_minutes,
+            "is_template": self.is_template,
         })
     }
 }
@@ -0,0 +1,82 @@
+//! Snapshot persistence to a JSON file.
+//!
+//! `DATA_FILE` points at a file holding every task in the `GET /tasks/dump` format. It
+//! is loaded into the repository at startup and rewritten from memory by
+//! `POST /tasks/compact`.
+
+use crate::models::repository::TaskRepository;
+use crate::models::task::Task;
+use crate::utils::logger::{log_error, log_info};
+use parking_lot::Mutex;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Serializes snapshot writes so a compaction cannot rename an older snapshot over a
+/// newer one.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Suffix counter that keeps temporary snapshot names unique within the process.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Replace the repository contents with the tasks stored at `path`. A missing file is
+/// treated as empty; other errors are logged and leave the repository untouched.
+pub fn load_snapshot(repo: &TaskRepository, path: &str) {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => {
+            log_error(&format!("cannot read data file {}: {}", path, e));
+            return;
+        }
+    };
+    match serde_json::from_str::<Vec<Task>>(&content) {
+        Ok(tasks) => {
+            let n = repo.restore(tasks).loaded;
+            log_info(&format!("loaded {} tasks from {}", n, path));
+        }
+        Err(e) => log_error(&format!("invalid data file {}: {}", path, e)),
+    }
+}
+
+/// Write the repository's tasks (oldest first) to `path`, taking the snapshot and
+/// writing it under one lock so concurrent compactions finish in order. Blocking.
+/// Returns the number of tasks and bytes written.
+pub fn compact(repo: &TaskRepository, path: &str, pretty: bool) -> std::io::Result<(usize, u64)> {
+    let _guard = WRITE_LOCK.lock();
+    let tasks = repo.list_sorted_by_created_at(false);
+    write_snapshot(path, &tasks, pretty).map(|bytes| (tasks.len(), bytes))
+}
+
+/// Write `tasks` to `path` as a JSON array, replacing the file atomically via a
+/// temporary sibling. Callers hold `WRITE_LOCK`. Returns the number of bytes written.
+fn write_snapshot(path: &str, tasks: &[Task], pretty: bool) -> std::io::Result<u64> {
+    let bytes = if pretty {
+        serde_json::to_vec_pretty(tasks)
+    } else {
+        serde_json::to_vec(tasks)
+    }
+    .map_err(std::io::Error::other)?;
+    let target = Path::new(path);
+    // unique per write, and never an existing file, so no two writers share an inode
+    let mut tmp_name = target.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let tmp = target.with_file_name(tmp_name);
+    let written = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&tmp)
+        .and_then(|mut file| {
+            file.write_all(&bytes)?;
+            file.sync_all()
+        })
+        .and_then(|()| std::fs::rename(&tmp, target));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    written.map(|()| bytes.len() as u64)
+}
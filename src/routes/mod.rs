@@ -3,38 +3,195 @@
 
 use axum::{
     Router,
-    routing::{get, post},
+    extract::DefaultBodyLimit,
+    handler::Handler,
+    middleware,
+    routing::{delete, get, post, put},
 };
 
 pub mod tasks;
 
+use crate::config::AppConfig;
 use crate::handlers::task_handler::{
-    bulk_delete_tasks, count_tasks, create_task, delete_task, get_priority, get_stats, get_tags,
-    get_task, get_tasks, get_tasks_by_priority, get_tasks_by_tag, import_tasks, import_tasks_file,
-    set_priority, set_tags, update_task,
+    add_reminder, archive_tasks, bulk_create_tasks, bulk_delete_tasks, bump_priority,
+    compact_tasks, convert_to_template, count_tasks, create_task, delete_all_tasks, delete_task,
+    dump_tasks, export_tasks, find_duplicates, gc_expired, get_activity, get_breadcrumb,
+    get_changes, get_daily_activity, get_due_reminders, get_orphans, get_plan, get_priority,
+    get_recent_tasks, get_reminders, get_schema, get_stats, get_tag_cooccurrence, get_tag_meta,
+    get_tags, get_task_by_seq, get_task_negotiated, get_tasks, get_tasks_by_priority,
+    get_tasks_by_tag, get_tasks_grouped_by_priority, get_templates, get_weekly_stats, head_task,
+    import_tasks, import_tasks_file, instantiate_template, load_tasks, log_time, lower_priority,
+    normalize_all_tags, patch_task, pin_task, preview_tag_normalization, random_task,
+    remove_matching_tags, reset_completed, search_tasks, set_color, set_estimate, set_priority,
+    set_status, set_tag_meta, set_tags, set_tags_many, split_task, tag_exists, unarchive_tasks,
+    unassign_tags, unpin_task, update_by_filter, update_task, update_task_full,
 };
 use crate::models::repository::TaskRepository;
 
-pub fn create_router() -> Router<TaskRepository> {
+/// Build the application router with configuration read from the environment.
+/// If `DATA_FILE` is set, the repository is loaded from that snapshot; if it is still
+/// empty and `SEED_TASKS` is set, it is seeded from that file.
+pub fn create_router() -> Router {
+    let config = AppConfig::from_env();
     let repo = TaskRepository::new();
+    if let Some(path) = config.data_file.as_deref() {
+        crate::persistence::load_snapshot(&repo, path);
+    }
+    if let Some(path) = config.seed_tasks.as_deref()
+        && repo.count() == 0
+    {
+        crate::seed::seed_from_file(&repo, path);
+    }
+    build_router(repo.with_config(config))
+}
+
+/// Build the application router around an existing repository (used by tests to
+/// inject configuration or pre-seeded data).
+/// With `BASE_PATH` set every route is nested under it; `/health` stays at the root
+/// unless `NEST_HEALTH` is set.
+pub fn build_router(repo: TaskRepository) -> Router {
+    let health = Router::new().route("/health", get(tasks::health));
+    let app = match repo.config().mount_path() {
+        None => with_middleware(api_routes(&repo).merge(health), &repo),
+        Some(base) if repo.config().nest_health => Router::new().nest(
+            &base,
+            with_middleware(api_routes(&repo).merge(health), &repo),
+        ),
+        Some(base) => Router::new()
+            .nest(&base, with_middleware(api_routes(&repo), &repo))
+            .merge(with_middleware(health, &repo)),
+    };
+    app.with_state(repo)
+}
+
+/// Every route except `/health`, at its unprefixed path.
+fn api_routes(repo: &TaskRepository) -> Router<TaskRepository> {
+    // bulk JSON-array bodies get their own limit; imports enforce theirs separately
+    let bulk_limit = DefaultBodyLimit::max(repo.config().bulk_max_bytes);
     Router::new()
         .route(
             "/tasks",
-            post(create_task).get(get_tasks).delete(bulk_delete_tasks),
+            post(create_task)
+                .get(get_tasks)
+                .delete(bulk_delete_tasks.layer(bulk_limit)),
         )
+        .route("/tasks/bulk", post(bulk_create_tasks.layer(bulk_limit)))
+        .route("/tasks/archive", post(archive_tasks.layer(bulk_limit)))
+        .route("/tasks/unarchive", post(unarchive_tasks.layer(bulk_limit)))
         .route("/tasks/import", post(import_tasks))
         .route("/tasks/import/file", post(import_tasks_file))
+        .route("/tasks/all", delete(delete_all_tasks))
         .route("/tasks/count", get(count_tasks))
+        .route("/tasks/gc", post(gc_expired))
+        .route("/tasks/duplicates", get(find_duplicates))
         .route("/tasks/stats", get(get_stats))
+        .route("/tasks/stats/weekly", get(get_weekly_stats))
+        .route("/tasks/plan", get(get_plan))
+        .route("/tasks/changes", get(get_changes))
+        .route("/tasks/recent", get(get_recent_tasks))
+        .route("/tasks/activity", get(get_activity))
+        .route("/tasks/activity/daily", get(get_daily_activity))
+        .route("/tasks/export", get(export_tasks))
+        .route("/tasks/dump", get(dump_tasks))
+        .route("/tasks/load", post(load_tasks))
+        .route("/tasks/compact", post(compact_tasks))
+        .route("/tasks/random", get(random_task))
+        .route("/tasks/schema", get(get_schema))
+        .route("/tasks/update-by-filter", post(update_by_filter))
+        .route("/tasks/reset-completed", post(reset_completed))
+        .route(
+            "/tasks/tags/unassign",
+            post(unassign_tags.layer(bulk_limit)),
+        )
+        .route("/tasks/tags/set", post(set_tags_many.layer(bulk_limit)))
+        .route("/tasks/tags/normalize", post(normalize_all_tags))
+        .route("/tasks/tags/remove-matching", post(remove_matching_tags))
+        .route(
+            "/tasks/tags/normalize-preview",
+            post(preview_tag_normalization),
+        )
+        .route("/tasks/tags/cooccurrence", get(get_tag_cooccurrence))
+        .route(
+            "/tasks/tags/{tag}/meta",
+            get(get_tag_meta).put(set_tag_meta),
+        )
+        .route("/tasks/search", get(search_tasks))
         .route("/tasks/search/by_tag", get(get_tasks_by_tag))
         .route("/tasks/search/by_priority", get(get_tasks_by_priority))
+        .route(
+            "/tasks/grouped/by_priority",
+            get(get_tasks_grouped_by_priority),
+        )
+        .route("/tasks/reminders/due", get(get_due_reminders))
+        .route("/tasks/orphans", get(get_orphans))
+        .route("/tasks/templates", get(get_templates))
+        .route(
+            "/tasks/templates/{id}/instantiate",
+            post(instantiate_template),
+        )
+        .route("/tasks/seq/{n}", get(get_task_by_seq))
         .route(
             "/tasks/{id}",
-            get(get_task).put(update_task).delete(delete_task),
+            get(get_task_negotiated)
+                .head(head_task)
+                .put(update_task)
+                .patch(patch_task)
+                .delete(delete_task),
         )
         .route("/tasks/{id}/tags", get(get_tags).put(set_tags))
+        .route("/tasks/{id}/tags/{tag}", get(tag_exists))
         .route("/tasks/{id}/priority", get(get_priority).put(set_priority))
-        .route("/health", get(tasks::health))
+        .route("/tasks/{id}/priority/bump", post(bump_priority))
+        .route("/tasks/{id}/priority/lower", post(lower_priority))
+        .route("/tasks/{id}/status", put(set_status))
+        .route("/tasks/{id}/full", put(update_task_full))
+        .route("/tasks/{id}/split", post(split_task))
+        .route("/tasks/{id}/color", put(set_color))
+        .route("/tasks/{id}/estimate", put(set_estimate))
+        .route("/tasks/{id}/log-time", post(log_time))
+        .route(
+            "/tasks/{id}/reminders",
+            get(get_reminders).post(add_reminder),
+        )
+        .route("/tasks/{id}/convert-to-template", post(convert_to_template))
+        .route("/tasks/{id}/pin", post(pin_task))
+        .route("/tasks/{id}/unpin", post(unpin_task))
+        .route("/tasks/{id}/breadcrumb", get(get_breadcrumb))
         .route("/info", get(tasks::info))
-        .with_state(repo)
+}
+
+/// Wrap `router` in the application middleware. The layers sit inside any nesting, so
+/// they always see unprefixed paths.
+fn with_middleware(
+    router: Router<TaskRepository>,
+    repo: &TaskRepository,
+) -> Router<TaskRepository> {
+    router
+        .layer(middleware::from_fn_with_state(
+            repo.clone(),
+            crate::middleware::request_timeout,
+        ))
+        .layer(middleware::from_fn(crate::middleware::strict_json))
+        .layer(middleware::from_fn(crate::middleware::total_count))
+        .layer(middleware::from_fn_with_state(
+            repo.clone(),
+            crate::middleware::audit_log,
+        ))
+        .layer(middleware::from_fn_with_state(
+            repo.clone(),
+            crate::middleware::read_only,
+        ))
+        .layer(middleware::from_fn_with_state(
+            repo.clone(),
+            crate::middleware::list_etag,
+        ))
+        .layer(middleware::from_fn_with_state(
+            repo.clone(),
+            crate::middleware::log_bodies,
+        ))
+        .layer(middleware::from_fn(crate::middleware::pretty_json))
+        .layer(middleware::from_fn_with_state(
+            repo.clone(),
+            crate::middleware::rate_limit,
+        ))
 }
@@ -1,14 +1,20 @@
 //! Additional task route helpers and example health check route.
 //! Kept as a separate module to give more PR surface area later.
 
+use axum::extract::State;
 use axum::response::Json;
 use axum::{Router, routing::get};
 use serde_json::json;
 
-pub fn routes() -> Router {
+use crate::models::repository::TaskRepository;
+
+/// Health and info routes on their own; `/info` reports on `repo` (its uptime and
+/// config), so pass the repository the service actually runs with.
+pub fn routes(repo: TaskRepository) -> Router {
     Router::new()
         .route("/health", get(health))
         .route("/info", get(info))
+        .with_state(repo)
 }
 
 /// Simple health check
@@ -16,12 +22,29 @@ pub(crate) async fn health() -> Json<serde_json::Value> {
     Json(json!({"status": "ok"}))
 }
 
-/// Lightweight info endpoint
-pub(crate) async fn info() -> Json<serde_json::Value> {
+/// Lightweight info endpoint, plus runtime details for checking a deployment:
+/// uptime, the build version (`BUILD_VERSION` at compile time, else the crate version)
+/// and which optional behaviors the active config enables.
+pub(crate) async fn info(State(repo): State<TaskRepository>) -> Json<serde_json::Value> {
+    let config = repo.config();
     Json(json!({
         "name": "rust_api_hub",
-        "version": "0.1.0",
-        "desc": "Axum-based task API"
+        "version": env!("CARGO_PKG_VERSION"),
+        "desc": "Axum-based task API",
+        "build_version": option_env!("BUILD_VERSION").unwrap_or(env!("CARGO_PKG_VERSION")),
+        "uptime_seconds": repo.uptime().as_secs(),
+        "features": {
+            "audit_log": config.audit_log_path.is_some(),
+            "read_only": config.read_only,
+            "log_bodies": config.log_bodies,
+            "delete_all": config.allow_delete_all,
+            "now_override": config.allow_now_override,
+            "create_debounce": config.create_debounce_ms.is_some(),
+            "rate_limit": config.rate_limit.is_some(),
+            "strict_subtask_completion": config.strict_subtask_completion,
+            "persistence": config.data_file.is_some(),
+        },
+        "base_path": config.mount_path(),
     }))
 }
 
@@ -0,0 +1,38 @@
+//! Startup seeding of demo tasks.
+//!
+//! `SEED_TASKS` points at a JSON file holding an array of task creation objects
+//! (`[{"title": "...", "description": "..."}, ...]`). The tasks are loaded into the
+//! repository before the router is built so a fresh server is not empty.
+
+use crate::models::repository::TaskRepository;
+use crate::models::task::{Task, TaskCreate, join_field_errors};
+use crate::utils::logger::{log_error, log_info};
+
+/// Parse `json` as an array of `TaskCreate` and insert every entry into `repo`.
+/// Nothing is inserted unless the whole array parses and validates.
+pub fn seed_from_str(repo: &TaskRepository, json: &str) -> Result<Vec<Task>, String> {
+    let creates: Vec<TaskCreate> =
+        serde_json::from_str(json).map_err(|e| format!("invalid seed json: {}", e))?;
+    for (i, c) in creates.iter().enumerate() {
+        c.validate().map_err(|e| {
+            format!(
+                "invalid seed task at index {}: {}",
+                i,
+                join_field_errors(&e)
+            )
+        })?;
+    }
+    repo.insert_many(&creates).map_err(|e| e.to_string())
+}
+
+/// Load seed tasks from the file at `path`. Errors are logged and leave the
+/// repository untouched; startup never fails because of bad seed data.
+pub fn seed_from_file(repo: &TaskRepository, path: &str) {
+    let result = std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read seed file: {}", e))
+        .and_then(|content| seed_from_str(repo, &content));
+    match result {
+        Ok(tasks) => log_info(&format!("seeded {} tasks from {}", tasks.len(), path)),
+        Err(e) => log_error(&format!("seeding from {} failed: {}", path, e)),
+    }
+}
@@ -0,0 +1,93 @@
+//! Minimal JSON Patch (RFC 6902) support: the `add`, `replace` and `remove` operations.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// One patch operation, e.g. `{"op": "replace", "path": "/title", "value": "x"}`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PatchOp {
+    pub op: String,
+    pub path: String,
+    #[serde(default)]
+    pub value: Option<Value>,
+}
+
+impl PatchOp {
+    /// Unescaped reference tokens of `path` (`~1` -> `/`, `~0` -> `~`).
+    pub fn tokens(&self) -> Result<Vec<String>, String> {
+        let rest = self
+            .path
+            .strip_prefix('/')
+            .ok_or_else(|| format!("invalid path: '{}'", self.path))?;
+        Ok(rest
+            .split('/')
+            .map(|t| t.replace("~1", "/").replace("~0", "~"))
+            .collect())
+    }
+}
+
+/// Apply `ops` to `doc` in order. Stops at the first failing op; callers should work on
+/// a copy if they need all-or-nothing semantics.
+pub fn apply(doc: &mut Value, ops: &[PatchOp]) -> Result<(), String> {
+    for op in ops {
+        apply_one(doc, op)?;
+    }
+    Ok(())
+}
+
+fn apply_one(doc: &mut Value, op: &PatchOp) -> Result<(), String> {
+    let mut tokens = op.tokens()?;
+    let last = tokens.pop().unwrap_or_default();
+    let mut parent = &mut *doc;
+    for t in &tokens {
+        parent = match parent {
+            Value::Object(m) => m.get_mut(t),
+            Value::Array(a) => t.parse::<usize>().ok().and_then(|i| a.get_mut(i)),
+            _ => None,
+        }
+        .ok_or_else(|| format!("path not found: '{}'", op.path))?;
+    }
+    let value = || {
+        op.value
+            .clone()
+            .ok_or_else(|| format!("'{}' requires a value", op.op))
+    };
+    let not_found = || format!("path not found: '{}'", op.path);
+
+    match (op.op.as_str(), parent) {
+        ("add", Value::Object(m)) => {
+            m.insert(last, value()?);
+        }
+        ("replace", Value::Object(m)) => {
+            let slot = m.get_mut(&last).ok_or_else(not_found)?;
+            *slot = value()?;
+        }
+        ("remove", Value::Object(m)) => {
+            m.remove(&last).ok_or_else(not_found)?;
+        }
+        ("add", Value::Array(a)) => {
+            let i = if last == "-" {
+                a.len()
+            } else {
+                array_index(&last, a.len() + 1).ok_or_else(not_found)?
+            };
+            a.insert(i, value()?);
+        }
+        ("replace", Value::Array(a)) => {
+            let i = array_index(&last, a.len()).ok_or_else(not_found)?;
+            a[i] = value()?;
+        }
+        ("remove", Value::Array(a)) => {
+            let i = array_index(&last, a.len()).ok_or_else(not_found)?;
+            a.remove(i);
+        }
+        ("add" | "replace" | "remove", _) => return Err(not_found()),
+        (other, _) => return Err(format!("unsupported op: '{}'", other)),
+    }
+    Ok(())
+}
+
+/// Parse an array index token, requiring it to be below `len`.
+fn array_index(token: &str, len: usize) -> Option<usize> {
+    token.parse::<usize>().ok().filter(|i| *i < len)
+}
@@ -1,2 +1,4 @@
 //! Utilities module
+pub mod json_patch;
 pub mod logger;
+pub mod text;
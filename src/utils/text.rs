@@ -0,0 +1,160 @@
+//! Text helpers shared by models and handlers.
+
+/// Turn free text into a URL-friendly slug: lowercase ASCII alphanumerics separated by
+/// single hyphens, with no leading/trailing hyphen. `"Fix the Bug!"` -> `"fix-the-bug"`.
+pub fn slugify(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut pending_hyphen = false;
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            if pending_hyphen && !out.is_empty() {
+                out.push('-');
+            }
+            pending_hyphen = false;
+            out.push(c.to_ascii_lowercase());
+        } else {
+            pending_hyphen = true;
+        }
+    }
+    out
+}
+
+/// Placeholders understood by `render_title_template`.
+const TITLE_PLACEHOLDERS: [&str; 2] = ["title", "date"];
+
+/// Check a title template: braces must pair up around a known placeholder
+/// (`{title}` or `{date}`), and `{title}` must appear so the client title is kept.
+pub fn validate_title_template(template: &str) -> Result<(), String> {
+    let mut has_title = false;
+    let mut rest = template;
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return Err("unmatched '}' in title template".into());
+        }
+        let after = &rest[open + 1..];
+        let close = after
+            .find('}')
+            .ok_or_else(|| "unclosed '{' in title template".to_string())?;
+        let name = &after[..close];
+        if !TITLE_PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "unknown placeholder '{{{}}}' in title template",
+                name
+            ));
+        }
+        has_title |= name == "title";
+        rest = &after[close + 1..];
+    }
+    if !has_title {
+        return Err("title template must contain {title}".into());
+    }
+    Ok(())
+}
+
+/// Expand `{title}` and `{date}` in a template in a single pass. Substituted text is
+/// never re-scanned, so a title containing `{date}` is kept literally.
+pub fn render_title_template(template: &str, title: &str, date: &str) -> String {
+    let mut out = String::with_capacity(template.len() + title.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after.find('}') {
+            Some(close) => {
+                match &after[..close] {
+                    "title" => out.push_str(title),
+                    "date" => out.push_str(date),
+                    other => {
+                        out.push('{');
+                        out.push_str(other);
+                        out.push('}');
+                    }
+                }
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push_str(&rest[open..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Edit distance between two strings (insertions, deletions and substitutions of chars).
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Match `text` against a glob where `*` stands for any run of chars (including none)
+/// and `?` for exactly one char. Everything else matches literally.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    // position after the last `*` and the text index it is currently matched up to
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || (p[pi] != '*' && p[pi] == t[ti])) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            pi += 1;
+            star = Some((pi, ti));
+        } else if let Some((sp, st)) = star {
+            // let the last `*` swallow one more char and retry
+            pi = sp;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|c| *c == '*')
+}
+
+/// Character trigrams of `s` in the style of PostgreSQL's `pg_trgm`: lowercased, split
+/// into alphanumeric words, each padded with two leading spaces and one trailing space.
+pub fn trigrams(s: &str) -> std::collections::HashSet<String> {
+    let lower = s.to_lowercase();
+    let mut out = std::collections::HashSet::new();
+    for word in lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        let padded: Vec<char> = format!("  {} ", word).chars().collect();
+        for w in padded.windows(3) {
+            out.insert(w.iter().collect());
+        }
+    }
+    out
+}
+
+/// Jaccard similarity of the trigram sets of `a` and `b`, from 0.0 (nothing shared) to
+/// 1.0 (same trigrams). Two texts without any words score 0.0.
+pub fn trigram_similarity(a: &str, b: &str) -> f64 {
+    trigram_similarity_to(&trigrams(a), b)
+}
+
+/// `trigram_similarity` against trigrams computed up front, so one query can be scored
+/// against many texts without rebuilding its set each time.
+pub fn trigram_similarity_to(ta: &std::collections::HashSet<String>, b: &str) -> f64 {
+    let tb = trigrams(b);
+    let union = ta.union(&tb).count();
+    if union == 0 {
+        return 0.0;
+    }
+    ta.intersection(&tb).count() as f64 / union as f64
+}
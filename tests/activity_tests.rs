@@ -0,0 +1,100 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use rust_api_hub::handlers::task_handler::{ActivityParams, get_activity};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskUpdate};
+
+async fn activity(repo: &TaskRepository, page: usize, per_page: usize) -> serde_json::Value {
+    let Json(v) = get_activity(
+        State(repo.clone()),
+        Query(ActivityParams {
+            page: Some(page),
+            per_page: Some(per_page),
+        }),
+    )
+    .await;
+    v
+}
+
+fn types(v: &serde_json::Value) -> Vec<&str> {
+    v["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["type"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn create_then_update_gives_two_ordered_entries() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("first", "d")).unwrap();
+    repo.update(
+        &t.id,
+        TaskUpdate {
+            title: Some("renamed".into()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let v = activity(&repo, 1, 20).await;
+    assert_eq!(v["total"], 2);
+    assert_eq!(types(&v), vec!["created", "updated"]);
+    let items = v["items"].as_array().unwrap();
+    assert!(items.iter().all(|e| e["task_id"] == t.id.to_string()));
+    assert_eq!(items[0]["detail"]["title"], "first");
+    assert_eq!(items[1]["detail"]["title"], "renamed");
+    let at = |i: usize| {
+        items[i]["at"]
+            .as_str()
+            .unwrap()
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap()
+    };
+    assert!(at(0) <= at(1));
+}
+
+#[tokio::test]
+async fn deletes_and_tag_changes_are_recorded() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("gone", "d")).unwrap();
+    repo.modify(&t.id, |t| t.tags = vec!["x".into()]);
+    assert!(repo.remove(&t.id));
+
+    let v = activity(&repo, 1, 20).await;
+    assert_eq!(types(&v), vec!["created", "tagged", "deleted"]);
+    assert_eq!(v["items"][1]["detail"]["tags"], serde_json::json!(["x"]));
+    // the tombstone still names the task
+    assert_eq!(v["items"][2]["task_id"], t.id.to_string());
+    assert_eq!(v["items"][2]["detail"]["title"], "gone");
+}
+
+#[tokio::test]
+async fn no_op_bulk_passes_are_not_recorded() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    // already unarchived: nothing changes
+    assert_eq!(repo.set_archived_many(&[t.id], false), 0);
+    let v = activity(&repo, 1, 20).await;
+    assert_eq!(types(&v), vec!["created"]);
+}
+
+#[tokio::test]
+async fn feed_is_paginated() {
+    let repo = TaskRepository::new();
+    for i in 0..5 {
+        repo.insert(Task::new_full(&format!("t{}", i), "d"))
+            .unwrap();
+    }
+    let v = activity(&repo, 2, 2).await;
+    assert_eq!(v["total"], 5);
+    assert_eq!(v["page"], 2);
+    let titles: Vec<_> = v["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["detail"]["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, vec!["t2", "t3"]);
+}
@@ -0,0 +1,56 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use rust_api_hub::handlers::task_handler::{ListParams, archive_tasks, get_tasks, unarchive_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+async fn listed_ids(repo: &TaskRepository, include_archived: bool) -> Vec<String> {
+    let params = ListParams {
+        include_archived: Some(include_archived),
+        ..Default::default()
+    };
+    let Json(v) = get_tasks(State(repo.clone()), HeaderMap::new(), Query(params))
+        .await
+        .unwrap();
+    v["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn archived_tasks_leave_default_list_and_return_on_unarchive() {
+    let repo = TaskRepository::new();
+    let tasks: Vec<Task> = (0..4)
+        .map(|i| {
+            repo.insert(Task::new_full(&format!("t{}", i), "d"))
+                .unwrap()
+        })
+        .collect();
+    let ids = vec![
+        tasks[0].id.to_string(),
+        tasks[1].id.to_string(),
+        "not-a-uuid".to_string(),
+        uuid::Uuid::new_v4().to_string(),
+    ];
+
+    let (code, Json(v)) = archive_tasks(State(repo.clone()), Json(ids.clone())).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(v["archived"], 2);
+    let visible = listed_ids(&repo, false).await;
+    assert_eq!(visible.len(), 2);
+    assert!(!visible.contains(&ids[0]) && !visible.contains(&ids[1]));
+    assert_eq!(listed_ids(&repo, true).await.len(), 4);
+
+    // archiving again changes nothing
+    let (_, Json(v)) = archive_tasks(State(repo.clone()), Json(ids.clone())).await;
+    assert_eq!(v["archived"], 0);
+
+    let (code, Json(v)) = unarchive_tasks(State(repo.clone()), Json(ids)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(v["unarchived"], 2);
+    assert_eq!(listed_ids(&repo, false).await.len(), 4);
+}
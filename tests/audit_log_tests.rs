@@ -0,0 +1,319 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use std::path::PathBuf;
+use tower::ServiceExt; // oneshot
+
+fn audited_repo() -> (TaskRepository, PathBuf) {
+    let path = std::env::temp_dir().join(format!("audit-{}.jsonl", uuid::Uuid::new_v4()));
+    let repo = TaskRepository::new().with_config(AppConfig {
+        audit_log_path: Some(path.to_string_lossy().into_owned()),
+        ..Default::default()
+    });
+    (repo, path)
+}
+
+async fn send(repo: &TaskRepository, method: &str, uri: &str, body: &str) -> StatusCode {
+    let req = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("x-actor", "  bulk-bot  ")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    build_router(repo.clone())
+        .oneshot(req)
+        .await
+        .unwrap()
+        .status()
+}
+
+/// `(operation, task_id)` of every audit line, in order.
+fn ops(path: &PathBuf) -> Vec<(String, String)> {
+    lines(path)
+        .iter()
+        .map(|e| {
+            (
+                e["operation"].as_str().unwrap().to_string(),
+                e["task_id"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+fn lines(path: &PathBuf) -> Vec<serde_json::Value> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn create_writes_one_audit_line() {
+    let (repo, path) = audited_repo();
+    let req = Request::post("/tasks")
+        .header("content-type", "application/json")
+        .header("x-actor", "alice")
+        .body(Body::from(r#"{"title":"audited","description":""}"#))
+        .unwrap();
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let created = repo.list().pop().unwrap();
+
+    let entries = lines(&path);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["operation"], "create");
+    assert_eq!(entries[0]["task_id"], created.id.to_string());
+    assert_eq!(entries[0]["actor"], "alice");
+    assert!(entries[0]["timestamp"].is_string());
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn delete_is_audited_and_failures_are_not() {
+    let (repo, path) = audited_repo();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+
+    let del = |id: String| Request::delete(format!("/tasks/{}", id)).body(Body::empty());
+    let resp = build_router(repo.clone())
+        .oneshot(del(t.id.to_string()).unwrap())
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+    // deleting again fails and is not recorded
+    let resp = build_router(repo.clone())
+        .oneshot(del(t.id.to_string()).unwrap())
+        .await
+        .unwrap();
+    assert!(!resp.status().is_success());
+
+    let entries = lines(&path);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["operation"], "delete");
+    assert_eq!(entries[0]["task_id"], t.id.to_string());
+    assert!(entries[0]["actor"].is_null());
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn unwritable_path_does_not_fail_request() {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        audit_log_path: Some("/nonexistent-dir/audit.jsonl".into()),
+        ..Default::default()
+    });
+    let req = Request::post("/tasks")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"title":"still works","description":""}"#))
+        .unwrap();
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    assert_eq!(repo.count(), 1);
+}
+
+#[tokio::test]
+async fn bulk_mutations_write_one_line_per_task() {
+    let (repo, path) = audited_repo();
+    let status = send(
+        &repo,
+        "POST",
+        "/tasks/bulk",
+        r#"[{"title":"one","description":""},{"title":"two","description":""}]"#,
+    )
+    .await;
+    assert!(status.is_success());
+    let created: Vec<String> = repo.list().iter().map(|t| t.id.to_string()).collect();
+    let entries = ops(&path);
+    assert_eq!(entries.len(), 2);
+    for (op, id) in &entries {
+        assert_eq!(op, "create");
+        assert!(created.contains(id));
+    }
+    // the actor is trimmed as on single creates
+    assert!(lines(&path).iter().all(|e| e["actor"] == "bulk-bot"));
+
+    let ids = serde_json::to_string(&created).unwrap();
+    assert!(send(&repo, "DELETE", "/tasks", &ids).await.is_success());
+    let deletes: Vec<(String, String)> = ops(&path).split_off(2);
+    assert_eq!(deletes.len(), 2);
+    for (op, id) in &deletes {
+        assert_eq!(op, "delete");
+        assert!(created.contains(id));
+    }
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn split_and_filter_updates_are_audited() {
+    let (repo, path) = audited_repo();
+    let parent = repo.insert(Task::new_full("parent", "d")).unwrap();
+    let uri = format!("/tasks/{}/split", parent.id);
+    let status = send(&repo, "POST", &uri, r#"{"titles":["a","b"]}"#).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let entries = ops(&path);
+    assert_eq!(entries.iter().filter(|(op, _)| op == "create").count(), 2);
+    assert!(entries.contains(&("update".into(), parent.id.to_string())));
+
+    std::fs::remove_file(&path).ok();
+    let body = r#"{"filter":{"completed":false},"update":{"completed":true}}"#;
+    assert!(
+        send(&repo, "POST", "/tasks/update-by-filter", body)
+            .await
+            .is_success()
+    );
+    let entries = ops(&path);
+    assert_eq!(entries.len(), 3);
+    assert!(entries.iter().all(|(op, _)| op == "update"));
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn archive_and_unarchive_are_audited() {
+    let (repo, path) = audited_repo();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    let ids = format!(r#"["{}"]"#, t.id);
+    assert!(
+        send(&repo, "POST", "/tasks/archive", &ids)
+            .await
+            .is_success()
+    );
+    assert!(
+        send(&repo, "POST", "/tasks/unarchive", &ids)
+            .await
+            .is_success()
+    );
+    let update = ("update".to_string(), t.id.to_string());
+    assert_eq!(ops(&path), vec![update.clone(), update]);
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn tag_set_many_is_audited_as_tag_changes() {
+    let (repo, path) = audited_repo();
+    let a = repo.insert(Task::new_full("a", "d")).unwrap();
+    let b = repo.insert(Task::new_full("b", "d")).unwrap();
+    let body = format!(r#"{{"ids":["{}","{}"],"tags":["x"]}}"#, a.id, b.id);
+    assert!(
+        send(&repo, "POST", "/tasks/tags/set", &body)
+            .await
+            .is_success()
+    );
+    let mut entries = ops(&path);
+    entries.sort();
+    let mut expected = vec![
+        ("tags".to_string(), a.id.to_string()),
+        ("tags".to_string(), b.id.to_string()),
+    ];
+    expected.sort();
+    assert_eq!(entries, expected);
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn remove_matching_tags_is_audited_for_changed_tasks_only() {
+    let (repo, path) = audited_repo();
+    let mut tagged = Task::new_full("tagged", "d");
+    tagged.tags = vec!["tmp/a".into(), "keep".into()];
+    let tagged = repo.insert(tagged).unwrap();
+    let mut other = Task::new_full("other", "d");
+    other.tags = vec!["keep".into()];
+    repo.insert(other).unwrap();
+    let status = send(
+        &repo,
+        "POST",
+        "/tasks/tags/remove-matching",
+        r#"{"prefix":"tmp/"}"#,
+    )
+    .await;
+    assert!(status.is_success());
+    assert_eq!(ops(&path), vec![("tags".into(), tagged.id.to_string())]);
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn reset_completed_is_audited() {
+    let (repo, path) = audited_repo();
+    let mut done = Task::new_full("done", "d");
+    done.completed = true;
+    done.tags = vec!["sprint".into()];
+    let done = repo.insert(done).unwrap();
+    let status = send(
+        &repo,
+        "POST",
+        "/tasks/reset-completed",
+        r#"{"tag":"sprint"}"#,
+    )
+    .await;
+    assert!(status.is_success());
+    assert!(!repo.get(&done.id).unwrap().completed);
+    assert_eq!(ops(&path), vec![("update".into(), done.id.to_string())]);
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn load_delete_all_and_orphan_fixes_are_audited() {
+    let path = std::env::temp_dir().join(format!("audit-{}.jsonl", uuid::Uuid::new_v4()));
+    let repo = TaskRepository::new().with_config(AppConfig {
+        audit_log_path: Some(path.to_string_lossy().into_owned()),
+        allow_delete_all: true,
+        ..Default::default()
+    });
+    let old = repo.insert(Task::new_full("old", "d")).unwrap();
+
+    // default replace mode: the old task is wiped, the dumped one created
+    let mut orphan = Task::new_full("orphan", "d");
+    orphan.parent_id = Some(uuid::Uuid::new_v4());
+    let dump = serde_json::to_string(&vec![orphan.clone()]).unwrap();
+    assert!(send(&repo, "POST", "/tasks/load", &dump).await.is_success());
+    assert_eq!(
+        ops(&path),
+        vec![
+            ("delete".into(), old.id.to_string()),
+            ("create".into(), orphan.id.to_string()),
+        ]
+    );
+
+    std::fs::remove_file(&path).ok();
+    assert!(send(&repo, "GET", "/tasks/orphans", "").await.is_success());
+    assert!(ops(&path).is_empty());
+    assert!(
+        send(&repo, "GET", "/tasks/orphans?fix=true", "")
+            .await
+            .is_success()
+    );
+    assert_eq!(ops(&path), vec![("update".into(), orphan.id.to_string())]);
+
+    std::fs::remove_file(&path).ok();
+    assert!(send(&repo, "DELETE", "/tasks/all", "").await.is_success());
+    assert_eq!(ops(&path), vec![("delete".into(), orphan.id.to_string())]);
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn imports_and_tag_normalization_are_audited() {
+    let (repo, path) = audited_repo();
+    let body = r#"[{"title":"a","description":"d"},{"title":"b","description":"d"}]"#;
+    assert!(
+        send(&repo, "POST", "/tasks/import", body)
+            .await
+            .is_success()
+    );
+    let entries = ops(&path);
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|(op, _)| op == "create"));
+
+    std::fs::remove_file(&path).ok();
+    let mut messy = Task::new_full("messy", "d");
+    messy.tags = vec![" Work ".into()];
+    let messy = repo.insert(messy).unwrap();
+    assert!(
+        send(&repo, "POST", "/tasks/tags/normalize", "")
+            .await
+            .is_success()
+    );
+    assert_eq!(ops(&path), vec![("tags".into(), messy.id.to_string())]);
+    std::fs::remove_file(&path).ok();
+}
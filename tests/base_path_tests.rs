@@ -0,0 +1,95 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt;
+
+fn repo(base_path: &str, nest_health: bool) -> TaskRepository {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        base_path: base_path.into(),
+        nest_health,
+        ..Default::default()
+    });
+    repo.insert(Task::new_full("seeded", "d")).unwrap();
+    repo
+}
+
+async fn status(repo: &TaskRepository, req: Request<Body>) -> StatusCode {
+    build_router(repo.clone())
+        .oneshot(req)
+        .await
+        .unwrap()
+        .status()
+}
+
+fn get(path: &str) -> Request<Body> {
+    Request::get(path).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn routes_are_served_under_the_prefix() {
+    let repo = repo("/api/v1", false);
+    let resp = build_router(repo.clone())
+        .oneshot(get("/api/v1/tasks"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(v["items"][0]["title"], "seeded");
+
+    let create = Request::post("/api/v1/tasks")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(r#"{"title":"new","description":"d"}"#))
+        .unwrap();
+    assert_eq!(status(&repo, create).await, StatusCode::CREATED);
+    assert_eq!(
+        status(&repo, get("/api/v1/tasks/count")).await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn bare_paths_404_when_prefixed() {
+    let repo = repo("/api/v1", false);
+    assert_eq!(status(&repo, get("/tasks")).await, StatusCode::NOT_FOUND);
+    assert_eq!(status(&repo, get("/info")).await, StatusCode::NOT_FOUND);
+    // health stays at the root by default
+    assert_eq!(status(&repo, get("/health")).await, StatusCode::OK);
+    assert_eq!(
+        status(&repo, get("/api/v1/health")).await,
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn health_moves_under_the_prefix_when_nested() {
+    let repo = repo("api/v1/", true);
+    assert_eq!(status(&repo, get("/api/v1/health")).await, StatusCode::OK);
+    assert_eq!(status(&repo, get("/health")).await, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn path_based_middleware_still_applies_under_the_prefix() {
+    let repo = repo("/api/v1", false);
+    let resp = build_router(repo.clone())
+        .oneshot(get("/api/v1/tasks"))
+        .await
+        .unwrap();
+    // list_etag matches on `/tasks`, so it must see the unprefixed path
+    let etag = resp.headers().get(header::ETAG).cloned().unwrap();
+    let req = Request::get("/api/v1/tasks")
+        .header(header::IF_NONE_MATCH, etag)
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(status(&repo, req).await, StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn empty_base_path_serves_at_root() {
+    let repo = repo("", false);
+    assert_eq!(status(&repo, get("/tasks")).await, StatusCode::OK);
+    assert_eq!(status(&repo, get("/health")).await, StatusCode::OK);
+}
@@ -0,0 +1,53 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::get_breadcrumb;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+#[tokio::test]
+async fn child_breadcrumb_runs_root_to_child() {
+    let repo = TaskRepository::new();
+    let root = repo.insert(Task::new_full("root", "d")).unwrap();
+    let (_, children) = repo.split(&root.id, &["child".to_string()]).unwrap();
+    let child = &children[0];
+    assert_eq!(child.parent_id, Some(root.id));
+
+    let (code, Json(resp)) = get_breadcrumb(Path(child.id.to_string()), State(repo.clone())).await;
+    assert_eq!(code, StatusCode::OK);
+    let crumbs = resp.as_array().unwrap();
+    assert_eq!(crumbs.len(), 2);
+    assert_eq!(crumbs[0]["id"], root.id.to_string());
+    assert_eq!(crumbs[0]["title"], "root");
+    assert_eq!(crumbs[1]["id"], child.id.to_string());
+    assert_eq!(crumbs[1]["title"], "child");
+}
+
+#[tokio::test]
+async fn task_without_parent_has_single_crumb() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("alone", "d")).unwrap();
+    let (code, Json(resp)) = get_breadcrumb(Path(t.id.to_string()), State(repo.clone())).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp, serde_json::json!([{"id": t.id, "title": "alone"}]));
+}
+
+#[tokio::test]
+async fn parent_cycle_returns_conflict() {
+    let repo = TaskRepository::new();
+    let a = repo.insert(Task::new_full("a", "d")).unwrap();
+    let b = repo.insert(Task::new_full("b", "d")).unwrap();
+    repo.modify(&a.id, |t| t.parent_id = Some(b.id));
+    repo.modify(&b.id, |t| t.parent_id = Some(a.id));
+
+    let (code, _) = get_breadcrumb(Path(a.id.to_string()), State(repo.clone())).await;
+    assert_eq!(code, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn missing_task_is_404() {
+    let repo = TaskRepository::new();
+    let (code, _) =
+        get_breadcrumb(Path(uuid::Uuid::new_v4().to_string()), State(repo.clone())).await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,77 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt; // oneshot
+
+fn repo_with_limit(bulk_max_bytes: usize) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        bulk_max_bytes,
+        ..Default::default()
+    })
+}
+
+fn ids_body(n: usize) -> String {
+    let ids: Vec<String> = (0..n).map(|_| uuid::Uuid::new_v4().to_string()).collect();
+    serde_json::to_string(&ids).unwrap()
+}
+
+async fn bulk_delete(repo: &TaskRepository, body: String) -> StatusCode {
+    let req = Request::delete("/tasks")
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap();
+    build_router(repo.clone())
+        .oneshot(req)
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn over_limit_bulk_delete_returns_413() {
+    let repo = repo_with_limit(1024);
+    let body = ids_body(100);
+    assert!(body.len() > 1024);
+    assert_eq!(
+        bulk_delete(&repo, body).await,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+}
+
+#[tokio::test]
+async fn under_limit_bulk_delete_is_processed() {
+    let repo = repo_with_limit(1024);
+    assert_eq!(bulk_delete(&repo, ids_body(2)).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn default_limit_applies_to_bulk_routes_only() {
+    let repo = TaskRepository::new();
+    // ~3.7 MB of ids: over the 1 MB bulk default
+    let body = ids_body(100_000);
+    assert_eq!(
+        bulk_delete(&repo, body).await,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+
+    // a 1.1 MB import is fine: other routes keep axum's default (2 MB) limit
+    let rows: Vec<String> = (0..9_000)
+        .map(|i| {
+            format!(
+                r#"{{"title":"t{}","description":"{}"}}"#,
+                i,
+                "x".repeat(100)
+            )
+        })
+        .collect();
+    let body = format!("[{}]", rows.join(","));
+    assert!(body.len() > 1024 * 1024);
+    let req = Request::post("/tasks/import")
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap();
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
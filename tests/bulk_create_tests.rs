@@ -0,0 +1,40 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::bulk_create_tasks;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::TaskCreate;
+
+fn create(title: &str) -> TaskCreate {
+    TaskCreate {
+        title: title.into(),
+        description: "d".into(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn bulk_create_all_valid() {
+    let repo = TaskRepository::new();
+    let payload = vec![create("a"), create("b"), create("c")];
+    let (code, Json(resp)) = bulk_create_tasks(State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::CREATED);
+    let created = resp["created"].as_array().unwrap();
+    assert_eq!(created.len(), 3);
+    assert!(created.iter().all(|t| t["id"].as_str().is_some()));
+    assert!(resp["errors"].as_array().unwrap().is_empty());
+    assert_eq!(repo.count(), 3);
+}
+
+#[tokio::test]
+async fn bulk_create_mixed_reports_errors_by_index() {
+    let repo = TaskRepository::new();
+    let payload = vec![create("ok"), create("   "), create("also ok")];
+    let (code, Json(resp)) = bulk_create_tasks(State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["created"].as_array().unwrap().len(), 2);
+    let errors = resp["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0]["index"].as_u64().unwrap(), 1);
+    assert_eq!(repo.count(), 2);
+}
@@ -27,8 +27,15 @@ async fn bulk_delete_some_removes_only_specified() {
         let payload = TaskCreate {
             title: format!("t{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (code, created) = create_task(State(repo.clone()), Json(payload)).await;
+        let (code, created) = create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
         assert_eq!(code, StatusCode::CREATED);
         ids.push(created.id.to_string());
     }
@@ -53,8 +60,15 @@ async fn bulk_delete_all_removes_everything() {
         let payload = TaskCreate {
             title: format!("t{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (code, created) = create_task(State(repo.clone()), Json(payload)).await;
+        let (code, created) = create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
         assert_eq!(code, StatusCode::CREATED);
         ids.push(created.id.to_string());
     }
@@ -64,3 +78,40 @@ async fn bulk_delete_all_removes_everything() {
     assert_eq!(v["deleted"].as_u64().unwrap(), 5);
     assert_eq!(repo.list().len(), 0);
 }
+
+#[tokio::test]
+async fn bulk_delete_reports_not_found_and_invalid_ids() {
+    let repo = app_state();
+    let payload = TaskCreate {
+        title: "t".into(),
+        description: "d".into(),
+        ..Default::default()
+    };
+    let (_, created) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap();
+    let existing = created.id.to_string();
+    let missing = uuid::Uuid::new_v4().to_string();
+
+    let ids = vec![existing.clone(), missing.clone(), "not-a-uuid".to_string()];
+    let (code, body) = bulk_delete_tasks(State(repo.clone()), Json(ids)).await;
+    assert_eq!(code, StatusCode::OK);
+    let v = body.0;
+    assert_eq!(v["deleted"].as_u64().unwrap(), 1);
+    assert_eq!(v["not_found"], serde_json::json!([missing]));
+    assert_eq!(v["invalid"], serde_json::json!(["not-a-uuid"]));
+    assert_eq!(repo.count(), 0);
+}
+
+#[tokio::test]
+async fn bulk_delete_empty_payload_has_empty_reports() {
+    let repo = app_state();
+    let (_, body) = bulk_delete_tasks(State(repo.clone()), Json(Vec::<String>::new())).await;
+    let v = body.0;
+    assert!(v["not_found"].as_array().unwrap().is_empty());
+    assert!(v["invalid"].as_array().unwrap().is_empty());
+}
@@ -0,0 +1,79 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use rust_api_hub::config::{AppConfig, DEFAULT_MAX_BULK_ITEMS};
+use rust_api_hub::handlers::task_handler::{bulk_create_tasks, bulk_delete_tasks, import_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskCreate};
+
+fn limited(max: usize) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        max_bulk_items: max,
+        ..Default::default()
+    })
+}
+
+fn ids(n: usize) -> Vec<String> {
+    (0..n).map(|_| uuid::Uuid::new_v4().to_string()).collect()
+}
+
+#[tokio::test]
+async fn bulk_delete_over_limit_returns_400() {
+    let repo = limited(3);
+    let t = repo.insert(Task::new_full("keep", "d")).unwrap();
+    let mut payload = ids(3);
+    payload.push(t.id.to_string());
+    let (code, Json(resp)) = bulk_delete_tasks(State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(resp["error"], "too many items");
+    // rejected before any repository work
+    assert_eq!(repo.count(), 1);
+}
+
+#[tokio::test]
+async fn bulk_delete_at_limit_succeeds() {
+    let repo = limited(3);
+    let t = repo.insert(Task::new_full("gone", "d")).unwrap();
+    let mut payload = ids(2);
+    payload.push(t.id.to_string());
+    let (code, Json(resp)) = bulk_delete_tasks(State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["deleted"], 1);
+}
+
+#[tokio::test]
+async fn bulk_create_over_limit_returns_400() {
+    let repo = limited(2);
+    let payload: Vec<TaskCreate> = (0..3)
+        .map(|i| TaskCreate {
+            title: format!("t{}", i),
+            ..Default::default()
+        })
+        .collect();
+    let (code, _) = bulk_create_tasks(State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.count(), 0);
+}
+
+#[tokio::test]
+async fn json_import_over_limit_returns_400() {
+    let repo = limited(1);
+    let body = Bytes::from(r#"[{"title":"a","description":""},{"title":"b","description":""}]"#);
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        HeaderMap::new(),
+        Query(Default::default()),
+        body,
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(resp["error"], "too many items");
+    assert_eq!(repo.count(), 0);
+}
+
+#[test]
+fn default_limit_is_ten_thousand() {
+    assert_eq!(AppConfig::default().max_bulk_items, DEFAULT_MAX_BULK_ITEMS);
+    assert_eq!(DEFAULT_MAX_BULK_ITEMS, 10_000);
+}
@@ -0,0 +1,76 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{ChangesQuery, get_changes, update_task};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskUpdate};
+
+fn repo() -> TaskRepository {
+    TaskRepository::new()
+}
+
+#[tokio::test]
+async fn changes_returns_only_recent_updates() {
+    let repo = repo();
+    let old = Task::new_full("old", "d");
+    let touched = Task::new_full("touched", "d");
+    repo.insert(old.clone()).unwrap();
+    repo.insert(touched.clone()).unwrap();
+
+    // first sync: everything
+    let (code, Json(first)) =
+        get_changes(State(repo.clone()), Query(ChangesQuery { since: None })).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(first["items"].as_array().unwrap().len(), 2);
+    let since = first["now"].as_str().unwrap().to_string();
+
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    let upd = TaskUpdate {
+        title: None,
+        description: None,
+        completed: Some(true),
+        color: None,
+    };
+    let _ = update_task(Path(touched.id.to_string()), State(repo.clone()), Json(upd)).await;
+
+    let (code, Json(resp)) = get_changes(
+        State(repo.clone()),
+        Query(ChangesQuery { since: Some(since) }),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    let items = resp["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"].as_str().unwrap(), touched.id.to_string());
+}
+
+#[tokio::test]
+async fn changes_sorted_by_updated_at_and_now_parseable() {
+    let repo = repo();
+    let a = Task::new_full("a", "d");
+    std::thread::sleep(std::time::Duration::from_millis(1));
+    let b = Task::new_full("b", "d");
+    repo.insert(b.clone()).unwrap();
+    repo.insert(a.clone()).unwrap();
+
+    let (_code, Json(resp)) =
+        get_changes(State(repo.clone()), Query(ChangesQuery { since: None })).await;
+    let items = resp["items"].as_array().unwrap();
+    assert_eq!(items[0]["title"].as_str().unwrap(), "a");
+    assert_eq!(items[1]["title"].as_str().unwrap(), "b");
+    let now = resp["now"].as_str().expect("now present");
+    assert!(chrono::DateTime::parse_from_rfc3339(now).is_ok());
+}
+
+#[tokio::test]
+async fn changes_invalid_since_returns_400() {
+    let repo = repo();
+    let (code, _) = get_changes(
+        State(repo),
+        Query(ChangesQuery {
+            since: Some("yesterday".into()),
+        }),
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,92 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::create_task;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskCreate};
+use uuid::Uuid;
+
+fn create(title: &str, id: Option<Uuid>) -> Json<TaskCreate> {
+    Json(TaskCreate {
+        title: title.into(),
+        description: "d".into(),
+        id,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn insert_if_absent_does_not_overwrite() {
+    let repo = TaskRepository::new();
+    let t = Task::new_full("first", "d");
+    let stored = repo.insert_if_absent(t.clone()).unwrap().unwrap();
+    assert_eq!(stored.id, t.id);
+
+    let mut other = Task::new_full("second", "d");
+    other.id = t.id;
+    assert!(repo.insert_if_absent(other).unwrap().is_none());
+    assert_eq!(repo.get(&t.id).unwrap().title, "first");
+    assert_eq!(repo.count(), 1);
+}
+
+#[tokio::test]
+async fn create_with_new_client_id_uses_it() {
+    let repo = TaskRepository::new();
+    let id = Uuid::new_v4();
+    let (code, Json(task)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        create("mine", Some(id)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(task.id, id);
+    assert_eq!(repo.get(&id).unwrap().title, "mine");
+}
+
+#[tokio::test]
+async fn duplicate_client_id_returns_409_without_overwriting() {
+    let repo = TaskRepository::new();
+    let id = Uuid::new_v4();
+    let _ = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        create("original", Some(id)),
+    )
+    .await
+    .unwrap();
+
+    let (code, Json(resp)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        create("imposter", Some(id)),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(code, StatusCode::CONFLICT);
+    assert_eq!(resp["id"], id.to_string());
+    assert_eq!(repo.get(&id).unwrap().title, "original");
+    assert_eq!(repo.count(), 1);
+}
+
+#[tokio::test]
+async fn without_client_id_a_fresh_id_is_generated() {
+    let repo = TaskRepository::new();
+    let (_, Json(a)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        create("a", None),
+    )
+    .await
+    .unwrap();
+    let (_, Json(b)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        create("b", None),
+    )
+    .await
+    .unwrap();
+    assert_ne!(a.id, b.id);
+    assert_eq!(repo.count(), 2);
+}
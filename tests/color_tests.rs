@@ -0,0 +1,96 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use rust_api_hub::handlers::task_handler::{ColorPayload, create_task, set_color, update_task};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskCreate, TaskUpdate};
+
+fn color(c: Option<&str>) -> Json<ColorPayload> {
+    Json(ColorPayload {
+        color: c.map(String::from),
+    })
+}
+
+#[tokio::test]
+async fn set_valid_color_then_clear_it() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+
+    let (code, Json(v)) = set_color(
+        Path(t.id.to_string()),
+        State(repo.clone()),
+        color(Some("#1a2B3c")),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(v["task"]["color"], "#1a2B3c");
+
+    let (code, Json(v)) = set_color(Path(t.id.to_string()), State(repo.clone()), color(None)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert!(v["task"]["color"].is_null());
+    assert_eq!(repo.get(&t.id).unwrap().color, None);
+}
+
+#[tokio::test]
+async fn invalid_hex_is_rejected_everywhere() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    for bad in ["red", "#fff", "#12345g", "123456"] {
+        let (code, _) = set_color(
+            Path(t.id.to_string()),
+            State(repo.clone()),
+            color(Some(bad)),
+        )
+        .await;
+        assert_eq!(code, StatusCode::BAD_REQUEST, "{}", bad);
+    }
+
+    let (code, Json(v)) = update_task(
+        Path(t.id.to_string()),
+        State(repo.clone()),
+        Json(TaskUpdate {
+            color: Some("blue".into()),
+            ..Default::default()
+        }),
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(v["errors"][0]["field"], "color");
+
+    let payload = TaskCreate {
+        title: "t".into(),
+        color: Some("#zzzzzz".into()),
+        ..Default::default()
+    };
+    let err = create_task(State(repo.clone()), HeaderMap::new(), Json(payload))
+        .await
+        .unwrap_err();
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.get(&t.id).unwrap().color, None);
+}
+
+#[tokio::test]
+async fn color_is_settable_on_create_and_update() {
+    let repo = TaskRepository::new();
+    let payload = TaskCreate {
+        title: "t".into(),
+        color: Some("#ff0000".into()),
+        ..Default::default()
+    };
+    let (_, Json(task)) = create_task(State(repo.clone()), HeaderMap::new(), Json(payload))
+        .await
+        .unwrap();
+    assert_eq!(task.color.as_deref(), Some("#ff0000"));
+
+    let (code, Json(v)) = update_task(
+        Path(task.id.to_string()),
+        State(repo),
+        Json(TaskUpdate {
+            color: Some("#00ff00".into()),
+            ..Default::default()
+        }),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(v["task"]["color"], "#00ff00");
+}
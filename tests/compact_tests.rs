@@ -0,0 +1,110 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{CompactParams, compact_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::persistence::load_snapshot;
+
+fn file_backed(path: &std::path::Path) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        data_file: Some(path.to_string_lossy().into_owned()),
+        ..Default::default()
+    })
+}
+
+async fn compact(repo: &TaskRepository, indent: bool) -> (StatusCode, serde_json::Value) {
+    let (status, Json(body)) =
+        compact_tasks(State(repo.clone()), Query(CompactParams { indent })).await;
+    (status, body)
+}
+
+#[tokio::test]
+async fn compaction_after_deletes_shrinks_the_file_to_current_state() {
+    let path = std::env::temp_dir().join(format!("data-{}.json", uuid::Uuid::new_v4()));
+    let repo = file_backed(&path);
+    let tasks: Vec<Task> = (0..5)
+        .map(|i| {
+            repo.insert(Task::new_full(&format!("t{}", i), "d"))
+                .unwrap()
+        })
+        .collect();
+
+    let (status, body) = compact(&repo, false).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["tasks"], 5);
+    let before = std::fs::metadata(&path).unwrap().len();
+    assert_eq!(body["bytes"], before);
+
+    for t in &tasks[..3] {
+        assert!(repo.remove(&t.id));
+    }
+    let (_, body) = compact(&repo, false).await;
+    assert_eq!(body["tasks"], 2);
+    let after = std::fs::metadata(&path).unwrap().len();
+    assert!(after < before);
+
+    let stored: Vec<Task> = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    let mut ids: Vec<_> = stored.iter().map(|t| t.id).collect();
+    let mut live: Vec<_> = repo.list().iter().map(|t| t.id).collect();
+    ids.sort();
+    live.sort();
+    assert_eq!(ids, live);
+
+    // the snapshot round-trips into a fresh repository
+    let reloaded = TaskRepository::new();
+    load_snapshot(&reloaded, &path.to_string_lossy());
+    assert_eq!(reloaded.count(), 2);
+    assert_eq!(reloaded.get(&tasks[4].id).unwrap().title, "t4");
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn indent_writes_a_larger_file() {
+    let path = std::env::temp_dir().join(format!("data-{}.json", uuid::Uuid::new_v4()));
+    let repo = file_backed(&path);
+    repo.insert(Task::new_full("t", "d")).unwrap();
+    let (_, compact_body) = compact(&repo, false).await;
+    let (_, pretty_body) = compact(&repo, true).await;
+    assert!(pretty_body["bytes"].as_u64() > compact_body["bytes"].as_u64());
+    assert!(std::fs::read_to_string(&path).unwrap().contains('\n'));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn in_memory_repo_is_rejected() {
+    let repo = TaskRepository::new();
+    let (status, body) = compact(&repo, false).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "repository is not file-backed");
+}
+
+#[tokio::test]
+async fn concurrent_compactions_leave_a_valid_file() {
+    let dir = std::env::temp_dir().join(format!("compact-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir(&dir).unwrap();
+    let path = dir.join("data.json");
+    let repo = file_backed(&path);
+    for i in 0..50 {
+        repo.insert(Task::new_full(&format!("t{}", i), "d"))
+            .unwrap();
+    }
+
+    // indented and compact snapshots differ in length, so a torn write would not parse
+    let runs: Vec<_> = (0..8)
+        .map(|i| {
+            let repo = repo.clone();
+            tokio::spawn(async move { compact(&repo, i % 2 == 0).await.0 })
+        })
+        .collect();
+    for r in runs {
+        assert_eq!(r.await.unwrap(), StatusCode::OK);
+    }
+
+    let stored: Vec<Task> = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(stored.len(), 50);
+    // no temporary files are left behind
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,84 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::handlers::task_handler::delete_task;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn if_match(v: &str) -> HeaderMap {
+    let mut h = HeaderMap::new();
+    h.insert(header::IF_MATCH, HeaderValue::from_str(v).unwrap());
+    h
+}
+
+fn seeded() -> (TaskRepository, Task) {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    (repo, t)
+}
+
+#[tokio::test]
+async fn matching_etag_deletes() {
+    let (repo, t) = seeded();
+    let etag = repo.etag(&t.id).unwrap();
+    let (status, _) =
+        delete_task(Path(t.id.to_string()), State(repo.clone()), if_match(&etag)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(repo.get(&t.id).is_none());
+}
+
+#[tokio::test]
+async fn matching_updated_at_deletes() {
+    let (repo, t) = seeded();
+    let at = t.updated_at.to_rfc3339();
+    let (status, _) = delete_task(Path(t.id.to_string()), State(repo.clone()), if_match(&at)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(repo.get(&t.id).is_none());
+}
+
+#[tokio::test]
+async fn stale_etag_returns_412_and_keeps_task() {
+    let (repo, t) = seeded();
+    let stale = repo.etag(&t.id).unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    repo.modify(&t.id, |t| {
+        t.title = "changed".into();
+        t.updated_at = chrono::Utc::now();
+    });
+
+    let (status, Json(body)) = delete_task(
+        Path(t.id.to_string()),
+        State(repo.clone()),
+        if_match(&stale),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert_eq!(body["error"], "precondition failed");
+    assert_eq!(body["etag"], repo.etag(&t.id).unwrap());
+    assert!(repo.get(&t.id).is_some());
+}
+
+#[tokio::test]
+async fn no_header_or_wildcard_deletes() {
+    let (repo, t) = seeded();
+    let (status, _) = delete_task(
+        Path(t.id.to_string()),
+        State(repo.clone()),
+        HeaderMap::new(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let u = repo.insert(Task::new_full("u", "d")).unwrap();
+    let (status, _) = delete_task(Path(u.id.to_string()), State(repo.clone()), if_match("*")).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(repo.count(), 0);
+}
+
+#[tokio::test]
+async fn if_match_on_missing_task_is_404() {
+    let repo = TaskRepository::new();
+    let id = uuid::Uuid::new_v4().to_string();
+    let (status, _) = delete_task(Path(id), State(repo), if_match("*")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
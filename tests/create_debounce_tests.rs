@@ -0,0 +1,103 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::create_task;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::TaskCreate;
+use std::time::Duration;
+
+fn repo(ms: Option<u64>) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        create_debounce_ms: ms,
+        ..Default::default()
+    })
+}
+
+async fn create(repo: &TaskRepository, title: &str) -> StatusCode {
+    let payload = TaskCreate {
+        title: title.into(),
+        description: "d".into(),
+        ..Default::default()
+    };
+    match create_task(State(repo.clone()), HeaderMap::new(), Json(payload)).await {
+        Ok((status, _)) => status,
+        Err((status, _)) => status,
+    }
+}
+
+#[tokio::test]
+async fn identical_create_within_window_is_throttled() {
+    let repo = repo(Some(60_000));
+    assert_eq!(create(&repo, "Buy milk").await, StatusCode::CREATED);
+    // normalized: case and surrounding whitespace do not matter
+    assert_eq!(
+        create(&repo, "  buy MILK ").await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    assert_eq!(repo.count(), 1);
+}
+
+#[tokio::test]
+async fn identical_create_after_window_succeeds() {
+    let repo = repo(Some(50));
+    assert_eq!(create(&repo, "Buy milk").await, StatusCode::CREATED);
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert_eq!(create(&repo, "Buy milk").await, StatusCode::CREATED);
+    assert_eq!(repo.count(), 2);
+}
+
+#[tokio::test]
+async fn distinct_titles_are_never_throttled() {
+    let repo = repo(Some(60_000));
+    for i in 0..5 {
+        assert_eq!(
+            create(&repo, &format!("task {}", i)).await,
+            StatusCode::CREATED
+        );
+    }
+    assert_eq!(repo.count(), 5);
+}
+
+#[tokio::test]
+async fn disabled_by_default() {
+    let repo = repo(None);
+    assert_eq!(create(&repo, "same").await, StatusCode::CREATED);
+    assert_eq!(create(&repo, "same").await, StatusCode::CREATED);
+    assert_eq!(repo.debounce_entries(), 0);
+}
+
+#[test]
+fn stale_entries_are_pruned_once_the_map_grows() {
+    let repo = TaskRepository::new();
+    let window = Duration::from_millis(1);
+    for i in 0..1024 {
+        assert!(repo.debounce_create(&format!("t{}", i), window));
+    }
+    std::thread::sleep(Duration::from_millis(5));
+    assert!(repo.debounce_create("fresh", window));
+    assert_eq!(repo.debounce_entries(), 1);
+}
+
+#[tokio::test]
+async fn failed_create_does_not_claim_the_title() {
+    let repo = repo(Some(60_000));
+    let taken = repo
+        .insert(rust_api_hub::models::task::Task::new_full("first", "d"))
+        .unwrap();
+    // the client-supplied id collides, so nothing is created
+    let payload = TaskCreate {
+        id: Some(taken.id),
+        title: "Write report".into(),
+        description: "d".into(),
+        ..Default::default()
+    };
+    let Err((status, _)) = create_task(State(repo.clone()), HeaderMap::new(), Json(payload)).await
+    else {
+        panic!("duplicate id was accepted");
+    };
+    assert_eq!(status, StatusCode::CONFLICT);
+    // the corrected retry is not throttled
+    assert_eq!(create(&repo, "Write report").await, StatusCode::CREATED);
+    assert_eq!(repo.count(), 2);
+}
@@ -16,8 +16,15 @@ async fn created_at_is_present_and_valid_format() {
     let payload = TaskCreate {
         title: "t1".into(),
         description: "d1".into(),
+        ..Default::default()
     };
-    let (code, created) = create_task(State(repo.clone()), Json(payload)).await;
+    let (code, created) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap();
     assert_eq!(code, StatusCode::CREATED);
     // created_at should be a valid RFC3339 timestamp when serialized
     let ca = created.created_at.to_rfc3339();
@@ -34,8 +41,15 @@ async fn created_at_uniqueness_for_multiple_creates() {
         let payload = TaskCreate {
             title: format!("t{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (code, created) = create_task(State(repo.clone()), Json(payload)).await;
+        let (code, created) = create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
         assert_eq!(code, StatusCode::CREATED);
         timestamps.push(created.created_at.to_rfc3339());
         // small sleep to avoid identical timestamps on very fast systems
@@ -55,8 +69,15 @@ async fn created_at_retained_in_repository_and_serialized() {
     let payload = TaskCreate {
         title: "t1".into(),
         description: "d1".into(),
+        ..Default::default()
     };
-    let (_code, created) = create_task(State(repo.clone()), Json(payload)).await;
+    let (_code, created) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap();
     let id = created.id;
     // fetch stored task
     let stored = repo.get(&id).expect("task should be present");
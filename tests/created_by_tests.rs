@@ -0,0 +1,55 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue};
+use rust_api_hub::handlers::task_handler::{ListParams, MAX_ACTOR_LEN, create_task, get_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskCreate};
+
+async fn create(repo: &TaskRepository, actor: Option<&str>) -> Task {
+    let mut headers = HeaderMap::new();
+    if let Some(a) = actor {
+        headers.insert("x-actor", HeaderValue::from_str(a).unwrap());
+    }
+    let payload = TaskCreate {
+        title: "t".into(),
+        description: "d".into(),
+        ..Default::default()
+    };
+    let (_, Json(task)) = create_task(State(repo.clone()), headers, Json(payload))
+        .await
+        .unwrap();
+    task
+}
+
+#[tokio::test]
+async fn created_by_filter_returns_only_that_actors_tasks() {
+    let repo = TaskRepository::new();
+    let alice = create(&repo, Some("alice")).await;
+    create(&repo, Some("bob")).await;
+    create(&repo, None).await;
+    assert_eq!(alice.created_by.as_deref(), Some("alice"));
+
+    let params = ListParams {
+        created_by: Some("alice".into()),
+        ..Default::default()
+    };
+    let Json(v) = get_tasks(State(repo), HeaderMap::new(), Query(params))
+        .await
+        .unwrap();
+    let items = v["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], alice.id.to_string());
+}
+
+#[tokio::test]
+async fn actor_is_trimmed_and_length_limited() {
+    let repo = TaskRepository::new();
+    assert_eq!(
+        create(&repo, Some("  carol  ")).await.created_by.as_deref(),
+        Some("carol")
+    );
+    assert_eq!(create(&repo, Some("   ")).await.created_by, None);
+    let long = "x".repeat(MAX_ACTOR_LEN + 10);
+    let task = create(&repo, Some(&long)).await;
+    assert_eq!(task.created_by.unwrap().len(), MAX_ACTOR_LEN);
+}
@@ -0,0 +1,116 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{
+    PriorityPayload, create_task, set_priority, update_task,
+};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, Task, TaskCreate, TaskUpdate};
+
+fn repo(min: usize) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        critical_min_desc: min,
+        ..Default::default()
+    })
+}
+
+fn create(desc: &str, priority: Priority) -> Json<TaskCreate> {
+    Json(TaskCreate {
+        title: "t".into(),
+        description: desc.into(),
+        priority: Some(priority),
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn short_critical_description_is_rejected_on_create() {
+    let repo = repo(10);
+    let (code, Json(err)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        create("too short", Priority::Critical),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        err["error"],
+        "critical tasks require a description of at least 10 chars"
+    );
+    assert_eq!(repo.count(), 0);
+}
+
+#[tokio::test]
+async fn long_enough_critical_description_is_accepted() {
+    let repo = repo(10);
+    let (code, Json(task)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        create("detailed enough", Priority::Critical),
+    )
+    .await
+    .unwrap();
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(task.priority, Priority::Critical);
+}
+
+#[tokio::test]
+async fn non_critical_tasks_are_unaffected() {
+    let repo = repo(10);
+    for p in [Priority::Low, Priority::Medium, Priority::High] {
+        let res = create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            create("", p),
+        )
+        .await;
+        assert!(res.is_ok());
+    }
+}
+
+#[tokio::test]
+async fn update_cannot_shorten_a_critical_description() {
+    let repo = repo(10);
+    let (_, Json(task)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        create("detailed enough", Priority::Critical),
+    )
+    .await
+    .unwrap();
+    let upd = TaskUpdate {
+        description: Some("short".into()),
+        ..Default::default()
+    };
+    let (code, _) = update_task(Path(task.id.to_string()), State(repo.clone()), Json(upd)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.get(&task.id).unwrap().description, "detailed enough");
+}
+
+#[tokio::test]
+async fn raising_priority_to_critical_checks_the_description() {
+    let repo = repo(10);
+    let t = repo.insert(Task::new_full("t", "short")).unwrap();
+    let payload = PriorityPayload {
+        priority: "critical".into(),
+    };
+    let err = set_priority(Path(t.id.to_string()), State(repo.clone()), Json(payload))
+        .await
+        .unwrap_err();
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.get(&t.id).unwrap().priority, Priority::Medium);
+}
+
+#[tokio::test]
+async fn rule_is_off_by_default() {
+    let repo = repo(0);
+    let res = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        create("", Priority::Critical),
+    )
+    .await;
+    assert!(res.is_ok());
+}
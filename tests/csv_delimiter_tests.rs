@@ -0,0 +1,52 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::handlers::task_handler::{ImportParams, import_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+
+fn csv_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+    headers
+}
+
+fn delimiter(d: &str) -> Query<ImportParams> {
+    Query(ImportParams {
+        delimiter: Some(d.into()),
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn semicolon_delimited_csv_imports() {
+    let repo = TaskRepository::new();
+    let body = Bytes::from("title;description\nfirst;has, a comma\nsecond;plain\n");
+    let (code, Json(resp)) =
+        import_tasks(State(repo.clone()), csv_headers(), delimiter(";"), body).await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"], 2);
+    assert_eq!(resp["tasks"][0]["description"], "has, a comma");
+}
+
+#[tokio::test]
+async fn tab_delimited_csv_imports() {
+    let repo = TaskRepository::new();
+    let body = Bytes::from("title\tdescription\nfirst\tone\nsecond\ttwo\n");
+    let (code, Json(resp)) =
+        import_tasks(State(repo.clone()), csv_headers(), delimiter("\t"), body).await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"], 2);
+    assert_eq!(repo.count(), 2);
+}
+
+#[tokio::test]
+async fn multi_char_delimiter_is_rejected() {
+    let repo = TaskRepository::new();
+    let body = Bytes::from("title;;description\na;;b\n");
+    let (code, Json(resp)) =
+        import_tasks(State(repo.clone()), csv_headers(), delimiter(";;"), body).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert!(resp["error"].as_str().unwrap().contains("delimiter"));
+    assert_eq!(repo.count(), 0);
+}
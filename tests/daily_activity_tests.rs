@@ -0,0 +1,164 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use chrono::{DateTime, Duration, Utc};
+use rust_api_hub::handlers::task_handler::{DailyActivityParams, get_daily_activity};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn at(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+}
+
+fn task(repo: &TaskRepository, created: &str, completed: Option<&str>) {
+    let mut t = Task::new_full("t", "d");
+    t.created_at = at(created);
+    t.updated_at = t.created_at;
+    if let Some(c) = completed {
+        t.completed = true;
+        t.completed_at = Some(at(c));
+        t.updated_at = at(c);
+    }
+    repo.insert(t).unwrap();
+}
+
+/// Two tasks created on the 1st (one completed on the 2nd), one created and completed
+/// on the 2nd, one outside the range.
+fn seeded() -> TaskRepository {
+    let repo = TaskRepository::new();
+    task(&repo, "2024-03-01T09:00:00Z", None);
+    task(&repo, "2024-03-01T10:00:00Z", Some("2024-03-02T08:00:00Z"));
+    task(&repo, "2024-03-02T11:00:00Z", Some("2024-03-02T12:00:00Z"));
+    task(&repo, "2024-02-20T10:00:00Z", None);
+    repo
+}
+
+async fn daily(
+    repo: &TaskRepository,
+    params: DailyActivityParams,
+) -> (StatusCode, serde_json::Value) {
+    let (status, Json(body)) = get_daily_activity(State(repo.clone()), Query(params)).await;
+    (status, body)
+}
+
+#[tokio::test]
+async fn counts_per_day_in_range() {
+    let repo = seeded();
+    let (status, body) = daily(
+        &repo,
+        DailyActivityParams {
+            from: Some("2024-03-01".into()),
+            to: Some("2024-03-05".into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["days"],
+        serde_json::json!([
+            {"date": "2024-03-01", "created": 2, "updated": 0, "completed": 0},
+            {"date": "2024-03-02", "created": 1, "updated": 2, "completed": 2},
+        ])
+    );
+}
+
+#[tokio::test]
+async fn include_empty_lists_every_day() {
+    let repo = seeded();
+    let (_, body) = daily(
+        &repo,
+        DailyActivityParams {
+            from: Some("2024-03-01".into()),
+            to: Some("2024-03-04".into()),
+            include_empty: true,
+        },
+    )
+    .await;
+    let days = body["days"].as_array().unwrap();
+    assert_eq!(days.len(), 4);
+    assert_eq!(days[3]["date"], "2024-03-04");
+    assert_eq!(days[3]["created"], 0);
+}
+
+#[tokio::test]
+async fn default_range_ends_today_and_bad_ranges_are_rejected() {
+    let repo = TaskRepository::new();
+    let mut t = Task::new_full("t", "d");
+    t.created_at = Utc::now() - Duration::days(40);
+    t.updated_at = t.created_at;
+    repo.insert(t).unwrap();
+    repo.insert(Task::new_full("today", "d")).unwrap();
+    let (_, body) = daily(&repo, DailyActivityParams::default()).await;
+    let days = body["days"].as_array().unwrap();
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[0]["date"], Utc::now().format("%Y-%m-%d").to_string());
+
+    let (status, _) = daily(
+        &repo,
+        DailyActivityParams {
+            from: Some("2024-03-05".into()),
+            to: Some("2024-03-01".into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn extreme_dates_do_not_panic() {
+    let repo = seeded();
+    // the default `from` would fall before the earliest representable date
+    let (status, _) = daily(
+        &repo,
+        DailyActivityParams {
+            to: Some("-262143-01-01".into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // the last representable day has no successor
+    let (status, body) = daily(
+        &repo,
+        DailyActivityParams {
+            from: Some("+262142-12-31".into()),
+            to: Some("+262142-12-31".into()),
+            include_empty: true,
+        },
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["days"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn ranges_over_the_cap_are_rejected() {
+    let repo = seeded();
+    let (status, body) = daily(
+        &repo,
+        DailyActivityParams {
+            from: Some("-262143-01-01".into()),
+            to: Some("+262142-12-31".into()),
+            include_empty: true,
+        },
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("range too long"));
+
+    // exactly the cap is fine
+    let (status, body) = daily(
+        &repo,
+        DailyActivityParams {
+            from: Some("2024-01-01".into()),
+            to: Some("2024-12-31".into()),
+            include_empty: true,
+        },
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["days"].as_array().unwrap().len(), 366);
+}
@@ -0,0 +1,65 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{create_task, import_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::TaskCreate;
+
+fn repo_with_default() -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        default_description: "imported".into(),
+        ..Default::default()
+    })
+}
+
+async fn create(repo: &TaskRepository, description: &str) -> String {
+    let (code, Json(task)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(TaskCreate {
+            title: "t".into(),
+            description: description.into(),
+            ..Default::default()
+        }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(code, StatusCode::CREATED);
+    task.description
+}
+
+#[tokio::test]
+async fn blank_create_description_gets_default() {
+    let repo = repo_with_default();
+    assert_eq!(create(&repo, "").await, "imported");
+    assert_eq!(create(&repo, "   ").await, "imported");
+    assert_eq!(create(&repo, "keep me").await, "keep me");
+}
+
+#[tokio::test]
+async fn blank_import_description_gets_default() {
+    let repo = repo_with_default();
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+    let csv = "title,description\nFirst,\nSecond,has one\n";
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        headers,
+        Query(Default::default()),
+        Bytes::from(csv),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"], 2);
+    let mut descs: Vec<_> = repo.list().into_iter().map(|t| t.description).collect();
+    descs.sort();
+    assert_eq!(descs, vec!["has one", "imported"]);
+}
+
+#[tokio::test]
+async fn unset_default_leaves_blank_descriptions() {
+    let repo = TaskRepository::new();
+    assert_eq!(create(&repo, "").await, "");
+}
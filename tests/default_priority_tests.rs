@@ -0,0 +1,62 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{create_task, import_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, TaskCreate};
+
+fn repo_with_low() -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        default_priority: Priority::Low,
+        ..Default::default()
+    })
+}
+
+async fn create(repo: &TaskRepository, priority: Option<Priority>) -> Priority {
+    let (code, Json(task)) = create_task(
+        State(repo.clone()),
+        HeaderMap::new(),
+        Json(TaskCreate {
+            title: "t".into(),
+            description: "d".into(),
+            priority,
+            ..Default::default()
+        }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(code, StatusCode::CREATED);
+    task.priority
+}
+
+#[tokio::test]
+async fn configured_default_applies_to_created_tasks() {
+    let repo = repo_with_low();
+    assert_eq!(create(&repo, None).await, Priority::Low);
+    assert_eq!(create(&repo, Some(Priority::High)).await, Priority::High);
+}
+
+#[tokio::test]
+async fn configured_default_applies_to_imports() {
+    let repo = repo_with_low();
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        headers,
+        Query(Default::default()),
+        Bytes::from("title,description\nFirst,x\nSecond,y\n"),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"], 2);
+    assert!(repo.list().iter().all(|t| t.priority == Priority::Low));
+}
+
+#[tokio::test]
+async fn unset_default_is_medium() {
+    let repo = TaskRepository::new();
+    assert_eq!(create(&repo, None).await, Priority::Medium);
+}
@@ -0,0 +1,62 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt; // oneshot
+
+fn seeded(repo: TaskRepository) -> TaskRepository {
+    for i in 0..3 {
+        let mut t = Task::new_full(&format!("t{}", i), "d");
+        t.tags = vec!["x".into()];
+        repo.insert(t).unwrap();
+    }
+    repo
+}
+
+async fn delete_all(repo: &TaskRepository) -> (StatusCode, serde_json::Value) {
+    let resp = build_router(repo.clone())
+        .oneshot(Request::delete("/tasks/all").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[test]
+fn drain_returns_all_tasks_and_empties_repo() {
+    let repo = seeded(TaskRepository::new());
+    let mut ids: Vec<_> = repo.list().iter().map(|t| t.id).collect();
+    let mut drained: Vec<_> = repo.drain().iter().map(|t| t.id).collect();
+    ids.sort();
+    drained.sort();
+    assert_eq!(drained, ids);
+    assert_eq!(repo.count(), 0);
+    assert!(repo.list_by_tag("x").is_empty());
+    assert!(repo.drain().is_empty());
+}
+
+#[tokio::test]
+async fn delete_all_is_forbidden_by_default() {
+    let repo = seeded(TaskRepository::new());
+    let (status, body) = delete_all(&repo).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"], "delete all is disabled");
+    assert_eq!(repo.count(), 3);
+}
+
+#[tokio::test]
+async fn delete_all_removes_everything_when_enabled() {
+    let repo = seeded(TaskRepository::new().with_config(AppConfig {
+        allow_delete_all: true,
+        ..Default::default()
+    }));
+    let (status, body) = delete_all(&repo).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["deleted"], 3);
+    assert_eq!(repo.count(), 0);
+}
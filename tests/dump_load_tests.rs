@@ -0,0 +1,113 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, Task};
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt;
+
+fn populated() -> TaskRepository {
+    let repo = TaskRepository::new();
+    let now = chrono::Utc::now();
+    for i in 0..5 {
+        let mut t = Task::new_full(&format!("task {}", i), "d");
+        t.created_at = now - chrono::Duration::minutes(60 - i);
+        t.updated_at = now - chrono::Duration::minutes(30 - i);
+        t.tags = vec![format!("tag{}", i % 2)];
+        t.priority = Priority::High;
+        t.completed = i % 2 == 0;
+        repo.insert(t).unwrap();
+    }
+    repo
+}
+
+async fn send(repo: &TaskRepository, req: Request<Body>) -> (StatusCode, Vec<u8>) {
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    (status, body.to_vec())
+}
+
+fn sorted(mut tasks: Vec<Task>) -> Vec<Task> {
+    tasks.sort_by_key(|t| t.id);
+    tasks
+}
+
+#[tokio::test]
+async fn dump_then_load_round_trips_exactly() {
+    let source = populated();
+    let (status, dump) = send(
+        &source,
+        Request::get("/tasks/dump").body(Body::empty()).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let dumped: Vec<Task> = serde_json::from_slice(&dump).unwrap();
+    assert_eq!(dumped.len(), 5);
+
+    let target = TaskRepository::new();
+    let (status, body) = send(
+        &target,
+        Request::post("/tasks/load")
+            .header("content-type", "application/json")
+            .body(Body::from(dump))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let v: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(v["loaded"], 5);
+
+    // ids, timestamps, seq and every other field survive unchanged
+    assert_eq!(sorted(target.list()), sorted(source.list()));
+}
+
+#[tokio::test]
+async fn load_replaces_existing_tasks() {
+    let repo = populated();
+    let keep = Task::new_full("only", "d");
+    let payload = serde_json::to_vec(&vec![keep.clone()]).unwrap();
+    let (status, _) = send(
+        &repo,
+        Request::post("/tasks/load")
+            .header("content-type", "application/json")
+            .body(Body::from(payload))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let tasks = repo.list();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].id, keep.id);
+    assert_eq!(tasks[0].created_at, keep.created_at);
+    // a task without a seq gets one so `GET /tasks/seq/{n}` still works
+    assert_ne!(tasks[0].seq, 0);
+}
+
+#[tokio::test]
+async fn load_rejects_invalid_and_duplicate_tasks() {
+    let repo = populated();
+    let (status, _) = send(
+        &repo,
+        Request::post("/tasks/load")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"[{"title":"missing id and timestamps"}]"#))
+            .unwrap(),
+    )
+    .await;
+    assert!(status.is_client_error());
+
+    let t = Task::new_full("dup", "d");
+    let payload = serde_json::to_vec(&vec![t.clone(), t]).unwrap();
+    let (status, body) = send(
+        &repo,
+        Request::post("/tasks/load")
+            .header("content-type", "application/json")
+            .body(Body::from(payload))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8_lossy(&body).contains("duplicate id"));
+    // failed loads leave the repository untouched
+    assert_eq!(repo.count(), 5);
+}
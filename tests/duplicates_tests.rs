@@ -0,0 +1,65 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use rust_api_hub::handlers::task_handler::{DuplicatesParams, find_duplicates};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::utils::text::levenshtein;
+
+fn add(repo: &TaskRepository, title: &str) -> Task {
+    let t = repo.insert(Task::new_full(title, "d")).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(1));
+    t
+}
+
+async fn groups(repo: &TaskRepository, threshold: Option<usize>) -> serde_json::Value {
+    let Json(resp) =
+        find_duplicates(State(repo.clone()), Query(DuplicatesParams { threshold })).await;
+    resp["groups"].clone()
+}
+
+#[tokio::test]
+async fn same_title_tasks_form_one_group() {
+    let repo = TaskRepository::new();
+    let a = add(&repo, "Fix login");
+    add(&repo, "unique task");
+    let b = add(&repo, "  fix LOGIN ");
+
+    let g = groups(&repo, None).await;
+    assert_eq!(
+        g,
+        serde_json::json!([{"title": "fix login", "ids": [a.id, b.id]}])
+    );
+}
+
+#[tokio::test]
+async fn unique_titles_are_not_reported() {
+    let repo = TaskRepository::new();
+    add(&repo, "one");
+    add(&repo, "two");
+    assert!(groups(&repo, None).await.as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn threshold_groups_near_duplicates() {
+    let repo = TaskRepository::new();
+    let a = add(&repo, "fix login");
+    let b = add(&repo, "fix logn");
+    add(&repo, "write docs");
+
+    // exact matching sees no duplicates
+    assert!(groups(&repo, None).await.as_array().unwrap().is_empty());
+
+    let g = groups(&repo, Some(1)).await;
+    assert_eq!(
+        g,
+        serde_json::json!([{"title": "fix login", "ids": [a.id, b.id]}])
+    );
+}
+
+#[test]
+fn levenshtein_distances() {
+    assert_eq!(levenshtein("", ""), 0);
+    assert_eq!(levenshtein("abc", ""), 3);
+    assert_eq!(levenshtein("kitten", "sitting"), 3);
+    assert_eq!(levenshtein("fix login", "fix logn"), 1);
+}
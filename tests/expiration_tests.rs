@@ -0,0 +1,176 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use chrono::{Duration, Utc};
+use rust_api_hub::handlers::task_handler::{
+    GetParams, ListParams, create_task, gc_expired, get_task, get_tasks, head_task,
+};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::TaskCreate;
+
+fn create(title: &str, expires_in: Option<Duration>) -> TaskCreate {
+    TaskCreate {
+        title: title.into(),
+        description: "d".into(),
+        expires_at: expires_in.map(|d| Utc::now() + d),
+        ..Default::default()
+    }
+}
+
+fn list_len(v: &serde_json::Value) -> usize {
+    v["items"].as_array().unwrap().len()
+}
+
+#[tokio::test]
+async fn expired_task_is_hidden_by_default() {
+    let repo = TaskRepository::new();
+    let (_, Json(expired)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(create("old", Some(Duration::seconds(-5)))),
+    )
+    .await
+    .unwrap();
+    let _ = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(create("keep", None)),
+    )
+    .await
+    .unwrap();
+
+    let Json(list) = get_tasks(
+        State(repo.clone()),
+        HeaderMap::new(),
+        Query(ListParams::default()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(list_len(&list), 1);
+    assert_eq!(list["items"][0]["title"], "keep");
+
+    let (code, _) = get_task(
+        Path(expired.id.to_string()),
+        State(repo.clone()),
+        Query(GetParams::default()),
+    )
+    .await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn expired_task_visible_with_flag() {
+    let repo = TaskRepository::new();
+    let (_, Json(expired)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(create("old", Some(Duration::seconds(-5)))),
+    )
+    .await
+    .unwrap();
+
+    let params = ListParams {
+        include_expired: Some(true),
+        ..Default::default()
+    };
+    let Json(list) = get_tasks(State(repo.clone()), HeaderMap::new(), Query(params))
+        .await
+        .unwrap();
+    assert_eq!(list_len(&list), 1);
+
+    let (code, Json(body)) = get_task(
+        Path(expired.id.to_string()),
+        State(repo.clone()),
+        Query(GetParams {
+            include_expired: Some(true),
+        }),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert!(body["task"]["expires_at"].is_string());
+}
+
+#[tokio::test]
+async fn future_expiry_is_not_hidden() {
+    let repo = TaskRepository::new();
+    let (_, Json(t)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(create("soon", Some(Duration::hours(1)))),
+    )
+    .await
+    .unwrap();
+    let (code, _) = get_task(
+        Path(t.id.to_string()),
+        State(repo.clone()),
+        Query(GetParams::default()),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn gc_removes_only_expired_tasks() {
+    let repo = TaskRepository::new();
+    let (_, Json(expired)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(create("old", Some(Duration::seconds(-5)))),
+    )
+    .await
+    .unwrap();
+    let _ = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(create("later", Some(Duration::hours(1)))),
+    )
+    .await
+    .unwrap();
+    let _ = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(create("keep", None)),
+    )
+    .await
+    .unwrap();
+
+    let Json(resp) = gc_expired(State(repo.clone())).await;
+    assert_eq!(resp["removed"], 1);
+    assert_eq!(repo.count(), 2);
+    assert!(repo.get(&expired.id).is_none());
+
+    // a second pass has nothing left to remove
+    let Json(resp) = gc_expired(State(repo.clone())).await;
+    assert_eq!(resp["removed"], 0);
+}
+
+#[tokio::test]
+async fn head_hides_expired_tasks_too() {
+    let repo = TaskRepository::new();
+    let (_, Json(expired)) = create_task(
+        State(repo.clone()),
+        HeaderMap::new(),
+        Json(create("old", Some(Duration::seconds(-5)))),
+    )
+    .await
+    .unwrap();
+
+    let resp = head_task(
+        Path(expired.id.to_string()),
+        State(repo.clone()),
+        Query(GetParams::default()),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = head_task(
+        Path(expired.id.to_string()),
+        State(repo.clone()),
+        Query(GetParams {
+            include_expired: Some(true),
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().contains_key("etag"));
+}
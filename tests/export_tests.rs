@@ -0,0 +1,105 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::Response;
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{ExportParams, export_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn seeded(repo: TaskRepository, n: usize) -> TaskRepository {
+    for i in 0..n {
+        repo.insert(Task::new_full(&format!("t{}", i), "d"))
+            .unwrap();
+    }
+    repo
+}
+
+async fn body_string(resp: Response) -> String {
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn export_limit_truncates_and_sets_header() {
+    let repo = seeded(TaskRepository::new(), 5);
+    let params = ExportParams {
+        limit: Some(2),
+        ..Default::default()
+    };
+    let resp = export_tasks(State(repo), Query(params)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["x-truncated"], "true");
+    let v: serde_json::Value = serde_json::from_str(&body_string(resp).await).unwrap();
+    assert_eq!(v.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn export_without_truncation_has_no_header() {
+    let repo = seeded(TaskRepository::new(), 2);
+    let params = ExportParams {
+        limit: Some(2),
+        ..Default::default()
+    };
+    let resp = export_tasks(State(repo), Query(params)).await;
+    assert!(resp.headers().get("x-truncated").is_none());
+    let v: serde_json::Value = serde_json::from_str(&body_string(resp).await).unwrap();
+    assert_eq!(v.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn export_max_config_caps_output() {
+    let config = AppConfig {
+        export_max: Some(3),
+        ..Default::default()
+    };
+    let repo = seeded(TaskRepository::new().with_config(config), 5);
+    let params = ExportParams {
+        limit: Some(10),
+        ..Default::default()
+    };
+    let resp = export_tasks(State(repo), Query(params)).await;
+    assert_eq!(resp.headers()["x-truncated"], "true");
+    let v: serde_json::Value = serde_json::from_str(&body_string(resp).await).unwrap();
+    assert_eq!(v.as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn export_csv_has_header_and_rows() {
+    let repo = seeded(TaskRepository::new(), 2);
+    let params = ExportParams {
+        format: Some("csv".into()),
+        ..Default::default()
+    };
+    let resp = export_tasks(State(repo), Query(params)).await;
+    assert_eq!(resp.headers()["content-type"], "text/csv");
+    let body = body_string(resp).await;
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("id,title,description"));
+}
+
+#[tokio::test]
+async fn large_streamed_export_parses_back_to_full_set() {
+    let repo = seeded(TaskRepository::new(), 3000);
+    let resp = export_tasks(State(repo.clone()), Query(ExportParams::default())).await;
+    assert_eq!(resp.headers()["content-type"], "application/json");
+    let tasks: Vec<Task> = serde_json::from_str(&body_string(resp).await).unwrap();
+    assert_eq!(tasks.len(), 3000);
+    let mut ids: Vec<_> = tasks.iter().map(|t| t.id).collect();
+    let mut expected: Vec<_> = repo.list().iter().map(|t| t.id).collect();
+    ids.sort();
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    let params = ExportParams {
+        format: Some("csv".into()),
+        ..Default::default()
+    };
+    let resp = export_tasks(State(repo), Query(params)).await;
+    assert_eq!(resp.headers()["content-type"], "text/csv");
+    let csv = body_string(resp).await;
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+    assert_eq!(reader.records().count(), 3000);
+}
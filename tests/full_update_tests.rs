@@ -0,0 +1,76 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{FullUpdatePayload, update_task_full};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, Task};
+
+#[tokio::test]
+async fn full_update_applies_all_fields_together() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("old", "desc")).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    let version = repo.version();
+
+    let payload = FullUpdatePayload {
+        title: Some("new title".into()),
+        tags: Some(vec!["Backend".into(), "api".into()]),
+        priority: Some("high".into()),
+        ..Default::default()
+    };
+    let (code, Json(resp)) =
+        update_task_full(Path(t.id.to_string()), State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["task"]["title"], "new title");
+
+    let stored = repo.get(&t.id).unwrap();
+    assert_eq!(stored.title, "new title");
+    assert_eq!(stored.slug, "new-title");
+    assert_eq!(stored.description, "desc");
+    assert_eq!(stored.tags, vec!["backend", "api"]);
+    assert_eq!(stored.priority, Priority::High);
+    assert!(stored.updated_at > t.updated_at);
+    // one atomic write, not one per field
+    assert_eq!(repo.version(), version + 1);
+    assert_eq!(repo.list_by_tag("backend").len(), 1);
+}
+
+#[tokio::test]
+async fn full_update_rejects_invalid_priority_without_changes() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("old", "desc")).unwrap();
+    let payload = FullUpdatePayload {
+        title: Some("new".into()),
+        priority: Some("urgent".into()),
+        ..Default::default()
+    };
+    let (code, _) =
+        update_task_full(Path(t.id.to_string()), State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.get(&t.id).unwrap(), t);
+}
+
+#[tokio::test]
+async fn full_update_rejects_empty_tag() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("old", "desc")).unwrap();
+    let payload = FullUpdatePayload {
+        tags: Some(vec!["  ".into()]),
+        ..Default::default()
+    };
+    let (code, _) =
+        update_task_full(Path(t.id.to_string()), State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn full_update_missing_task_returns_404() {
+    let repo = TaskRepository::new();
+    let (code, _) = update_task_full(
+        Path(uuid::Uuid::new_v4().to_string()),
+        State(repo.clone()),
+        Json(FullUpdatePayload::default()),
+    )
+    .await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,60 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use rust_api_hub::handlers::task_handler::{GroupedParams, get_tasks_grouped_by_priority};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, Task};
+
+fn add(repo: &TaskRepository, title: &str, p: Priority, completed: bool) -> Task {
+    let mut t = Task::new_full(title, "d");
+    t.priority = p;
+    t.completed = completed;
+    let t = repo.insert(t).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    t
+}
+
+fn titles(v: &serde_json::Value) -> Vec<&str> {
+    v.as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["title"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn tasks_land_in_buckets_sorted_newest_first() {
+    let repo = TaskRepository::new();
+    add(&repo, "h1", Priority::High, false);
+    add(&repo, "c1", Priority::Critical, false);
+    add(&repo, "h2", Priority::High, true);
+    add(&repo, "l1", Priority::Low, false);
+    add(&repo, "h3", Priority::High, false);
+
+    let Json(resp) =
+        get_tasks_grouped_by_priority(State(repo.clone()), Query(GroupedParams::default())).await;
+    assert_eq!(titles(&resp["critical"]), vec!["c1"]);
+    assert_eq!(titles(&resp["high"]), vec!["h3", "h2", "h1"]);
+    assert_eq!(titles(&resp["low"]), vec!["l1"]);
+    // empty buckets are still present
+    assert_eq!(resp["medium"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn completed_filter_applies_to_every_bucket() {
+    let repo = TaskRepository::new();
+    add(&repo, "h1", Priority::High, false);
+    add(&repo, "h2", Priority::High, true);
+    add(&repo, "m1", Priority::Medium, true);
+
+    let Json(resp) = get_tasks_grouped_by_priority(
+        State(repo.clone()),
+        Query(GroupedParams {
+            completed: Some(true),
+        }),
+    )
+    .await;
+    assert_eq!(titles(&resp["high"]), vec!["h2"]);
+    assert_eq!(titles(&resp["medium"]), vec!["m1"]);
+    assert!(resp["critical"].as_array().unwrap().is_empty());
+    assert!(resp["low"].as_array().unwrap().is_empty());
+}
@@ -1,5 +1,5 @@
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use rust_api_hub::handlers::task_handler::{count_tasks, create_task};
 use rust_api_hub::models::repository::TaskRepository;
@@ -12,7 +12,12 @@ fn app_state() -> TaskRepository {
 #[tokio::test]
 async fn count_empty_repo_is_zero() {
     let repo = app_state();
-    let body = count_tasks(State(repo)).await;
+    let body = count_tasks(
+        State(repo),
+        axum::http::HeaderMap::new(),
+        Query(Default::default()),
+    )
+    .await;
     // body is Json<Value> -> {"count": 0}
     let v = body.0;
     assert_eq!(v["count"].as_u64().unwrap(), 0);
@@ -24,10 +29,22 @@ async fn count_after_one_insert_is_one() {
     let payload = TaskCreate {
         title: "t1".into(),
         description: "d1".into(),
+        ..Default::default()
     };
-    let (code, _created) = create_task(State(repo.clone()), Json(payload)).await;
+    let (code, _created) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap();
     assert_eq!(code, StatusCode::CREATED);
-    let body = count_tasks(State(repo)).await;
+    let body = count_tasks(
+        State(repo),
+        axum::http::HeaderMap::new(),
+        Query(Default::default()),
+    )
+    .await;
     let v = body.0;
     assert_eq!(v["count"].as_u64().unwrap(), 1);
 }
@@ -39,11 +56,104 @@ async fn count_after_multiple_inserts_is_n() {
         let payload = TaskCreate {
             title: format!("t{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (code, _created) = create_task(State(repo.clone()), Json(payload)).await;
+        let (code, _created) = create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
         assert_eq!(code, StatusCode::CREATED);
     }
-    let body = count_tasks(State(repo)).await;
+    let body = count_tasks(
+        State(repo),
+        axum::http::HeaderMap::new(),
+        Query(Default::default()),
+    )
+    .await;
     let v = body.0;
     assert_eq!(v["count"].as_u64().unwrap(), 5);
 }
+
+#[tokio::test]
+async fn count_matches_filtered_list_length() {
+    use rust_api_hub::handlers::task_handler::{ListParams, get_tasks};
+    use rust_api_hub::models::task::Task;
+
+    let repo = app_state();
+    for i in 0..12 {
+        let mut t = Task::new_full(&format!("t{}", i), "d");
+        t.completed = i % 2 == 0;
+        if i % 3 == 0 {
+            t.tags = vec!["x".into()];
+        }
+        repo.insert(t).unwrap();
+    }
+    let params = || ListParams {
+        completed: Some(true),
+        tag: Some("x".into()),
+        per_page: Some(100),
+        ..Default::default()
+    };
+    let Json(count) = count_tasks(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Query(params()),
+    )
+    .await;
+    let Json(list) = get_tasks(State(repo), axum::http::HeaderMap::new(), Query(params()))
+        .await
+        .unwrap();
+    assert_eq!(count["count"], 2);
+    assert_eq!(
+        count["count"].as_u64().unwrap() as usize,
+        list["items"].as_array().unwrap().len()
+    );
+}
+
+#[tokio::test]
+async fn count_honors_priority_and_created_range() {
+    use chrono::{Duration, Utc};
+    use rust_api_hub::handlers::task_handler::{ListParams, get_tasks};
+    use rust_api_hub::models::task::{Priority, Task};
+
+    let repo = app_state();
+    let start = Utc::now() - Duration::days(10);
+    for i in 0..10 {
+        let mut t = Task::new_full(&format!("t{}", i), "d");
+        t.created_at = start + Duration::days(i);
+        t.priority = if i % 2 == 0 {
+            Priority::High
+        } else {
+            Priority::Low
+        };
+        repo.insert(t).unwrap();
+    }
+    // days 2..6, high only: 2, 4
+    let params = || ListParams {
+        priority: Some(Priority::High),
+        created_after: Some(start + Duration::days(2)),
+        created_before: Some(start + Duration::days(6)),
+        ..Default::default()
+    };
+    let Json(count) = count_tasks(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Query(params()),
+    )
+    .await;
+    let Json(list) = get_tasks(State(repo), axum::http::HeaderMap::new(), Query(params()))
+        .await
+        .unwrap();
+    assert_eq!(count["count"], 2);
+    assert_eq!(list["total"], 2);
+    let titles: Vec<_> = list["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, ["t2", "t4"]);
+}
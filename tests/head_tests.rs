@@ -0,0 +1,59 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt; // oneshot
+
+async fn head(repo: &TaskRepository, id: &str) -> (StatusCode, Option<String>, usize) {
+    let req = Request::head(format!("/tasks/{}", id))
+        .body(Body::empty())
+        .unwrap();
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    let status = resp.status();
+    let etag = resp
+        .headers()
+        .get(header::ETAG)
+        .map(|v| v.to_str().unwrap().to_string());
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, etag, bytes.len())
+}
+
+#[tokio::test]
+async fn head_existing_task_returns_200_with_etag_and_no_body() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    let (status, etag, len) = head(&repo, &t.id.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag, Some(t.etag()));
+    assert_eq!(len, 0);
+}
+
+#[tokio::test]
+async fn head_missing_task_returns_404() {
+    let repo = TaskRepository::new();
+    let (status, etag, len) = head(&repo, &uuid::Uuid::new_v4().to_string()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(etag.is_none());
+    assert_eq!(len, 0);
+}
+
+#[tokio::test]
+async fn head_invalid_uuid_returns_400() {
+    let repo = TaskRepository::new();
+    let (status, _, _) = head(&repo, "not-a-uuid").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn etag_changes_when_task_is_modified() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    assert!(repo.contains(&t.id));
+    let before = repo.etag(&t.id).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    repo.modify(&t.id, |t| t.title = "z".into());
+    assert_ne!(repo.etag(&t.id).unwrap(), before);
+}
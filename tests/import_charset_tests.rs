@@ -0,0 +1,83 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::handlers::task_handler::{ImportParams, import_tasks, import_tasks_csv};
+use rust_api_hub::models::repository::TaskRepository;
+
+fn csv_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+    headers
+}
+
+fn charset(c: Option<&str>) -> Query<ImportParams> {
+    Query(ImportParams {
+        charset: c.map(str::to_string),
+        ..Default::default()
+    })
+}
+
+/// "title,description\nCafé,Crème brûlée\n" encoded as ISO-8859-1.
+fn latin1_csv() -> Bytes {
+    let body = b"title,description\nCaf\xe9,Cr\xe8me br\xfbl\xe9e\n".to_vec();
+    Bytes::from(body)
+}
+
+#[tokio::test]
+async fn latin1_csv_imports_in_latin1_mode() {
+    let repo = TaskRepository::new();
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        csv_headers(),
+        charset(Some("latin1")),
+        latin1_csv(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"], 1);
+    assert_eq!(resp["tasks"][0]["title"], "Café");
+    assert_eq!(resp["tasks"][0]["description"], "Crème brûlée");
+}
+
+#[tokio::test]
+async fn latin1_csv_is_rejected_in_default_utf8_mode() {
+    let repo = TaskRepository::new();
+    for params in [charset(None), charset(Some("utf-8"))] {
+        let (code, Json(resp)) =
+            import_tasks(State(repo.clone()), csv_headers(), params, latin1_csv()).await;
+        assert_eq!(code, StatusCode::BAD_REQUEST);
+        assert_eq!(resp["error"], "invalid utf8 in body");
+    }
+    assert_eq!(repo.count(), 0);
+}
+
+#[tokio::test]
+async fn csv_handler_honors_charset() {
+    let repo = TaskRepository::new();
+    let (code, _) = import_tasks_csv(State(repo.clone()), charset(None), latin1_csv()).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    let (code, Json(resp)) = import_tasks_csv(
+        State(repo.clone()),
+        charset(Some("ISO-8859-1")),
+        latin1_csv(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["tasks"][0]["title"], "Café");
+}
+
+#[tokio::test]
+async fn unknown_charset_is_rejected() {
+    let repo = TaskRepository::new();
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        csv_headers(),
+        charset(Some("ebcdic")),
+        Bytes::from("title\nx\n"),
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert!(resp["error"].as_str().unwrap().contains("charset"));
+    assert_eq!(repo.count(), 0);
+}
@@ -0,0 +1,97 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::import_tasks;
+use rust_api_hub::models::repository::TaskRepository;
+
+fn headers(ct: &'static str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(ct));
+    headers
+}
+
+#[tokio::test]
+async fn xml_body_returns_415_with_accepted_types() {
+    let repo = TaskRepository::new();
+    let body = Bytes::from("<tasks><task><title>a</title></task></tasks>");
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        headers("application/xml"),
+        Query(Default::default()),
+        body,
+    )
+    .await;
+    assert_eq!(code, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    let accepted: Vec<&str> = resp["accepted"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap())
+        .collect();
+    assert!(accepted.contains(&"application/json"));
+    assert!(accepted.contains(&"text/csv"));
+    assert_eq!(repo.count(), 0);
+}
+
+#[tokio::test]
+async fn content_type_parameters_are_ignored() {
+    let repo = TaskRepository::new();
+    let body = Bytes::from(r#"[{"title":"a","description":"b"}]"#);
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        headers("application/json; charset=utf-8"),
+        Query(Default::default()),
+        body,
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"].as_u64().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn ndjson_imports_each_line() {
+    let repo = TaskRepository::new();
+    let body = Bytes::from(
+        "{\"title\":\"a\",\"description\":\"1\"}\n\n{\"title\":\"\",\"description\":\"2\"}\n",
+    );
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        headers("application/x-ndjson"),
+        Query(Default::default()),
+        body,
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"].as_u64().unwrap(), 1);
+    assert_eq!(resp["failed"].as_u64().unwrap(), 1);
+    assert_eq!(resp["errors"][0]["line"].as_u64().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn missing_content_type_defaults_to_json_unless_strict() {
+    let body = Bytes::from(r#"[{"title":"a","description":"b"}]"#);
+    let repo = TaskRepository::new();
+    let (code, _) = import_tasks(
+        State(repo),
+        HeaderMap::new(),
+        Query(Default::default()),
+        body.clone(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+
+    let strict = TaskRepository::new().with_config(AppConfig {
+        import_strict_content_type: true,
+        ..Default::default()
+    });
+    let (code, _) = import_tasks(
+        State(strict),
+        HeaderMap::new(),
+        Query(Default::default()),
+        body,
+    )
+    .await;
+    assert_eq!(code, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
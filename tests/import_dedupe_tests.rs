@@ -0,0 +1,120 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::handlers::task_handler::import_tasks;
+use rust_api_hub::models::repository::{ImportClaim, TaskRepository};
+
+fn headers(import_id: &'static str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    headers.insert("x-import-id", HeaderValue::from_static(import_id));
+    headers
+}
+
+fn body() -> Bytes {
+    Bytes::from(r#"[{"title":"a","description":"d"},{"title":"b","description":"d"}]"#)
+}
+
+#[tokio::test]
+async fn same_import_id_inserts_once() {
+    let repo = TaskRepository::new();
+    let (code, Json(first)) = import_tasks(
+        State(repo.clone()),
+        headers("batch-1"),
+        Query(Default::default()),
+        body(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(first["imported"], 2);
+
+    let (code, Json(second)) = import_tasks(
+        State(repo.clone()),
+        headers("batch-1"),
+        Query(Default::default()),
+        body(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(second, first);
+    assert_eq!(repo.count(), 2);
+}
+
+#[tokio::test]
+async fn different_import_ids_insert_twice() {
+    let repo = TaskRepository::new();
+    let (code, _) = import_tasks(
+        State(repo.clone()),
+        headers("batch-1"),
+        Query(Default::default()),
+        body(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    let (code, _) = import_tasks(
+        State(repo.clone()),
+        headers("batch-2"),
+        Query(Default::default()),
+        body(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(repo.count(), 4);
+}
+
+#[tokio::test]
+async fn failed_import_is_not_remembered() {
+    let repo = TaskRepository::new();
+    let (code, _) = import_tasks(
+        State(repo.clone()),
+        headers("retry"),
+        Query(Default::default()),
+        Bytes::new(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    let (code, _) = import_tasks(
+        State(repo.clone()),
+        headers("retry"),
+        Query(Default::default()),
+        body(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(repo.count(), 2);
+}
+
+#[tokio::test]
+async fn running_import_id_is_reserved() {
+    let repo = TaskRepository::new();
+    // stands in for a concurrent request that is still importing
+    assert_eq!(repo.claim_import("batch-9"), ImportClaim::Claimed);
+    assert_eq!(repo.claim_import("batch-9"), ImportClaim::Pending);
+
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        headers("batch-9"),
+        Query(Default::default()),
+        body(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CONFLICT);
+    assert_eq!(resp["error"], "import already in progress");
+    assert_eq!(repo.count(), 0);
+
+    // once the other request gives up, a retry runs
+    repo.release_import("batch-9");
+    let (code, _) = import_tasks(
+        State(repo.clone()),
+        headers("batch-9"),
+        Query(Default::default()),
+        body(),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert!(matches!(repo.claim_import("batch-9"), ImportClaim::Done(_)));
+}
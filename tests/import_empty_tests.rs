@@ -0,0 +1,44 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::handlers::task_handler::import_tasks;
+use rust_api_hub::models::repository::TaskRepository;
+
+fn headers(ct: &'static str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(ct));
+    headers
+}
+
+#[tokio::test]
+async fn empty_body_returns_clear_error_for_any_content_type() {
+    for ct in ["application/json", "text/csv", "application/xml"] {
+        let repo = TaskRepository::new();
+        let (code, Json(resp)) = import_tasks(
+            State(repo.clone()),
+            headers(ct),
+            Query(Default::default()),
+            Bytes::new(),
+        )
+        .await;
+        assert_eq!(code, StatusCode::BAD_REQUEST);
+        assert_eq!(resp["error"].as_str().unwrap(), "empty import body");
+    }
+}
+
+#[tokio::test]
+async fn header_only_csv_imports_zero_rows() {
+    let repo = TaskRepository::new();
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        headers("text/csv"),
+        Query(Default::default()),
+        Bytes::from("title,description\n"),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"].as_u64().unwrap(), 0);
+    assert_eq!(resp["failed"].as_u64().unwrap(), 0);
+    assert_eq!(repo.count(), 0);
+}
@@ -1,6 +1,6 @@
 use axum::Json;
 use axum::body::Bytes;
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
 use rust_api_hub::models::repository::TaskRepository;
 
@@ -31,6 +31,7 @@ async fn file_import_valid_csv_inserts_all() {
     let (code, Json(resp)) = rust_api_hub::handlers::task_handler::import_tasks_file(
         State(repo.clone()),
         headers,
+        Query(Default::default()),
         bytes,
     )
     .await;
@@ -61,6 +62,7 @@ async fn file_import_partial_failure_reports_rows() {
     let (code, Json(resp)) = rust_api_hub::handlers::task_handler::import_tasks_file(
         State(repo.clone()),
         headers,
+        Query(Default::default()),
         bytes,
     )
     .await;
@@ -99,6 +101,7 @@ async fn file_import_too_large_returns_413() {
     let (code, _resp) = rust_api_hub::handlers::task_handler::import_tasks_file(
         State(repo.clone()),
         headers,
+        Query(Default::default()),
         bytes,
     )
     .await;
@@ -0,0 +1,84 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::handlers::task_handler::import_tasks;
+use rust_api_hub::models::repository::TaskRepository;
+
+async fn import(headers: HeaderMap, body: &str) -> (StatusCode, serde_json::Value, TaskRepository) {
+    let repo = TaskRepository::new();
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        headers,
+        Query(Default::default()),
+        Bytes::from(body.to_string()),
+    )
+    .await;
+    (code, resp, repo)
+}
+
+fn octet_stream() -> HeaderMap {
+    let mut h = HeaderMap::new();
+    h.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/octet-stream"),
+    );
+    h
+}
+
+#[tokio::test]
+async fn raw_json_without_content_type_imports() {
+    let (code, resp, repo) = import(
+        HeaderMap::new(),
+        "\n  [{\"title\":\"a\",\"description\":\"1\"},{\"title\":\"b\",\"description\":\"2\"}]",
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"], 2);
+    assert_eq!(repo.count(), 2);
+}
+
+#[tokio::test]
+async fn raw_csv_without_content_type_imports() {
+    let (code, resp, repo) =
+        import(HeaderMap::new(), "title,description\nFirst,x\nSecond,y\n").await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"], 2);
+    let mut titles: Vec<_> = repo.list().into_iter().map(|t| t.title).collect();
+    titles.sort();
+    assert_eq!(titles, ["First", "Second"]);
+}
+
+#[tokio::test]
+async fn octet_stream_is_sniffed_too() {
+    let (code, resp, _) = import(
+        octet_stream(),
+        "{\"title\":\"a\",\"description\":\"1\"}\n{\"title\":\"b\",\"description\":\"2\"}\n",
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"], 2);
+
+    let (code, resp, _) = import(octet_stream(), "title\nonly titles\n").await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"], 1);
+}
+
+#[tokio::test]
+async fn unrecognized_body_gets_guidance() {
+    let (code, resp, repo) = import(HeaderMap::new(), "name;notes\nx;y\n").await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert!(resp["error"].as_str().unwrap().contains("cannot detect"));
+    assert!(resp["accepted"].is_array());
+    assert_eq!(repo.count(), 0);
+}
+
+#[tokio::test]
+async fn explicit_content_type_is_not_overridden() {
+    let mut h = HeaderMap::new();
+    h.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+    // a JSON-looking body declared as CSV is parsed as CSV and has no title column
+    let (_, resp, repo) = import(h, "[{\"title\":\"a\",\"description\":\"1\"}]").await;
+    assert_eq!(resp["imported"], 0);
+    assert_eq!(repo.count(), 0);
+}
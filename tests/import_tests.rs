@@ -1,6 +1,6 @@
 use axum::Json;
 use axum::body::Bytes;
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use rust_api_hub::models::repository::TaskRepository;
 use rust_api_hub::models::task::TaskCreate;
@@ -16,10 +16,12 @@ async fn import_json_inserts_all() {
         TaskCreate {
             title: "a".into(),
             description: "d1".into(),
+            ..Default::default()
         },
         TaskCreate {
             title: "b".into(),
             description: "d2".into(),
+            ..Default::default()
         },
     ];
 
@@ -38,8 +40,12 @@ async fn import_csv_parses_and_inserts() {
     let csv = "title,description\nrow1,desc1\nrow2,desc2\n";
     let body = Bytes::from(csv);
 
-    let (code, Json(resp)) =
-        rust_api_hub::handlers::task_handler::import_tasks_csv(State(repo.clone()), body).await;
+    let (code, Json(resp)) = rust_api_hub::handlers::task_handler::import_tasks_csv(
+        State(repo.clone()),
+        Query(Default::default()),
+        body,
+    )
+    .await;
     assert_eq!(code, StatusCode::CREATED);
     assert_eq!(resp["imported"].as_u64().unwrap(), 2);
     assert_eq!(repo.count(), 2);
@@ -50,7 +56,11 @@ async fn import_csv_bad_returns_400() {
     let repo = app_state();
     let bad = "not,a,csv\nthis is not valid rows";
     let body = Bytes::from(bad);
-    let (code, _body) =
-        rust_api_hub::handlers::task_handler::import_tasks_csv(State(repo.clone()), body).await;
+    let (code, _body) = rust_api_hub::handlers::task_handler::import_tasks_csv(
+        State(repo.clone()),
+        Query(Default::default()),
+        body,
+    )
+    .await;
     assert_eq!(code, StatusCode::BAD_REQUEST);
 }
@@ -0,0 +1,54 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::handlers::task_handler::{ImportParams, import_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+async fn import_csv(repo: &TaskRepository, csv: &str, dedupe: bool) -> serde_json::Value {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+    let (code, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        headers,
+        Query(ImportParams {
+            dedupe,
+            ..Default::default()
+        }),
+        Bytes::from(csv.to_string()),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    resp
+}
+
+const CSV: &str = "title,description\n  Buy MILK ,dup of existing\nWalk dog,new\nwalk dog,repeat\n";
+
+#[tokio::test]
+async fn dedupe_skips_existing_and_repeated_titles() {
+    let repo = TaskRepository::new();
+    repo.insert(Task::new_full("buy milk", "d")).unwrap();
+
+    let resp = import_csv(&repo, CSV, true).await;
+    assert_eq!(resp["imported"], 1);
+    assert_eq!(resp["skipped"], 2);
+    assert_eq!(resp["skipped_rows"][0]["reason"], "title already exists");
+    assert_eq!(
+        resp["skipped_rows"][1]["reason"],
+        "duplicate title in import"
+    );
+    assert_eq!(resp["skipped_rows"][1]["title"], "walk dog");
+    assert_eq!(repo.count(), 2);
+}
+
+#[tokio::test]
+async fn without_dedupe_everything_is_inserted() {
+    let repo = TaskRepository::new();
+    repo.insert(Task::new_full("buy milk", "d")).unwrap();
+
+    let resp = import_csv(&repo, CSV, false).await;
+    assert_eq!(resp["imported"], 3);
+    assert!(resp.get("skipped").is_none());
+    assert_eq!(repo.count(), 4);
+}
@@ -0,0 +1,83 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::models::repository::TaskRepository;
+
+fn app_state() -> TaskRepository {
+    TaskRepository::new()
+}
+
+fn csv_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+    headers
+}
+
+fn multipart(boundary: &str, csv: &str) -> (HeaderMap, Bytes) {
+    let mut body = String::new();
+    body.push_str(&format!("--{}\r\n", boundary));
+    body.push_str("Content-Disposition: form-data; name=\"file\"; filename=\"tasks.csv\"\r\n");
+    body.push_str("Content-Type: text/csv\r\n\r\n");
+    body.push_str(csv);
+    body.push_str(&format!("\r\n--{}--\r\n", boundary));
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_str(&format!("multipart/form-data; boundary={}", boundary)).unwrap(),
+    );
+    (headers, Bytes::from(body))
+}
+
+#[tokio::test]
+async fn import_title_only_csv_defaults_description() {
+    let repo = app_state();
+    let csv = "title\nFirst\nSecond\n";
+    let (code, Json(resp)) = rust_api_hub::handlers::task_handler::import_tasks(
+        State(repo.clone()),
+        csv_headers(),
+        Query(Default::default()),
+        Bytes::from(csv),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"].as_u64().unwrap(), 2);
+    assert_eq!(resp["failed"].as_u64().unwrap(), 0);
+    let items = repo.list();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|t| t.description.is_empty()));
+}
+
+#[tokio::test]
+async fn import_two_column_csv_still_works() {
+    let repo = app_state();
+    let csv = "title,description\nFirst,desc1\n";
+    let (code, Json(resp)) = rust_api_hub::handlers::task_handler::import_tasks(
+        State(repo.clone()),
+        csv_headers(),
+        Query(Default::default()),
+        Bytes::from(csv),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"].as_u64().unwrap(), 1);
+    assert_eq!(repo.list()[0].description, "desc1");
+}
+
+#[tokio::test]
+async fn file_import_title_only_csv_defaults_description() {
+    let repo = app_state();
+    let (headers, body) = multipart("BOUND", "title\nOnly\n");
+    let (code, Json(resp)) = rust_api_hub::handlers::task_handler::import_tasks_file(
+        State(repo.clone()),
+        headers,
+        Query(Default::default()),
+        body,
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(resp["imported"].as_u64().unwrap(), 1);
+    let items = repo.list();
+    assert_eq!(items[0].title, "Only");
+    assert_eq!(items[0].description, "");
+}
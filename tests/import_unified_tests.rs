@@ -1,6 +1,6 @@
 use axum::Json;
 use axum::body::Bytes;
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
 use rust_api_hub::models::repository::TaskRepository;
 use rust_api_hub::models::task::TaskCreate;
@@ -16,10 +16,12 @@ async fn import_json_valid_inserts_all() {
         TaskCreate {
             title: "A".into(),
             description: "d1".into(),
+            ..Default::default()
         },
         TaskCreate {
             title: "B".into(),
             description: "d2".into(),
+            ..Default::default()
         },
     ];
     let body = Bytes::from(serde_json::to_vec(&payload).unwrap());
@@ -29,9 +31,13 @@ async fn import_json_valid_inserts_all() {
         HeaderValue::from_static("application/json"),
     );
 
-    let (code, Json(resp)) =
-        rust_api_hub::handlers::task_handler::import_tasks(State(repo.clone()), headers, body)
-            .await;
+    let (code, Json(resp)) = rust_api_hub::handlers::task_handler::import_tasks(
+        State(repo.clone()),
+        headers,
+        Query(Default::default()),
+        body,
+    )
+    .await;
     assert_eq!(code, StatusCode::CREATED);
     assert_eq!(resp["imported"].as_u64().unwrap(), 2);
     assert_eq!(repo.count(), 2);
@@ -46,10 +52,12 @@ async fn import_json_partial_failure_reports_errors() {
         TaskCreate {
             title: "Good".into(),
             description: "d1".into(),
+            ..Default::default()
         },
         TaskCreate {
             title: "".into(),
             description: "d-bad".into(),
+            ..Default::default()
         },
     ];
     let body = Bytes::from(serde_json::to_vec(&payload).unwrap());
@@ -59,9 +67,13 @@ async fn import_json_partial_failure_reports_errors() {
         HeaderValue::from_static("application/json"),
     );
 
-    let (code, Json(resp)) =
-        rust_api_hub::handlers::task_handler::import_tasks(State(repo.clone()), headers, body)
-            .await;
+    let (code, Json(resp)) = rust_api_hub::handlers::task_handler::import_tasks(
+        State(repo.clone()),
+        headers,
+        Query(Default::default()),
+        body,
+    )
+    .await;
     assert_eq!(code, StatusCode::CREATED);
     assert_eq!(resp["imported"].as_u64().unwrap(), 1);
     assert_eq!(resp["failed"].as_u64().unwrap(), 1);
@@ -79,9 +91,13 @@ async fn import_csv_partial_rows_are_reported() {
     let mut headers = HeaderMap::new();
     headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
 
-    let (code, Json(resp)) =
-        rust_api_hub::handlers::task_handler::import_tasks(State(repo.clone()), headers, body)
-            .await;
+    let (code, Json(resp)) = rust_api_hub::handlers::task_handler::import_tasks(
+        State(repo.clone()),
+        headers,
+        Query(Default::default()),
+        body,
+    )
+    .await;
     assert_eq!(code, StatusCode::CREATED);
     assert_eq!(resp["imported"].as_u64().unwrap(), 1);
     assert_eq!(resp["failed"].as_u64().unwrap(), 1);
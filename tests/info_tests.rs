@@ -0,0 +1,68 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt;
+
+async fn info(repo: TaskRepository) -> serde_json::Value {
+    let resp = build_router(repo)
+        .oneshot(Request::get("/info").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn info_keeps_existing_fields_and_adds_runtime_details() {
+    let v = info(TaskRepository::new()).await;
+    assert_eq!(v["name"], "rust_api_hub");
+    assert_eq!(v["version"], env!("CARGO_PKG_VERSION"));
+    assert!(v["desc"].is_string());
+    assert!(v["build_version"].is_string());
+    assert!(v["uptime_seconds"].is_u64());
+    assert!(v["base_path"].is_null());
+    let features = v["features"].as_object().unwrap();
+    assert!(features.values().all(|f| f == false));
+}
+
+#[tokio::test]
+async fn features_reflect_the_config() {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        log_bodies: true,
+        allow_delete_all: true,
+        create_debounce_ms: Some(500),
+        ..Default::default()
+    });
+    let v = info(repo).await;
+    let f = &v["features"];
+    assert_eq!(f["log_bodies"], true);
+    assert_eq!(f["delete_all"], true);
+    assert_eq!(f["create_debounce"], true);
+    assert_eq!(f["read_only"], false);
+    assert_eq!(f["audit_log"], false);
+    assert_eq!(f["persistence"], false);
+
+    let repo = TaskRepository::new().with_config(AppConfig {
+        data_file: Some("tasks.json".into()),
+        ..Default::default()
+    });
+    assert_eq!(info(repo).await["features"]["persistence"], true);
+}
+
+#[tokio::test]
+async fn base_path_is_reported() {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        base_path: "api/v1".into(),
+        ..Default::default()
+    });
+    let resp = build_router(repo)
+        .oneshot(Request::get("/api/v1/info").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(v["base_path"], "/api/v1");
+}
@@ -0,0 +1,64 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, header};
+use rust_api_hub::handlers::task_handler::{create_task, import_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskCreate};
+
+fn hooked_repo() -> TaskRepository {
+    TaskRepository::new().with_insert_hook(|t: &mut Task| t.tags.push("new".into()))
+}
+
+#[tokio::test]
+async fn insert_hook_tags_created_tasks() {
+    let repo = hooked_repo();
+    let payload = TaskCreate {
+        title: "hooked".into(),
+        description: "d".into(),
+        ..Default::default()
+    };
+    let (_code, Json(task)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap();
+    assert_eq!(task.tags, vec!["new".to_string()]);
+    assert_eq!(repo.get(&task.id).unwrap().tags, vec!["new".to_string()]);
+}
+
+#[tokio::test]
+async fn insert_hook_runs_for_imports() {
+    let repo = hooked_repo();
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+    let body = Bytes::from("title,description\nA,a\nB,b\n");
+    let _ = import_tasks(
+        State(repo.clone()),
+        headers,
+        Query(Default::default()),
+        body,
+    )
+    .await;
+    let items = repo.list();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|t| t.tags == vec!["new".to_string()]));
+}
+
+#[test]
+fn insert_hook_skips_overwrites() {
+    let repo = hooked_repo();
+    let mut t = repo.insert(Task::new_full("a", "b")).unwrap();
+    t.tags = vec!["replaced".into()];
+    repo.insert(t.clone()).unwrap();
+    assert_eq!(repo.get(&t.id).unwrap().tags, vec!["replaced".to_string()]);
+}
+
+#[test]
+fn no_hook_by_default() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    assert!(t.tags.is_empty());
+}
@@ -0,0 +1,110 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::handlers::task_handler::patch_task;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::utils::json_patch::{self, PatchOp};
+use serde_json::json;
+
+fn patch_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json-patch+json"),
+    );
+    headers
+}
+
+async fn patch(
+    repo: &TaskRepository,
+    task: &Task,
+    ops: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    let (code, Json(v)) = patch_task(
+        Path(task.id.to_string()),
+        State(repo.clone()),
+        patch_headers(),
+        Bytes::from(ops.to_string()),
+    )
+    .await;
+    (code, v)
+}
+
+#[tokio::test]
+async fn replace_title_op_updates_task() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("old", "d")).unwrap();
+    let (code, v) = patch(
+        &repo,
+        &t,
+        json!([{"op": "replace", "path": "/title", "value": "new"}]),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(v["task"]["title"], "new");
+    assert_eq!(repo.get(&t.id).unwrap().title, "new");
+}
+
+#[tokio::test]
+async fn forbidden_id_path_is_rejected() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("old", "d")).unwrap();
+    let other = uuid::Uuid::new_v4();
+    let (code, v) = patch(
+        &repo,
+        &t,
+        json!([{"op": "replace", "path": "/id", "value": other}]),
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert!(v["error"].as_str().unwrap().contains("/id"));
+    assert!(repo.get(&t.id).is_some());
+}
+
+#[tokio::test]
+async fn failing_op_leaves_task_untouched() {
+    let repo = TaskRepository::new();
+    let mut t = Task::new_full("old", "d");
+    t.tags = vec!["a".into()];
+    let t = repo.insert(t).unwrap();
+    let (code, _) = patch(
+        &repo,
+        &t,
+        json!([
+            {"op": "add", "path": "/tags/-", "value": "b"},
+            {"op": "remove", "path": "/tags/5"}
+        ]),
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.get(&t.id).unwrap().tags, vec!["a".to_string()]);
+}
+
+#[tokio::test]
+async fn wrong_content_type_is_415() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("old", "d")).unwrap();
+    let (code, _) = patch_task(
+        Path(t.id.to_string()),
+        State(repo),
+        HeaderMap::new(),
+        Bytes::from("[]"),
+    )
+    .await;
+    assert_eq!(code, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[test]
+fn apply_handles_nested_arrays_and_escapes() {
+    let mut doc = json!({"a/b": [1, 2], "c": {"d": 1}});
+    let ops: Vec<PatchOp> = serde_json::from_value(json!([
+        {"op": "add", "path": "/a~1b/0", "value": 0},
+        {"op": "remove", "path": "/c/d"},
+        {"op": "add", "path": "/c/e", "value": true}
+    ]))
+    .unwrap();
+    json_patch::apply(&mut doc, &ops).unwrap();
+    assert_eq!(doc, json!({"a/b": [0, 1, 2], "c": {"e": true}}));
+}
@@ -0,0 +1,154 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use axum::response::Response;
+use chrono::{Duration, Utc};
+use rust_api_hub::config::{AppConfig, SlaPolicy};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, Task};
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt; // oneshot
+
+async fn get(repo: &TaskRepository, uri: &str, if_none_match: Option<&str>) -> Response {
+    get_at(repo, uri, if_none_match, None).await
+}
+
+async fn get_at(
+    repo: &TaskRepository,
+    uri: &str,
+    if_none_match: Option<&str>,
+    now: Option<&str>,
+) -> Response {
+    let mut req = Request::get(uri);
+    if let Some(tag) = if_none_match {
+        req = req.header(header::IF_NONE_MATCH, tag);
+    }
+    if let Some(now) = now {
+        req = req.header("x-now", now);
+    }
+    build_router(repo.clone())
+        .oneshot(req.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+fn etag(resp: &Response) -> String {
+    resp.headers()
+        .get(header::ETAG)
+        .expect("etag header")
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn unchanged_list_returns_304_on_repeat() {
+    let repo = TaskRepository::new();
+    repo.insert(Task::new_full("a", "b")).unwrap();
+
+    let first = get(&repo, "/tasks", None).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    let tag = etag(&first);
+
+    let second = get(&repo, "/tasks", Some(&tag)).await;
+    assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(etag(&second), tag);
+    let body = axum::body::to_bytes(second.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn create_bumps_version_and_returns_200() {
+    let repo = TaskRepository::new();
+    let tag = etag(&get(&repo, "/tasks", None).await);
+
+    let req = Request::post("/tasks")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"title":"new","description":""}"#))
+        .unwrap();
+    let created = build_router(repo.clone()).oneshot(req).await.unwrap();
+    assert_eq!(created.status(), StatusCode::CREATED);
+
+    let after = get(&repo, "/tasks", Some(&tag)).await;
+    assert_eq!(after.status(), StatusCode::OK);
+    assert_ne!(etag(&after), tag);
+}
+
+#[tokio::test]
+async fn query_params_are_part_of_the_etag() {
+    let repo = TaskRepository::new();
+    repo.insert(Task::new_full("a", "b")).unwrap();
+    let page1 = etag(&get(&repo, "/tasks?page=1", None).await);
+    let page2 = get(&repo, "/tasks?page=2", Some(&page1)).await;
+    assert_eq!(page2.status(), StatusCode::OK);
+    assert_ne!(etag(&page2), page1);
+}
+
+#[tokio::test]
+async fn expiring_task_changes_the_etag_without_a_write() {
+    let repo = TaskRepository::new();
+    let mut t = Task::new_full("brief", "b");
+    t.expires_at = Some(chrono::Utc::now() + chrono::Duration::milliseconds(20));
+    repo.insert(t).unwrap();
+    let tag = etag(&get(&repo, "/tasks", None).await);
+    std::thread::sleep(std::time::Duration::from_millis(40));
+    let after = get(&repo, "/tasks", Some(&tag)).await;
+    assert_eq!(after.status(), StatusCode::OK);
+}
+
+/// A repository holding one critical task created at `created_at`, with a one-hour SLA.
+fn repo_with_sla_task(created_at: chrono::DateTime<Utc>) -> TaskRepository {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        allow_now_override: true,
+        sla: SlaPolicy {
+            critical: Some(Duration::hours(1)),
+            high: None,
+            medium: None,
+            low: None,
+        },
+        ..Default::default()
+    });
+    let mut t = Task::new_full("urgent", "d");
+    t.priority = Priority::Critical;
+    t.created_at = created_at;
+    repo.insert(t).unwrap();
+    repo
+}
+
+async fn body_json(resp: Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn sla_breach_changes_the_etag_across_x_now() {
+    let created = Utc::now();
+    let repo = repo_with_sla_task(created);
+    let before = (created + Duration::minutes(30)).to_rfc3339();
+    let after = (created + Duration::minutes(90)).to_rfc3339();
+
+    let first = get_at(&repo, "/tasks?with_sla=true", None, Some(&before)).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    let tag = etag(&first);
+    assert_eq!(body_json(first).await["items"][0]["sla_breached"], false);
+
+    let later = get_at(&repo, "/tasks?with_sla=true", Some(&tag), Some(&after)).await;
+    assert_eq!(later.status(), StatusCode::OK);
+    assert_ne!(etag(&later), tag);
+    assert_eq!(body_json(later).await["items"][0]["sla_breached"], true);
+}
+
+#[tokio::test]
+async fn sla_breach_changes_the_etag_without_a_write() {
+    // crosses the one-hour SLA on the real clock between the two requests
+    let repo = repo_with_sla_task(Utc::now() - Duration::hours(1) + Duration::milliseconds(20));
+    let tag = etag(&get(&repo, "/tasks?with_sla=true", None).await);
+    std::thread::sleep(std::time::Duration::from_millis(40));
+    let after = get(&repo, "/tasks?with_sla=true", Some(&tag)).await;
+    assert_eq!(after.status(), StatusCode::OK);
+    assert_ne!(etag(&after), tag);
+    assert_eq!(body_json(after).await["items"][0]["sla_breached"], true);
+}
@@ -0,0 +1,83 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use rust_api_hub::handlers::task_handler::{ListParams, get_tasks};
+use rust_api_hub::models::repository::{SortField, TaskFilter, TaskRepository, TaskSort};
+use rust_api_hub::models::task::{Priority, Task};
+
+fn seeded() -> TaskRepository {
+    let repo = TaskRepository::new();
+    let priorities = [
+        Priority::Low,
+        Priority::Critical,
+        Priority::Medium,
+        Priority::High,
+    ];
+    for i in 0..23 {
+        let mut t = Task::new_full(&format!("t{}", i), "d");
+        t.priority = priorities[i % 4].clone();
+        t.completed = i % 3 == 0;
+        repo.insert(t).unwrap();
+    }
+    repo
+}
+
+#[tokio::test]
+async fn paginated_result_matches_get_tasks() {
+    let repo = seeded();
+    let cases = [
+        (None, None, Some(1), Some(10)),
+        (Some(true), Some("created_at:desc"), Some(1), Some(5)),
+        (Some(false), Some("priority:desc"), Some(2), Some(4)),
+        (None, Some("priority:asc"), Some(3), Some(10)),
+        (None, None, Some(9), Some(10)),
+    ];
+    for (completed, sort, page, per_page) in cases {
+        let params = ListParams {
+            completed,
+            sort: sort.map(String::from),
+            page,
+            per_page,
+            ..Default::default()
+        };
+        let Json(expected) = get_tasks(State(repo.clone()), HeaderMap::new(), Query(params))
+            .await
+            .unwrap();
+
+        let filter = TaskFilter {
+            completed,
+            hide_expired_at: Some(chrono::Utc::now()),
+            ..Default::default()
+        };
+        let got = repo.list_paginated(
+            &filter,
+            TaskSort::parse(sort.unwrap_or("created_at")),
+            page.unwrap(),
+            per_page.unwrap(),
+        );
+        assert_eq!(serde_json::to_value(&got).unwrap(), expected);
+    }
+}
+
+#[test]
+fn out_of_range_page_is_empty_but_keeps_total() {
+    let repo = seeded();
+    let page = repo.list_paginated(&TaskFilter::default(), TaskSort::default(), 100, 10);
+    assert!(page.items.is_empty());
+    assert_eq!(page.total, 23);
+}
+
+#[test]
+fn sort_parse_matches_query_syntax() {
+    assert_eq!(
+        TaskSort::parse("priority:desc"),
+        TaskSort {
+            field: SortField::Priority,
+            desc: true,
+            ..Default::default()
+        }
+    );
+    assert_eq!(TaskSort::parse("created_at"), TaskSort::default());
+    // unknown fields fall back to ascending created_at
+    assert_eq!(TaskSort::parse("title:desc"), TaskSort::default());
+}
@@ -0,0 +1,125 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt;
+
+/// A repository holding `existing` and `other`, plus a dump with a changed copy of
+/// `existing` and one new task.
+fn fixture() -> (TaskRepository, Task, Task, Vec<Task>) {
+    let repo = TaskRepository::new();
+    let existing = repo.insert(Task::new_full("stored", "d")).unwrap();
+    let other = repo.insert(Task::new_full("other", "d")).unwrap();
+    let mut changed = existing.clone();
+    changed.title = "from dump".into();
+    let new = Task::new_full("new", "d");
+    (repo, existing, other, vec![changed, new])
+}
+
+async fn load(
+    repo: &TaskRepository,
+    mode: &str,
+    tasks: &[Task],
+) -> (StatusCode, serde_json::Value) {
+    let uri = if mode.is_empty() {
+        "/tasks/load".to_string()
+    } else {
+        format!("/tasks/load?mode={}", mode)
+    };
+    let resp = build_router(repo.clone())
+        .oneshot(
+            Request::post(uri)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_vec(tasks).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn replace_is_the_default_and_wipes_first() {
+    let (repo, existing, other, dump) = fixture();
+    let (status, body) = load(&repo, "", &dump).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        serde_json::json!({"loaded": 2, "skipped": 0, "replaced": 1})
+    );
+    assert_eq!(repo.count(), 2);
+    assert_eq!(repo.get(&existing.id).unwrap().title, "from dump");
+    assert!(repo.get(&other.id).is_none());
+
+    let (_, body) = load(&repo, "replace", &dump).await;
+    assert_eq!(body["replaced"], 2);
+}
+
+#[tokio::test]
+async fn merge_upserts_and_keeps_the_rest() {
+    let (repo, existing, other, dump) = fixture();
+    let (status, body) = load(&repo, "merge", &dump).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        serde_json::json!({"loaded": 2, "skipped": 0, "replaced": 1})
+    );
+    assert_eq!(repo.count(), 3);
+    assert_eq!(repo.get(&existing.id).unwrap().title, "from dump");
+    assert!(repo.get(&other.id).is_some());
+    assert!(repo.get(&dump[1].id).is_some());
+}
+
+#[tokio::test]
+async fn skip_keeps_stored_tasks_on_collision() {
+    let (repo, existing, other, dump) = fixture();
+    let (status, body) = load(&repo, "skip", &dump).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        serde_json::json!({"loaded": 1, "skipped": 1, "replaced": 0})
+    );
+    assert_eq!(repo.count(), 3);
+    assert_eq!(repo.get(&existing.id).unwrap().title, "stored");
+    assert!(repo.get(&other.id).is_some());
+}
+
+#[tokio::test]
+async fn loaded_tasks_never_steal_a_stored_seq() {
+    let (repo, _, other, _) = fixture();
+    let mut incoming = Task::new_full("incoming", "d");
+    incoming.seq = other.seq;
+    let (_, body) = load(&repo, "merge", std::slice::from_ref(&incoming)).await;
+    assert_eq!(body["loaded"], 1);
+    assert_eq!(repo.get_by_seq(other.seq).unwrap().id, other.id);
+    let stored = repo.get(&incoming.id).unwrap();
+    assert_ne!(stored.seq, other.seq);
+    assert_eq!(repo.get_by_seq(stored.seq).unwrap().id, incoming.id);
+}
+
+#[tokio::test]
+async fn replace_gives_repeated_seqs_a_fresh_one() {
+    let (repo, _, _, _) = fixture();
+    let mut first = Task::new_full("first", "d");
+    first.seq = 7;
+    let mut second = Task::new_full("second", "d");
+    second.seq = first.seq;
+    let (status, body) = load(&repo, "replace", &[first.clone(), second.clone()]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["loaded"], 2);
+    assert_eq!(repo.get_by_seq(first.seq).unwrap().id, first.id);
+    let stored = repo.get(&second.id).unwrap();
+    assert_ne!(stored.seq, first.seq);
+    assert_eq!(repo.get_by_seq(stored.seq).unwrap().id, second.id);
+}
+
+#[tokio::test]
+async fn unknown_mode_is_rejected() {
+    let (repo, _, _, dump) = fixture();
+    let (status, _) = load(&repo, "upsert", &dump).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.count(), 2);
+}
@@ -0,0 +1,124 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use parking_lot::Mutex;
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::routes::build_router;
+use std::sync::Once;
+use tower::ServiceExt; // oneshot
+
+/// Global logger that keeps every debug message so tests can inspect them.
+struct Capture;
+
+static LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static INIT: Once = Once::new();
+
+impl log::Log for Capture {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+    fn log(&self, record: &log::Record) {
+        if record.level() == log::Level::Debug {
+            LINES.lock().push(record.args().to_string());
+        }
+    }
+    fn flush(&self) {}
+}
+
+fn captured(marker: &str) -> Vec<String> {
+    INIT.call_once(|| {
+        log::set_logger(&Capture).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+    });
+    LINES
+        .lock()
+        .iter()
+        .filter(|l| l.contains(marker))
+        .cloned()
+        .collect()
+}
+
+fn repo(config: AppConfig) -> TaskRepository {
+    captured("");
+    TaskRepository::new().with_config(config)
+}
+
+async fn create(repo: &TaskRepository, title: &str) -> serde_json::Value {
+    let body = serde_json::json!({"title": title, "description": "secret notes"});
+    let req = Request::post("/tasks")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn bodies_are_logged_with_redaction_when_enabled() {
+    let repo = repo(AppConfig {
+        log_bodies: true,
+        log_redact_fields: vec!["description".into()],
+        ..Default::default()
+    });
+    let task = create(&repo, "logged-marker-1").await;
+    // the handler and client still see the real body
+    assert_eq!(task["description"], "secret notes");
+    assert_eq!(repo.list()[0].description, "secret notes");
+
+    let lines = captured("logged-marker-1");
+    assert_eq!(lines.len(), 2, "{:?}", lines);
+    assert!(lines[0].starts_with("request body POST /tasks"));
+    assert!(lines[1].starts_with("response body POST /tasks 201"));
+    for l in &lines {
+        assert!(l.contains("[REDACTED]"));
+        assert!(!l.contains("secret"));
+    }
+}
+
+#[tokio::test]
+async fn logged_bodies_are_truncated() {
+    let repo = repo(AppConfig {
+        log_bodies: true,
+        log_body_max_bytes: 30,
+        ..Default::default()
+    });
+    create(&repo, "logged-marker-2").await;
+    // only this test sets a cap small enough to cut a body
+    let lines = captured("bytes truncated");
+    assert_eq!(lines.len(), 2, "{:?}", lines);
+    for l in &lines {
+        let body = l.split_once(": ").unwrap().1;
+        assert!(body.split("... (").next().unwrap().len() <= 30);
+    }
+}
+
+#[tokio::test]
+async fn nothing_is_logged_when_disabled() {
+    let repo = repo(AppConfig::default());
+    let task = create(&repo, "logged-marker-3").await;
+    assert_eq!(task["title"], "logged-marker-3");
+    assert!(captured("logged-marker-3").is_empty());
+}
+
+#[tokio::test]
+async fn oversized_request_bodies_are_413_when_enabled() {
+    let repo = repo(AppConfig {
+        log_bodies: true,
+        ..Default::default()
+    });
+    let description = "x".repeat(rust_api_hub::middleware::REQUEST_BODY_LIMIT);
+    let body = serde_json::json!({"title": "logged-marker-4", "description": description});
+    let req = Request::post("/tasks")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let err: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(err["error"], "payload too large");
+    assert_eq!(repo.count(), 0);
+    assert!(captured("logged-marker-4").is_empty());
+}
@@ -1,3 +1,6 @@
+// the explicit asserts document that logging must not panic
+#![allow(clippy::assertions_on_constants)]
+
 use rust_api_hub::utils::logger::{log_error, log_info};
 
 #[test]
@@ -0,0 +1,56 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use rust_api_hub::config::{AppConfig, DEFAULT_MAX_PAGE};
+use rust_api_hub::handlers::task_handler::{ListParams, get_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn page(n: usize) -> Query<ListParams> {
+    Query(ListParams {
+        page: Some(n),
+        per_page: Some(1),
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn page_over_default_limit_is_rejected() {
+    let repo = TaskRepository::new();
+    let (code, Json(resp)) = get_tasks(State(repo), HeaderMap::new(), page(1_000_000))
+        .await
+        .unwrap_err();
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(resp["error"], "page too large");
+}
+
+#[tokio::test]
+async fn page_at_the_limit_is_served() {
+    let repo = TaskRepository::new();
+    let Json(resp) = get_tasks(State(repo), HeaderMap::new(), page(DEFAULT_MAX_PAGE))
+        .await
+        .unwrap();
+    assert_eq!(resp["page"], DEFAULT_MAX_PAGE);
+    assert_eq!(resp["items"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn configured_limit_applies() {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        max_page: 2,
+        ..Default::default()
+    });
+    for i in 0..3 {
+        repo.insert(Task::new_full(&format!("t{}", i), "d"))
+            .unwrap();
+    }
+    let Json(resp) = get_tasks(State(repo.clone()), HeaderMap::new(), page(2))
+        .await
+        .unwrap();
+    assert_eq!(resp["items"].as_array().unwrap().len(), 1);
+
+    let (code, _) = get_tasks(State(repo), HeaderMap::new(), page(3))
+        .await
+        .unwrap_err();
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,73 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{TagsPayload, set_tags};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn repo_with_limit(max_tag_len: usize) -> (TaskRepository, Task) {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        max_tag_len,
+        ..Default::default()
+    });
+    let task = repo.insert(Task::new_full("t", "d")).unwrap();
+    (repo, task)
+}
+
+fn tags(tag: String) -> Json<TagsPayload> {
+    Json(TagsPayload { tags: vec![tag] })
+}
+
+#[tokio::test]
+async fn configured_limit_rejects_longer_tag() {
+    let (repo, task) = repo_with_limit(32);
+    let (code, Json(resp)) = set_tags(
+        Path(task.id.to_string()),
+        State(repo.clone()),
+        axum::extract::Query(Default::default()),
+        tags("a".repeat(40)),
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(resp["error"], "tag too long (max 32 chars)");
+    assert!(repo.get(&task.id).unwrap().tags.is_empty());
+}
+
+#[tokio::test]
+async fn configured_limit_accepts_shorter_tag() {
+    let (repo, task) = repo_with_limit(32);
+    let tag = format!("project/sub/{}", "a".repeat(18));
+    assert_eq!(tag.len(), 30);
+    let (code, _) = set_tags(
+        Path(task.id.to_string()),
+        State(repo.clone()),
+        axum::extract::Query(Default::default()),
+        tags(tag.clone()),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(repo.get(&task.id).unwrap().tags, vec![tag]);
+}
+
+#[tokio::test]
+async fn default_limit_is_64() {
+    let repo = TaskRepository::new();
+    let task = repo.insert(Task::new_full("t", "d")).unwrap();
+    let (code, _) = set_tags(
+        Path(task.id.to_string()),
+        State(repo.clone()),
+        axum::extract::Query(Default::default()),
+        tags("a".repeat(64)),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    let (code, _) = set_tags(
+        Path(task.id.to_string()),
+        State(repo.clone()),
+        axum::extract::Query(Default::default()),
+        tags("a".repeat(65)),
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+}
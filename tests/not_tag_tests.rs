@@ -0,0 +1,73 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use rust_api_hub::handlers::task_handler::{ListParams, get_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn add(repo: &TaskRepository, title: &str, tags: &[&str]) {
+    let mut t = Task::new_full(title, "d");
+    t.tags = tags.iter().map(|s| s.to_string()).collect();
+    repo.insert(t).unwrap();
+}
+
+async fn titles(repo: &TaskRepository, params: ListParams) -> (Vec<String>, u64) {
+    let Json(resp) = get_tasks(State(repo.clone()), HeaderMap::new(), Query(params))
+        .await
+        .unwrap();
+    let mut titles: Vec<String> = resp["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["title"].as_str().unwrap().to_string())
+        .collect();
+    titles.sort();
+    (titles, resp["total"].as_u64().unwrap())
+}
+
+fn repo() -> TaskRepository {
+    let repo = TaskRepository::new();
+    add(&repo, "archived", &["archived"]);
+    add(&repo, "urgent", &["urgent"]);
+    add(&repo, "urgent blocked", &["urgent", "blocked"]);
+    add(&repo, "untagged", &[]);
+    repo
+}
+
+#[tokio::test]
+async fn not_tag_excludes_tagged_tasks() {
+    let repo = repo();
+    let params = ListParams {
+        not_tag: Some("Archived".into()),
+        ..Default::default()
+    };
+    let (titles, total) = titles(&repo, params).await;
+    assert_eq!(titles, vec!["untagged", "urgent", "urgent blocked"]);
+    assert_eq!(total, 3);
+}
+
+#[tokio::test]
+async fn tag_and_not_tag_combine() {
+    let repo = repo();
+    let params = ListParams {
+        tag: Some("urgent".into()),
+        not_tag: Some("blocked".into()),
+        ..Default::default()
+    };
+    let (titles, total) = titles(&repo, params).await;
+    assert_eq!(titles, vec!["urgent"]);
+    assert_eq!(total, 1);
+}
+
+#[tokio::test]
+async fn total_reflects_filter_before_paging() {
+    let repo = repo();
+    let params = ListParams {
+        not_tag: Some("archived".into()),
+        per_page: Some(1),
+        ..Default::default()
+    };
+    let (titles, total) = titles(&repo, params).await;
+    assert_eq!(titles.len(), 1);
+    assert_eq!(total, 3);
+}
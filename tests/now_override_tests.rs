@@ -0,0 +1,66 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue};
+use chrono::{Duration, Utc};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{ListParams, get_stats, get_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+/// A repository holding one task that expires in an hour.
+fn repo_with_expiring_task(allow_override: bool) -> (TaskRepository, Task) {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        allow_now_override: allow_override,
+        ..Default::default()
+    });
+    let mut t = Task::new_full("soon", "d");
+    t.expires_at = Some(Utc::now() + Duration::hours(1));
+    let t = repo.insert(t).unwrap();
+    (repo, t)
+}
+
+fn future_now() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let later = (Utc::now() + Duration::days(1)).to_rfc3339();
+    headers.insert("x-now", HeaderValue::from_str(&later).unwrap());
+    headers
+}
+
+async fn listed(repo: &TaskRepository, headers: HeaderMap) -> usize {
+    let Json(resp) = get_tasks(State(repo.clone()), headers, Query(ListParams::default()))
+        .await
+        .unwrap();
+    resp["items"].as_array().unwrap().len()
+}
+
+#[tokio::test]
+async fn future_now_hides_soon_expiring_task_when_allowed() {
+    let (repo, t) = repo_with_expiring_task(true);
+    assert_eq!(listed(&repo, HeaderMap::new()).await, 1);
+    assert_eq!(listed(&repo, future_now()).await, 0);
+
+    let Json(stats) = get_stats(State(repo.clone()), future_now()).await;
+    assert_eq!(stats["expired"], 1);
+    let Json(stats) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+    assert_eq!(stats["expired"], 0);
+
+    // read-only: nothing stored changes
+    assert_eq!(repo.get(&t.id).unwrap(), t);
+    assert_eq!(repo.count(), 1);
+}
+
+#[tokio::test]
+async fn now_override_is_ignored_when_flag_is_off() {
+    let (repo, _) = repo_with_expiring_task(false);
+    assert_eq!(listed(&repo, future_now()).await, 1);
+    let Json(stats) = get_stats(State(repo.clone()), future_now()).await;
+    assert_eq!(stats["expired"], 0);
+}
+
+#[tokio::test]
+async fn invalid_now_header_falls_back_to_the_clock() {
+    let (repo, _) = repo_with_expiring_task(true);
+    let mut headers = HeaderMap::new();
+    headers.insert("x-now", HeaderValue::from_static("tomorrow"));
+    assert_eq!(listed(&repo, headers).await, 1);
+}
@@ -0,0 +1,90 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{
+    CooccurrenceParams, DuplicatesParams, find_duplicates, get_stats, get_tag_cooccurrence,
+};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn repo(workers: usize) -> TaskRepository {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        stats_workers: workers,
+        ..Default::default()
+    });
+    for i in 0..200 {
+        let mut t = Task::new_full(&format!("task {}", i % 50), "d");
+        t.completed = i % 4 == 0;
+        t.tags = vec!["a".into(), format!("t{}", i % 3)];
+        repo.insert(t).unwrap();
+    }
+    repo
+}
+
+#[tokio::test]
+async fn offloaded_endpoints_return_correct_results() {
+    // a single slot: concurrent calls queue up rather than fail
+    let repo = repo(1);
+    let (Json(stats), Json(dups), Json(pairs)) = tokio::join!(
+        get_stats(State(repo.clone()), HeaderMap::new()),
+        find_duplicates(
+            State(repo.clone()),
+            Query(DuplicatesParams { threshold: None })
+        ),
+        get_tag_cooccurrence(
+            State(repo.clone()),
+            Query(CooccurrenceParams { top: Some(1) })
+        ),
+    );
+    assert_eq!(stats["total"], 200);
+    assert_eq!(stats["completed"], 50);
+    assert_eq!(dups["groups"].as_array().unwrap().len(), 50);
+    assert!(
+        dups["groups"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|g| g["ids"].as_array().unwrap().len() == 4)
+    );
+    assert_eq!(pairs["pairs"][0]["a"], "a");
+    assert_eq!(pairs["pairs"][0]["count"], 67);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn stats_come_from_a_consistent_snapshot() {
+    let repo = repo(2);
+    let writer = {
+        let repo = repo.clone();
+        tokio::spawn(async move {
+            for i in 0..500 {
+                let mut t = Task::new_full("w", "d");
+                t.completed = i % 2 == 0;
+                t.tags = vec!["w".into()];
+                repo.insert(t).unwrap();
+                tokio::task::yield_now().await;
+            }
+        })
+    };
+    for _ in 0..50 {
+        let Json(stats) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+        let total = stats["total"].as_u64().unwrap();
+        assert_eq!(
+            total,
+            stats["completed"].as_u64().unwrap() + stats["incomplete"].as_u64().unwrap()
+        );
+        // every task carries the tag `a` or `w`, never both, so their counts add up
+        let count = |tag: &str| {
+            stats["tag_distribution"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|e| e["tag"] == tag)
+                .map_or(0, |e| e["count"].as_u64().unwrap())
+        };
+        assert_eq!(count("a") + count("w"), total);
+    }
+    writer.await.unwrap();
+    let Json(stats) = get_stats(State(repo), HeaderMap::new()).await;
+    assert_eq!(stats["total"], 700);
+}
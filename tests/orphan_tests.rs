@@ -0,0 +1,70 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{OrphansQuery, get_orphans};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+/// A parent split into two subtasks, then the parent deleted. Returns the child ids.
+fn orphaned(repo: &TaskRepository) -> Vec<uuid::Uuid> {
+    let parent = repo.insert(Task::new_full("parent", "d")).unwrap();
+    let (_, children) = repo
+        .split(&parent.id, &["a".to_string(), "b".to_string()])
+        .unwrap();
+    assert!(repo.remove(&parent.id));
+    children.iter().map(|c| c.id).collect()
+}
+
+#[tokio::test]
+async fn subtask_of_deleted_parent_is_listed() {
+    let repo = TaskRepository::new();
+    let ids = orphaned(&repo);
+    let healthy = repo.insert(Task::new_full("other", "d")).unwrap();
+    let _ = repo.split(&healthy.id, &["c".to_string()]).unwrap();
+
+    let (status, Json(body)) =
+        get_orphans(State(repo.clone()), Query(OrphansQuery::default())).await;
+    assert_eq!(status, StatusCode::OK);
+    let listed: Vec<_> = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap().to_string())
+        .collect();
+    let mut expected: Vec<_> = ids.iter().map(|i| i.to_string()).collect();
+    let mut sorted = listed.clone();
+    sorted.sort();
+    expected.sort();
+    assert_eq!(sorted, expected);
+    assert_eq!(body["fixed"], 0);
+    assert!(ids.iter().all(|i| repo.get(i).unwrap().parent_id.is_some()));
+}
+
+#[tokio::test]
+async fn fix_clears_dangling_parent() {
+    let repo = TaskRepository::new();
+    let ids = orphaned(&repo);
+
+    let (status, Json(body)) =
+        get_orphans(State(repo.clone()), Query(OrphansQuery { fix: true })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["fixed"], 2);
+    assert!(body["items"][0]["parent_id"].is_string());
+    assert!(ids.iter().all(|i| repo.get(i).unwrap().parent_id.is_none()));
+
+    let (_, Json(body)) = get_orphans(State(repo), Query(OrphansQuery::default())).await;
+    assert_eq!(body["items"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn fix_is_refused_in_read_only_mode() {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        read_only: true,
+        ..Default::default()
+    });
+    let ids = orphaned(&repo);
+    let (status, _) = get_orphans(State(repo.clone()), Query(OrphansQuery { fix: true })).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(repo.get(&ids[0]).unwrap().parent_id.is_some());
+}
@@ -1,5 +1,6 @@
 use axum::Json;
 use axum::extract::State;
+use axum::http::HeaderMap;
 use rust_api_hub::models::repository::TaskRepository;
 use rust_api_hub::models::task::TaskCreate;
 
@@ -15,10 +16,15 @@ async fn pagination_returns_correct_page() {
         let payload = TaskCreate {
             title: format!("t{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (_code, _created) =
-            rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload))
-                .await;
+        let (_code, _created) = rust_api_hub::handlers::task_handler::create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
     }
 
     use rust_api_hub::handlers::task_handler::ListParams;
@@ -27,9 +33,15 @@ async fn pagination_returns_correct_page() {
         per_page: Some(10),
         sort: None,
         completed: None,
+        ..Default::default()
     });
-    let Json(resp) =
-        rust_api_hub::handlers::task_handler::get_tasks(State(repo.clone()), params).await;
+    let Json(resp) = rust_api_hub::handlers::task_handler::get_tasks(
+        State(repo.clone()),
+        HeaderMap::new(),
+        params,
+    )
+    .await
+    .unwrap();
     assert_eq!(resp["items"].as_array().unwrap().len(), 10);
     assert_eq!(resp["page"].as_u64().unwrap(), 2);
     assert_eq!(resp["per_page"].as_u64().unwrap(), 10);
@@ -43,10 +55,15 @@ async fn per_page_limits_results_and_caps() {
         let payload = TaskCreate {
             title: format!("t{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (_code, _created) =
-            rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload))
-                .await;
+        let (_code, _created) = rust_api_hub::handlers::task_handler::create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
     }
 
     use rust_api_hub::handlers::task_handler::ListParams;
@@ -56,9 +73,15 @@ async fn per_page_limits_results_and_caps() {
         per_page: Some(1000),
         sort: None,
         completed: None,
+        ..Default::default()
     });
-    let Json(resp) =
-        rust_api_hub::handlers::task_handler::get_tasks(State(repo.clone()), params).await;
+    let Json(resp) = rust_api_hub::handlers::task_handler::get_tasks(
+        State(repo.clone()),
+        HeaderMap::new(),
+        params,
+    )
+    .await
+    .unwrap();
     // items should be 5 (only 5 tasks exist)
     assert_eq!(resp["items"].as_array().unwrap().len(), 5);
     // server should report capped per_page (100)
@@ -73,10 +96,15 @@ async fn sorting_by_created_at_desc() {
         let payload = TaskCreate {
             title: format!("t{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (_code, _created) =
-            rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload))
-                .await;
+        let (_code, _created) = rust_api_hub::handlers::task_handler::create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
         // ensure distinct timestamps
         std::thread::sleep(std::time::Duration::from_millis(1));
     }
@@ -87,9 +115,15 @@ async fn sorting_by_created_at_desc() {
         per_page: Some(5),
         sort: Some("created_at:desc".into()),
         completed: None,
+        ..Default::default()
     });
-    let Json(resp) =
-        rust_api_hub::handlers::task_handler::get_tasks(State(repo.clone()), params).await;
+    let Json(resp) = rust_api_hub::handlers::task_handler::get_tasks(
+        State(repo.clone()),
+        HeaderMap::new(),
+        params,
+    )
+    .await
+    .unwrap();
     let items = resp["items"].as_array().unwrap();
     assert_eq!(items[0]["title"].as_str().unwrap(), "t4");
 }
@@ -0,0 +1,76 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use rust_api_hub::handlers::task_handler::{ListParams, get_tasks, pin_task, unpin_task};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+async fn list(repo: &TaskRepository, params: ListParams) -> Vec<serde_json::Value> {
+    let Json(v) = get_tasks(State(repo.clone()), HeaderMap::new(), Query(params))
+        .await
+        .unwrap();
+    v["items"].as_array().unwrap().clone()
+}
+
+fn seeded() -> (TaskRepository, Vec<Task>) {
+    let repo = TaskRepository::new();
+    let now = chrono::Utc::now();
+    let tasks = (0..3)
+        .map(|i| {
+            let mut t = Task::new_full(&format!("t{}", i), "d");
+            t.created_at = now - chrono::Duration::minutes(10 - i);
+            repo.insert(t).unwrap()
+        })
+        .collect();
+    (repo, tasks)
+}
+
+#[tokio::test]
+async fn pinned_task_is_listed_first_under_created_at_asc() {
+    let (repo, tasks) = seeded();
+    let newest = &tasks[2];
+    let (status, Json(resp)) = pin_task(Path(newest.id.to_string()), State(repo.clone())).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(resp["task"]["pinned"], true);
+
+    let items = list(
+        &repo,
+        ListParams {
+            sort: Some("created_at:asc".into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    let ids: Vec<_> = items.iter().map(|t| t["id"].as_str().unwrap()).collect();
+    let expected = [&tasks[2], &tasks[0], &tasks[1]].map(|t| t.id.to_string());
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn pinned_only_returns_only_pinned_tasks() {
+    let (repo, tasks) = seeded();
+    let _ = pin_task(Path(tasks[0].id.to_string()), State(repo.clone())).await;
+    let _ = pin_task(Path(tasks[1].id.to_string()), State(repo.clone())).await;
+    let _ = unpin_task(Path(tasks[1].id.to_string()), State(repo.clone())).await;
+
+    let items = list(
+        &repo,
+        ListParams {
+            pinned_only: Some(true),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], tasks[0].id.to_string());
+    assert!(items.iter().all(|t| t["pinned"] == true));
+}
+
+#[tokio::test]
+async fn pin_unknown_or_invalid_id() {
+    let repo = TaskRepository::new();
+    let (status, _) = pin_task(Path(uuid::Uuid::new_v4().to_string()), State(repo.clone())).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = unpin_task(Path("nope".into()), State(repo)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,78 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::get_plan;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn repo() -> TaskRepository {
+    TaskRepository::new()
+}
+
+#[tokio::test]
+async fn plan_orders_linear_chain() {
+    let repo = repo();
+    // c depends on b, b depends on a; insert in reverse so insertion order doesn't help
+    let a = Task::new_full("a", "d");
+    let mut b = Task::new_full("b", "d");
+    let mut c = Task::new_full("c", "d");
+    b.depends_on = vec![a.id];
+    c.depends_on = vec![b.id];
+    repo.insert(c.clone()).unwrap();
+    repo.insert(b.clone()).unwrap();
+    repo.insert(a.clone()).unwrap();
+
+    let (code, axum::Json(resp)) = get_plan(State(repo.clone())).await;
+    assert_eq!(code, StatusCode::OK);
+    let order: Vec<String> = resp["order"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        order,
+        vec![a.id.to_string(), b.id.to_string(), c.id.to_string()]
+    );
+}
+
+#[tokio::test]
+async fn plan_independent_tasks_follow_created_at() {
+    let repo = repo();
+    let first = Task::new_full("first", "d");
+    std::thread::sleep(std::time::Duration::from_millis(1));
+    let second = Task::new_full("second", "d");
+    repo.insert(second.clone()).unwrap();
+    repo.insert(first.clone()).unwrap();
+
+    let (code, axum::Json(resp)) = get_plan(State(repo.clone())).await;
+    assert_eq!(code, StatusCode::OK);
+    let order = resp["order"].as_array().unwrap();
+    assert_eq!(order[0].as_str().unwrap(), first.id.to_string());
+    assert_eq!(order[1].as_str().unwrap(), second.id.to_string());
+}
+
+#[tokio::test]
+async fn plan_cycle_returns_conflict_with_ids() {
+    let repo = repo();
+    let mut a = Task::new_full("a", "d");
+    let mut b = Task::new_full("b", "d");
+    let mut downstream = Task::new_full("downstream", "d");
+    a.depends_on = vec![b.id];
+    b.depends_on = vec![a.id];
+    downstream.depends_on = vec![a.id];
+    repo.insert(a.clone()).unwrap();
+    repo.insert(b.clone()).unwrap();
+    repo.insert(downstream.clone()).unwrap();
+
+    let (code, axum::Json(resp)) = get_plan(State(repo.clone())).await;
+    assert_eq!(code, StatusCode::CONFLICT);
+    let ids: Vec<&str> = resp["ids"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&a.id.to_string().as_str()));
+    assert!(ids.contains(&b.id.to_string().as_str()));
+}
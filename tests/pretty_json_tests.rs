@@ -0,0 +1,60 @@
+use axum::body::Body;
+use axum::http::Request;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt; // oneshot
+
+async fn get_body(repo: &TaskRepository, req: Request<Body>) -> String {
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+fn repo() -> TaskRepository {
+    let repo = TaskRepository::new();
+    repo.insert(Task::new_full("pretty", "d")).unwrap();
+    repo
+}
+
+#[tokio::test]
+async fn pretty_query_param_indents_list() {
+    let repo = repo();
+    let req = Request::builder()
+        .uri("/tasks?pretty=true")
+        .body(Body::empty())
+        .unwrap();
+    let body = get_body(&repo, req).await;
+    assert!(body.contains("\n  \""));
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(v["total"], 1);
+}
+
+#[tokio::test]
+async fn pretty_header_indents_task_and_stats() {
+    let repo = repo();
+    let id = repo.list()[0].id;
+    for uri in [format!("/tasks/{}", id), "/tasks/stats".to_string()] {
+        let req = Request::builder()
+            .uri(&uri)
+            .header("x-pretty", "true")
+            .body(Body::empty())
+            .unwrap();
+        let body = get_body(&repo, req).await;
+        assert!(body.contains('\n'), "{} should be pretty", uri);
+    }
+}
+
+#[tokio::test]
+async fn compact_by_default() {
+    let repo = repo();
+    let req = Request::builder()
+        .uri("/tasks")
+        .body(Body::empty())
+        .unwrap();
+    let body = get_body(&repo, req).await;
+    assert!(!body.contains('\n'));
+}
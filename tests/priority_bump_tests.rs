@@ -0,0 +1,75 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{bump_priority, lower_priority};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, Task};
+
+fn add(repo: &TaskRepository, priority: Priority) -> Task {
+    let mut t = Task::new_full("t", "d");
+    t.priority = priority;
+    repo.insert(t).unwrap()
+}
+
+#[test]
+fn next_and_prev_step_one_level_and_saturate() {
+    assert_eq!(Priority::Low.next(), Priority::Medium);
+    assert_eq!(Priority::Critical.next(), Priority::Critical);
+    assert_eq!(Priority::Critical.prev(), Priority::High);
+    assert_eq!(Priority::Low.prev(), Priority::Low);
+}
+
+#[tokio::test]
+async fn bump_high_becomes_critical() {
+    let repo = TaskRepository::new();
+    let t = add(&repo, Priority::High);
+    let Json(resp) = bump_priority(Path(t.id.to_string()), State(repo.clone()))
+        .await
+        .unwrap();
+    assert_eq!(resp["task"]["priority"], "critical");
+    assert_eq!(repo.get(&t.id).unwrap().priority, Priority::Critical);
+}
+
+#[tokio::test]
+async fn bump_at_critical_stays_critical() {
+    let repo = TaskRepository::new();
+    let t = add(&repo, Priority::Critical);
+    let Json(resp) = bump_priority(Path(t.id.to_string()), State(repo.clone()))
+        .await
+        .unwrap();
+    assert_eq!(resp["task"]["priority"], "critical");
+}
+
+#[tokio::test]
+async fn lower_at_low_stays_low() {
+    let repo = TaskRepository::new();
+    let t = add(&repo, Priority::Low);
+    let Json(resp) = lower_priority(Path(t.id.to_string()), State(repo.clone()))
+        .await
+        .unwrap();
+    assert_eq!(resp["task"]["priority"], "low");
+}
+
+#[tokio::test]
+async fn bump_to_critical_respects_description_rule() {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        critical_min_desc: 20,
+        ..Default::default()
+    });
+    let t = add(&repo, Priority::High);
+    let (code, _) = bump_priority(Path(t.id.to_string()), State(repo.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.get(&t.id).unwrap().priority, Priority::High);
+}
+
+#[tokio::test]
+async fn missing_task_is_404() {
+    let repo = TaskRepository::new();
+    let (code, _) = lower_priority(Path(uuid::Uuid::new_v4().to_string()), State(repo.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(code, StatusCode::NOT_FOUND);
+}
@@ -1,5 +1,6 @@
 use axum::Json;
 use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
 use rust_api_hub::handlers::task_handler::PriorityPayload;
 use rust_api_hub::models::repository::TaskRepository;
 use rust_api_hub::models::task::TaskCreate;
@@ -16,9 +17,15 @@ async fn set_and_get_priority_roundtrip() {
     let payload = TaskCreate {
         title: "test task".into(),
         description: "desc".into(),
+        ..Default::default()
     };
-    let (_code, Json(task)) =
-        rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload)).await;
+    let (_code, Json(task)) = rust_api_hub::handlers::task_handler::create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap();
 
     // default priority should be medium
     assert_eq!(task.priority, rust_api_hub::models::task::Priority::Medium);
@@ -51,16 +58,21 @@ async fn search_by_priority_filters_correctly() {
     let repo = repo();
 
     // create 5 tasks with different priorities
-    let priorities = vec!["low", "medium", "high", "critical", "medium"];
+    let priorities = ["low", "medium", "high", "critical", "medium"];
 
     for (i, prio) in priorities.iter().enumerate() {
         let payload = TaskCreate {
             title: format!("task{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (_code, Json(task)) =
-            rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload))
-                .await;
+        let (_code, Json(task)) = rust_api_hub::handlers::task_handler::create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
 
         // set priority
         let priority_payload = PriorityPayload {
@@ -99,9 +111,15 @@ async fn invalid_priority_rejected() {
     let payload = TaskCreate {
         title: "test".into(),
         description: "d".into(),
+        ..Default::default()
     };
-    let (_code, Json(task)) =
-        rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload)).await;
+    let (_code, Json(task)) = rust_api_hub::handlers::task_handler::create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap();
 
     // try to set invalid priority
     let priority_payload = PriorityPayload {
@@ -125,16 +143,21 @@ async fn sort_by_priority_orders_correctly() {
     let repo = repo();
 
     // create tasks with different priorities
-    let priorities = vec!["low", "critical", "medium", "high"];
+    let priorities = ["low", "critical", "medium", "high"];
 
     for (i, prio) in priorities.iter().enumerate() {
         let payload = TaskCreate {
             title: format!("task{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (_code, Json(task)) =
-            rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload))
-                .await;
+        let (_code, Json(task)) = rust_api_hub::handlers::task_handler::create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
 
         let priority_payload = PriorityPayload {
             priority: prio.to_string(),
@@ -153,10 +176,16 @@ async fn sort_by_priority_orders_correctly() {
         page: None,
         per_page: None,
         sort: Some("priority:asc".into()),
+        ..Default::default()
     };
 
-    let Json(resp) =
-        rust_api_hub::handlers::task_handler::get_tasks(State(repo.clone()), Query(params)).await;
+    let Json(resp) = rust_api_hub::handlers::task_handler::get_tasks(
+        State(repo.clone()),
+        HeaderMap::new(),
+        Query(params),
+    )
+    .await
+    .unwrap();
 
     let items = resp["items"].as_array().unwrap();
     assert_eq!(items.len(), 4);
@@ -173,11 +202,16 @@ async fn sort_by_priority_orders_correctly() {
         page: None,
         per_page: None,
         sort: Some("priority:desc".into()),
+        ..Default::default()
     };
 
-    let Json(resp_desc) =
-        rust_api_hub::handlers::task_handler::get_tasks(State(repo.clone()), Query(params_desc))
-            .await;
+    let Json(resp_desc) = rust_api_hub::handlers::task_handler::get_tasks(
+        State(repo.clone()),
+        HeaderMap::new(),
+        Query(params_desc),
+    )
+    .await
+    .unwrap();
 
     let items_desc = resp_desc["items"].as_array().unwrap();
     assert_eq!(items_desc[0]["priority"].as_str().unwrap(), "critical");
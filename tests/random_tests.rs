@@ -0,0 +1,56 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{RandomParams, random_task};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn seeded_repo(n: usize) -> TaskRepository {
+    let repo = TaskRepository::new();
+    for i in 0..n {
+        repo.insert(Task::new_full(&format!("t{}", i), "d"))
+            .unwrap();
+    }
+    repo
+}
+
+#[tokio::test]
+async fn seeded_random_pick_is_deterministic() {
+    let repo = seeded_repo(20);
+    let pick = |seed| {
+        let repo = repo.clone();
+        async move {
+            let params = RandomParams {
+                seed: Some(seed),
+                ..Default::default()
+            };
+            let (code, Json(resp)) = random_task(State(repo), Query(params)).await;
+            assert_eq!(code, StatusCode::OK);
+            resp["task"]["id"].as_str().unwrap().to_string()
+        }
+    };
+    assert_eq!(pick(42).await, pick(42).await);
+}
+
+#[tokio::test]
+async fn random_respects_completed_filter() {
+    let repo = seeded_repo(5);
+    let mut done = Task::new_full("done", "d");
+    done.completed = true;
+    repo.insert(done.clone()).unwrap();
+    let params = RandomParams {
+        completed: Some(true),
+        seed: Some(7),
+    };
+    let (code, Json(resp)) = random_task(State(repo), Query(params)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["task"]["id"].as_str().unwrap(), done.id.to_string());
+}
+
+#[tokio::test]
+async fn random_on_empty_repo_returns_404() {
+    let repo = TaskRepository::new();
+    let (code, Json(resp)) = random_task(State(repo), Query(RandomParams::default())).await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+    assert_eq!(resp["error"].as_str().unwrap(), "no tasks");
+}
@@ -0,0 +1,121 @@
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Request, Response, StatusCode, header};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::models::repository::{RATE_MAX_CLIENTS, RATE_OVERFLOW_CLIENT, TaskRepository};
+use rust_api_hub::routes::build_router;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tower::ServiceExt;
+
+fn limited(limit: u32) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        rate_limit: Some(limit),
+        ..Default::default()
+    })
+}
+
+async fn get(repo: &TaskRepository, client: &str) -> Response<Body> {
+    get_via(repo, client, None).await
+}
+
+/// GET /tasks from peer `client`, optionally with an `X-Forwarded-For` header.
+async fn get_via(repo: &TaskRepository, client: &str, forwarded: Option<&str>) -> Response<Body> {
+    let peer: SocketAddr = format!("{}:40000", client).parse().unwrap();
+    let mut req = Request::get("/tasks").extension(ConnectInfo(peer));
+    if let Some(f) = forwarded {
+        req = req.header("x-forwarded-for", f);
+    }
+    build_router(repo.clone())
+        .oneshot(req.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+fn header_num(resp: &Response<Body>, name: &str) -> i64 {
+    resp.headers()[name].to_str().unwrap().parse().unwrap()
+}
+
+#[tokio::test]
+async fn headers_show_a_decreasing_budget() {
+    let repo = limited(3);
+    let now = chrono::Utc::now().timestamp();
+    let first = get(&repo, "10.0.0.1").await;
+    assert_eq!(first.status(), StatusCode::OK);
+    assert_eq!(header_num(&first, "x-ratelimit-limit"), 3);
+    assert_eq!(header_num(&first, "x-ratelimit-remaining"), 2);
+    let reset = header_num(&first, "x-ratelimit-reset");
+    assert!(reset > now && reset <= now + 61);
+    assert!(first.headers().get(header::RETRY_AFTER).is_none());
+
+    let second = get(&repo, "10.0.0.1").await;
+    assert_eq!(header_num(&second, "x-ratelimit-remaining"), 1);
+}
+
+#[tokio::test]
+async fn over_the_limit_gets_429_with_retry_after() {
+    let repo = limited(2);
+    for _ in 0..2 {
+        assert_eq!(get(&repo, "10.0.0.2").await.status(), StatusCode::OK);
+    }
+    let resp = get(&repo, "10.0.0.2").await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header_num(&resp, "x-ratelimit-remaining"), 0);
+    assert!(header_num(&resp, "x-ratelimit-reset") > 0);
+    let retry = header_num(&resp, header::RETRY_AFTER.as_str());
+    assert!((1..=60).contains(&retry));
+
+    // other clients keep their own budget
+    let other = get(&repo, "10.0.0.3").await;
+    assert_eq!(other.status(), StatusCode::OK);
+    assert_eq!(header_num(&other, "x-ratelimit-remaining"), 1);
+}
+
+#[tokio::test]
+async fn no_headers_when_rate_limiting_is_off() {
+    let resp = get(&TaskRepository::new(), "10.0.0.4").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().get("x-ratelimit-limit").is_none());
+}
+
+#[tokio::test]
+async fn forwarded_for_is_ignored_from_untrusted_peers() {
+    let repo = limited(1);
+    assert_eq!(
+        get_via(&repo, "10.0.0.5", Some("1.1.1.1")).await.status(),
+        StatusCode::OK
+    );
+    // a fresh spoofed header does not buy a fresh budget
+    let resp = get_via(&repo, "10.0.0.5", Some("2.2.2.2")).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn trusted_proxies_key_on_the_forwarded_client() {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        rate_limit: Some(1),
+        trusted_proxies: vec!["10.0.0.9".parse().unwrap()],
+        ..Default::default()
+    });
+    // the rightmost untrusted hop is the client; hops left of it are client-supplied
+    let first = get_via(&repo, "10.0.0.9", Some("6.6.6.6, 3.3.3.3, 10.0.0.9")).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    let again = get_via(&repo, "10.0.0.9", Some("7.7.7.7, 3.3.3.3")).await;
+    assert_eq!(again.status(), StatusCode::TOO_MANY_REQUESTS);
+    let other = get_via(&repo, "10.0.0.9", Some("4.4.4.4")).await;
+    assert_eq!(other.status(), StatusCode::OK);
+}
+
+#[test]
+fn tracked_clients_are_capped() {
+    let repo = TaskRepository::new();
+    let window = Duration::from_secs(60);
+    for i in 0..RATE_MAX_CLIENTS + 50 {
+        repo.rate_limit_hit(&format!("client-{}", i), 5, window);
+    }
+    // every client past the cap landed in the shared overflow bucket
+    assert_eq!(repo.rate_limit_clients(), RATE_MAX_CLIENTS + 1);
+    // ... and spent its budget together
+    assert!(!repo.rate_limit_hit(RATE_OVERFLOW_CLIENT, 5, window).allowed);
+    assert!(repo.rate_limit_hit("client-0", 5, window).allowed);
+}
@@ -0,0 +1,78 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt; // oneshot
+
+fn repo(read_only: bool) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        read_only,
+        ..Default::default()
+    })
+}
+
+async fn send(repo: &TaskRepository, req: Request<Body>) -> (StatusCode, serde_json::Value) {
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    let status = resp.status();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+    )
+}
+
+fn create_req() -> Request<Body> {
+    Request::post("/tasks")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"title":"a","description":"b"}"#))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn post_returns_503_in_read_only_mode() {
+    let repo = repo(true);
+    let (code, body) = send(&repo, create_req()).await;
+    assert_eq!(code, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["error"], "read-only mode");
+    assert_eq!(repo.count(), 0);
+}
+
+#[tokio::test]
+async fn put_and_delete_are_blocked_in_read_only_mode() {
+    let repo = repo(true);
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    let uri = format!("/tasks/{}", t.id);
+    let put = Request::put(&uri)
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"completed":true}"#))
+        .unwrap();
+    assert_eq!(send(&repo, put).await.0, StatusCode::SERVICE_UNAVAILABLE);
+    let del = Request::delete(&uri).body(Body::empty()).unwrap();
+    assert_eq!(send(&repo, del).await.0, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(repo.get(&t.id).unwrap(), t);
+}
+
+#[tokio::test]
+async fn reads_still_work_in_read_only_mode() {
+    let repo = repo(true);
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    let get = Request::get("/tasks").body(Body::empty()).unwrap();
+    let (code, body) = send(&repo, get).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(body["items"].as_array().unwrap().len(), 1);
+    let head = Request::head(format!("/tasks/{}", t.id))
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&repo, head).await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn writes_work_when_not_read_only() {
+    let repo = repo(false);
+    let (code, _) = send(&repo, create_req()).await;
+    assert_eq!(code, StatusCode::CREATED);
+}
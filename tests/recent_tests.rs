@@ -0,0 +1,54 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use rust_api_hub::handlers::task_handler::{RecentParams, get_recent_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn seeded(n: usize) -> (TaskRepository, Vec<Task>) {
+    let repo = TaskRepository::new();
+    let base = chrono::Utc::now() - chrono::Duration::hours(1);
+    let tasks = (0..n)
+        .map(|i| {
+            let mut t = Task::new_full(&format!("t{}", i), "d");
+            t.updated_at = base + chrono::Duration::seconds(i as i64);
+            repo.insert(t).unwrap()
+        })
+        .collect();
+    (repo, tasks)
+}
+
+#[test]
+fn returns_most_recently_updated_after_updates() {
+    let (repo, tasks) = seeded(5);
+    // touch two of the oldest tasks so they become the newest
+    for (i, t) in [&tasks[1], &tasks[0]].into_iter().enumerate() {
+        repo.modify(&t.id, |t| {
+            t.updated_at = chrono::Utc::now() + chrono::Duration::seconds(i as i64)
+        });
+    }
+    let ids: Vec<_> = repo.recent_updated(3).into_iter().map(|t| t.id).collect();
+    assert_eq!(ids, vec![tasks[0].id, tasks[1].id, tasks[4].id]);
+}
+
+#[test]
+fn n_larger_than_store_returns_everything() {
+    let (repo, _) = seeded(3);
+    assert_eq!(repo.recent_updated(10).len(), 3);
+    assert!(repo.recent_updated(0).is_empty());
+}
+
+#[tokio::test]
+async fn handler_respects_limit_and_cap() {
+    let (repo, tasks) = seeded(120);
+    let Json(v) =
+        get_recent_tasks(State(repo.clone()), Query(RecentParams { limit: Some(2) })).await;
+    let items = v["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["id"], tasks[119].id.to_string());
+
+    let Json(v) = get_recent_tasks(State(repo.clone()), Query(RecentParams::default())).await;
+    assert_eq!(v["items"].as_array().unwrap().len(), 10);
+
+    let Json(v) = get_recent_tasks(State(repo), Query(RecentParams { limit: Some(500) })).await;
+    assert_eq!(v["items"].as_array().unwrap().len(), 100);
+}
@@ -0,0 +1,117 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use chrono::{Duration, Utc};
+use rust_api_hub::handlers::task_handler::{
+    ReminderPayload, RemindersDueQuery, add_reminder, get_due_reminders, get_reminders,
+};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+async fn remind(repo: &TaskRepository, id: &str, at: &str) -> (StatusCode, serde_json::Value) {
+    let (status, Json(v)) = add_reminder(
+        Path(id.to_string()),
+        State(repo.clone()),
+        Json(ReminderPayload { at: at.into() }),
+    )
+    .await;
+    (status, v)
+}
+
+async fn due(repo: &TaskRepository, before: Option<String>) -> Vec<String> {
+    let (status, Json(v)) = get_due_reminders(
+        State(repo.clone()),
+        HeaderMap::new(),
+        Query(RemindersDueQuery { before }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    v["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn reminders_are_listed_sorted_without_duplicates() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    let id = t.id.to_string();
+    for at in [
+        "2030-01-02T00:00:00Z",
+        "2030-01-01T00:00:00Z",
+        "2030-01-02T00:00:00Z",
+    ] {
+        let (status, _) = remind(&repo, &id, at).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (status, Json(v)) = get_reminders(Path(id), State(repo)).await;
+    assert_eq!(status, StatusCode::OK);
+    let got: Vec<chrono::DateTime<Utc>> = serde_json::from_value(v["reminders"].clone()).unwrap();
+    assert_eq!(
+        got,
+        vec![
+            "2030-01-01T00:00:00Z"
+                .parse::<chrono::DateTime<Utc>>()
+                .unwrap(),
+            "2030-01-02T00:00:00Z".parse().unwrap(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn due_query_returns_only_past_due_tasks() {
+    let repo = TaskRepository::new();
+    let now = Utc::now();
+    let past = repo.insert(Task::new_full("past", "d")).unwrap();
+    let future = repo.insert(Task::new_full("future", "d")).unwrap();
+    let mixed = repo.insert(Task::new_full("mixed", "d")).unwrap();
+    repo.insert(Task::new_full("none", "d")).unwrap();
+
+    let hour_ago = (now - Duration::hours(1)).to_rfc3339();
+    let day_ago = (now - Duration::days(1)).to_rfc3339();
+    let tomorrow = (now + Duration::days(1)).to_rfc3339();
+    remind(&repo, &past.id.to_string(), &hour_ago).await;
+    remind(&repo, &future.id.to_string(), &tomorrow).await;
+    remind(&repo, &mixed.id.to_string(), &tomorrow).await;
+    remind(&repo, &mixed.id.to_string(), &day_ago).await;
+
+    // default cutoff is now; earliest reminder first
+    assert_eq!(
+        due(&repo, None).await,
+        vec![mixed.id.to_string(), past.id.to_string()]
+    );
+    // an explicit cutoff between the two past reminders
+    let cutoff = (now - Duration::hours(2)).to_rfc3339();
+    assert_eq!(due(&repo, Some(cutoff)).await, vec![mixed.id.to_string()]);
+}
+
+#[tokio::test]
+async fn invalid_timestamps_and_ids_are_rejected() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    let (status, v) = remind(&repo, &t.id.to_string(), "tomorrow").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(v["error"].as_str().unwrap().contains("timestamp"));
+    assert!(repo.get(&t.id).unwrap().reminders.is_empty());
+
+    let (status, _) = remind(
+        &repo,
+        &uuid::Uuid::new_v4().to_string(),
+        "2030-01-01T00:00:00Z",
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = get_due_reminders(
+        State(repo),
+        HeaderMap::new(),
+        Query(RemindersDueQuery {
+            before: Some("nope".into()),
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,88 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{RemoveMatchingPayload, remove_matching_tags};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn tagged(repo: &TaskRepository, tags: &[&str]) -> Task {
+    let mut t = Task::new_full("t", "d");
+    t.tags = tags.iter().map(|s| s.to_string()).collect();
+    repo.insert(t).unwrap()
+}
+
+async fn remove(
+    repo: &TaskRepository,
+    payload: RemoveMatchingPayload,
+) -> (StatusCode, serde_json::Value) {
+    let (status, Json(v)) = remove_matching_tags(State(repo.clone()), Json(payload)).await;
+    (status, v)
+}
+
+#[tokio::test]
+async fn prefix_strips_only_matching_tags() {
+    let repo = TaskRepository::new();
+    let a = tagged(&repo, &["tmp/one", "tmp/two", "keep"]);
+    let b = tagged(&repo, &["TMP/x", "other"]);
+    let c = tagged(&repo, &["keep", "not-tmp/"]);
+
+    let (status, v) = remove(
+        &repo,
+        RemoveMatchingPayload {
+            prefix: Some("tmp/".into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["updated"], 2);
+    assert_eq!(v["removed_instances"], 3);
+
+    assert_eq!(repo.get(&a.id).unwrap().tags, vec!["keep"]);
+    assert_eq!(repo.get(&b.id).unwrap().tags, vec!["other"]);
+    let c_after = repo.get(&c.id).unwrap();
+    assert_eq!(c_after.tags, vec!["keep", "not-tmp/"]);
+    // untouched tasks keep their timestamp
+    assert_eq!(c_after.updated_at, c.updated_at);
+    assert!(repo.list_by_tag("tmp/one").is_empty());
+}
+
+#[tokio::test]
+async fn glob_matches_anywhere_in_the_tag() {
+    let repo = TaskRepository::new();
+    let a = tagged(&repo, &["q1-draft", "q2-final", "draft"]);
+
+    let (status, v) = remove(
+        &repo,
+        RemoveMatchingPayload {
+            glob: Some("q?-*".into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["updated"], 1);
+    assert_eq!(v["removed_instances"], 2);
+    assert_eq!(repo.get(&a.id).unwrap().tags, vec!["draft"]);
+}
+
+#[tokio::test]
+async fn requires_exactly_one_pattern() {
+    let repo = TaskRepository::new();
+    tagged(&repo, &["tmp/one"]);
+    for payload in [
+        RemoveMatchingPayload::default(),
+        RemoveMatchingPayload {
+            prefix: Some("tmp/".into()),
+            glob: Some("tmp/*".into()),
+        },
+        RemoveMatchingPayload {
+            prefix: Some("  ".into()),
+            ..Default::default()
+        },
+    ] {
+        let (status, _) = remove(&repo, payload).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+    assert_eq!(repo.list_by_tag("tmp/one").len(), 1);
+}
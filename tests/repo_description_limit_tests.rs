@@ -0,0 +1,122 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use rust_api_hub::config::{AppConfig, OverflowPolicy};
+use rust_api_hub::handlers::task_handler::{patch_task, update_task};
+use rust_api_hub::models::repository::{RepoError, TaskRepository};
+use rust_api_hub::models::task::{Task, TaskCreate, TaskUpdate};
+use serde_json::json;
+
+fn repo(policy: OverflowPolicy) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        repo_max_description: Some(5),
+        description_overflow: policy,
+        ..Default::default()
+    })
+}
+
+fn create(description: &str) -> TaskCreate {
+    TaskCreate {
+        title: "t".into(),
+        description: description.into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn reject_policy_returns_an_error_and_stores_nothing() {
+    let repo = repo(OverflowPolicy::Reject);
+    let err = repo.insert(Task::new_full("t", "too long")).unwrap_err();
+    assert_eq!(err, RepoError::DescriptionTooLong { max: 5 });
+    assert_eq!(err.to_string(), "description too long (max 5 chars)");
+
+    let err = repo
+        .insert_many(&[create("ok"), create("way too long")])
+        .unwrap_err();
+    assert_eq!(err, RepoError::DescriptionTooLong { max: 5 });
+    assert_eq!(repo.count(), 0);
+
+    let t = repo.insert(Task::new_full("t", "fine")).unwrap();
+    let upd = TaskUpdate {
+        description: Some("much too long".into()),
+        ..Default::default()
+    };
+    assert!(repo.update(&t.id, upd).is_err());
+    assert_eq!(repo.get(&t.id).unwrap().description, "fine");
+}
+
+#[test]
+fn truncate_policy_clips_stored_descriptions() {
+    let repo = repo(OverflowPolicy::Truncate);
+    let t = repo.insert(Task::new_full("t", "héllo world")).unwrap();
+    assert_eq!(t.description, "héllo");
+    assert_eq!(repo.get(&t.id).unwrap().description, "héllo");
+
+    let created = repo.insert_many(&[create("abcdefgh")]).unwrap();
+    assert_eq!(created[0].description, "abcde");
+
+    let upd = TaskUpdate {
+        description: Some("123456789".into()),
+        ..Default::default()
+    };
+    let updated = repo.update(&t.id, upd).unwrap().unwrap();
+    assert_eq!(updated.description, "12345");
+}
+
+#[test]
+fn default_is_unbounded() {
+    let repo = TaskRepository::new();
+    let long = "x".repeat(50_000);
+    assert_eq!(
+        repo.insert(Task::new_full("t", &long)).unwrap().description,
+        long
+    );
+}
+
+async fn patch_description(repo: &TaskRepository, task: &Task, value: &str) -> (StatusCode, Task) {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json-patch+json"),
+    );
+    let ops = json!([{"op": "replace", "path": "/description", "value": value}]);
+    let (code, _) = patch_task(
+        Path(task.id.to_string()),
+        State(repo.clone()),
+        headers,
+        Bytes::from(ops.to_string()),
+    )
+    .await;
+    (code, repo.get(&task.id).unwrap())
+}
+
+#[tokio::test]
+async fn patch_is_held_to_the_limit() {
+    let repo = repo(OverflowPolicy::Reject);
+    let t = repo.insert(Task::new_full("t", "fine")).unwrap();
+    let (code, stored) = patch_description(&repo, &t, "much too long").await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(stored.description, "fine");
+
+    let repo = self::repo(OverflowPolicy::Truncate);
+    let t = repo.insert(Task::new_full("t", "fine")).unwrap();
+    let (code, stored) = patch_description(&repo, &t, "123456789").await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(stored.description, "12345");
+}
+
+#[tokio::test]
+async fn put_is_held_to_the_limit() {
+    let repo = repo(OverflowPolicy::Reject);
+    let t = repo.insert(Task::new_full("t", "fine")).unwrap();
+    let upd = TaskUpdate {
+        description: Some("much too long".into()),
+        ..Default::default()
+    };
+    let (code, Json(err)) =
+        update_task(Path(t.id.to_string()), State(repo.clone()), Json(upd)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(err["error"], "description too long (max 5 chars)");
+    assert_eq!(repo.get(&t.id).unwrap().description, "fine");
+}
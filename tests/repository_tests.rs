@@ -7,7 +7,7 @@ fn repo_insert_and_get() {
     let repo = TaskRepository::new();
     let t = Task::new_full("a", "b");
     let id = t.id;
-    repo.insert(t.clone());
+    repo.insert(t.clone()).unwrap();
     let got = repo.get(&id).expect("should exist");
     assert_eq!(got.title, "a");
 }
@@ -17,8 +17,8 @@ fn repo_list_and_remove() {
     let repo = TaskRepository::new();
     let t1 = Task::new_full("1", "1");
     let t2 = Task::new_full("2", "2");
-    repo.insert(t1.clone());
-    repo.insert(t2.clone());
+    repo.insert(t1.clone()).unwrap();
+    repo.insert(t2.clone()).unwrap();
     let l = repo.list();
     assert_eq!(l.len(), 2);
     assert!(repo.remove(&t1.id));
@@ -31,13 +31,14 @@ fn repo_update_works() {
     let repo = TaskRepository::new();
     let t = Task::new_full("x", "y");
     let id = t.id;
-    repo.insert(t.clone());
+    repo.insert(t.clone()).unwrap();
     let upd = rust_api_hub::models::task::TaskUpdate {
         title: Some("Z".to_string()),
         description: None,
         completed: Some(true),
+        color: None,
     };
-    let res = repo.update(&id, upd);
+    let res = repo.update(&id, upd).unwrap();
     assert!(res.is_some());
     let got = repo.get(&id).unwrap();
     assert_eq!(got.title, "Z");
@@ -47,13 +48,73 @@ fn repo_update_works() {
 #[test]
 fn repo_nonexistent_update_none() {
     let repo = TaskRepository::new();
-    let res = repo.update(
-        &Uuid::new_v4(),
-        rust_api_hub::models::task::TaskUpdate {
-            title: None,
-            description: None,
-            completed: None,
-        },
-    );
+    let res = repo
+        .update(
+            &Uuid::new_v4(),
+            rust_api_hub::models::task::TaskUpdate {
+                title: None,
+                description: None,
+                completed: None,
+                color: None,
+            },
+        )
+        .unwrap();
     assert!(res.is_none());
 }
+
+#[test]
+fn repo_insert_many_large_batch() {
+    let repo = TaskRepository::new();
+    let creates: Vec<rust_api_hub::models::task::TaskCreate> = (0..5000)
+        .map(|i| rust_api_hub::models::task::TaskCreate {
+            title: format!("t{}", i),
+            description: "d".into(),
+            ..Default::default()
+        })
+        .collect();
+    let created = repo.insert_many(&creates).unwrap();
+    assert_eq!(created.len(), 5000);
+    assert_eq!(repo.count(), 5000);
+    // returned tasks preserve input order and are all stored
+    for (i, t) in created.iter().enumerate() {
+        assert_eq!(t.title, format!("t{}", i));
+        assert_eq!(repo.get(&t.id).unwrap(), *t);
+    }
+}
+
+#[test]
+fn repo_modify_missing_returns_none() {
+    let repo = TaskRepository::new();
+    assert!(repo.modify(&Uuid::new_v4(), |t| t.title.clear()).is_none());
+}
+
+#[test]
+fn repo_modify_applies_and_bumps_updated_at() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    let got = repo.modify(&t.id, |t| t.title = "z".into()).unwrap();
+    assert_eq!(got.title, "z");
+    assert!(got.updated_at > t.updated_at);
+    assert_eq!(repo.get(&t.id).unwrap(), got);
+}
+
+#[test]
+fn repo_modify_concurrent_updates_are_not_lost() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("counter", "")).unwrap();
+    let handles: Vec<_> = (0..16)
+        .map(|_| {
+            let repo = repo.clone();
+            std::thread::spawn(move || {
+                for _ in 0..50 {
+                    repo.modify(&t.id, |t| t.description.push('x')).unwrap();
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+    assert_eq!(repo.get(&t.id).unwrap().description.len(), 16 * 50);
+}
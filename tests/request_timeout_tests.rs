@@ -0,0 +1,46 @@
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::middleware;
+use axum::routing::get;
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::models::repository::TaskRepository;
+use tower::ServiceExt;
+
+fn app(timeout_ms: u64) -> Router {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        request_timeout_ms: timeout_ms,
+        ..Default::default()
+    });
+    Router::new()
+        .route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                "done"
+            }),
+        )
+        .route("/fast", get(|| async { "ok" }))
+        .layer(middleware::from_fn_with_state(
+            repo,
+            rust_api_hub::middleware::request_timeout,
+        ))
+}
+
+async fn status(app: Router, path: &str) -> StatusCode {
+    app.oneshot(Request::get(path).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn slow_handler_times_out_and_fast_one_succeeds() {
+    assert_eq!(status(app(50), "/slow").await, StatusCode::REQUEST_TIMEOUT);
+    assert_eq!(status(app(50), "/fast").await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn zero_disables_the_timeout() {
+    assert_eq!(status(app(0), "/slow").await, StatusCode::OK);
+}
@@ -0,0 +1,87 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{ResetCompletedFilter, reset_completed};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskUpdate};
+
+fn add(repo: &TaskRepository, tags: &[&str], completed: bool) -> Task {
+    let mut t = Task::new_full("t", "d");
+    t.tags = tags.iter().map(|s| s.to_string()).collect();
+    let t = repo.insert(t).unwrap();
+    repo.update(
+        &t.id,
+        TaskUpdate {
+            completed: Some(completed),
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn resets_only_completed_tasks_tagged_test() {
+    let repo = TaskRepository::new();
+    let tagged = [
+        add(&repo, &["test"], true),
+        add(&repo, &["test", "x"], true),
+    ];
+    let other = add(&repo, &["prod"], true);
+    let open = add(&repo, &["test"], false);
+    assert!(tagged[0].completed_at.is_some());
+
+    let (code, Json(resp)) = reset_completed(
+        State(repo.clone()),
+        Json(ResetCompletedFilter {
+            tag: Some("test".into()),
+            ..Default::default()
+        }),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["reset"], 2);
+
+    for t in &tagged {
+        let stored = repo.get(&t.id).unwrap();
+        assert!(!stored.completed);
+        assert!(stored.completed_at.is_none());
+    }
+    assert!(repo.get(&other.id).unwrap().completed);
+    assert!(!repo.get(&open.id).unwrap().completed);
+}
+
+#[tokio::test]
+async fn before_matches_on_created_at() {
+    let repo = TaskRepository::new();
+    let t = add(&repo, &[], true);
+    let cutoff = t.created_at.to_rfc3339();
+    let (code, Json(resp)) = reset_completed(
+        State(repo.clone()),
+        Json(ResetCompletedFilter {
+            before: Some(cutoff),
+            ..Default::default()
+        }),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["reset"], 0);
+}
+
+#[tokio::test]
+async fn empty_or_invalid_filter_is_rejected() {
+    let repo = TaskRepository::new();
+    let t = add(&repo, &["test"], true);
+    let (code, _) = reset_completed(State(repo.clone()), Json(Default::default())).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    let (code, _) = reset_completed(
+        State(repo.clone()),
+        Json(ResetCompletedFilter {
+            before: Some("yesterday".into()),
+            ..Default::default()
+        }),
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert!(repo.get(&t.id).unwrap().completed);
+}
@@ -1,11 +1,12 @@
 use axum::body::Body;
 use axum::http::Request;
+use rust_api_hub::models::repository::TaskRepository;
 use rust_api_hub::routes::tasks::routes;
 use tower::ServiceExt; // oneshot
 
 #[tokio::test]
 async fn test_health_ok() {
-    let app = routes();
+    let app = routes(TaskRepository::new());
     let req = Request::builder()
         .uri("/health")
         .body(Body::empty())
@@ -16,8 +17,23 @@ async fn test_health_ok() {
 
 #[tokio::test]
 async fn test_info_ok() {
-    let app = routes();
+    let app = routes(TaskRepository::new());
     let req = Request::builder().uri("/info").body(Body::empty()).unwrap();
     let resp = app.oneshot(req).await.unwrap();
     assert_eq!(resp.status(), 200);
 }
+
+#[tokio::test]
+async fn info_reports_the_given_repository() {
+    let repo = TaskRepository::new().with_config(rust_api_hub::config::AppConfig {
+        read_only: true,
+        ..Default::default()
+    });
+    let req = Request::builder().uri("/info").body(Body::empty()).unwrap();
+    let resp = routes(repo).oneshot(req).await.unwrap();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(v["features"]["read_only"], true);
+}
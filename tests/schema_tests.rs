@@ -0,0 +1,50 @@
+use axum::Json;
+use rust_api_hub::handlers::task_handler::get_schema;
+use rust_api_hub::models::task::Task;
+
+fn field<'a>(schema: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    schema
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["name"] == name)
+        .unwrap_or_else(|| panic!("no field {}", name))
+}
+
+#[tokio::test]
+async fn title_is_required_and_priority_lists_its_values() {
+    let Json(schema) = get_schema().await;
+    let title = field(&schema, "title");
+    assert_eq!(title["type"], "string");
+    assert_eq!(title["required"], true);
+    assert_eq!(title["mutable"], true);
+
+    let priority = field(&schema, "priority");
+    assert_eq!(priority["type"], "enum");
+    assert_eq!(
+        priority["values"],
+        serde_json::json!(["low", "medium", "high", "critical"])
+    );
+    assert_eq!(priority["required"], false);
+
+    let tags = field(&schema, "tags");
+    assert_eq!(tags["type"], "array");
+    assert_eq!(tags["items"], "string");
+    assert_eq!(field(&schema, "id")["mutable"], false);
+}
+
+#[tokio::test]
+async fn schema_covers_exactly_the_serialized_task_fields() {
+    let Json(schema) = get_schema().await;
+    let mut names: Vec<String> = schema
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["name"].as_str().unwrap().to_string())
+        .collect();
+    let task = serde_json::to_value(Task::new_full("t", "d")).unwrap();
+    let mut keys: Vec<String> = task.as_object().unwrap().keys().cloned().collect();
+    names.sort();
+    keys.sort();
+    assert_eq!(names, keys);
+}
@@ -0,0 +1,146 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{SearchParams, search_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn seeded() -> (TaskRepository, Task) {
+    let repo = TaskRepository::new();
+    let target = repo
+        .insert(Task::new_full("Renew passport", "book an appointment"))
+        .unwrap();
+    repo.insert(Task::new_full("Water plants", "balcony"))
+        .unwrap();
+    repo.insert(Task::new_full("Pay invoices", "march batch"))
+        .unwrap();
+    (repo, target)
+}
+
+async fn search(repo: &TaskRepository, params: SearchParams) -> (StatusCode, serde_json::Value) {
+    let (status, Json(body)) = search_tasks(State(repo.clone()), Query(params)).await;
+    (status, body)
+}
+
+#[tokio::test]
+async fn misspelled_fuzzy_query_finds_the_task_with_a_score() {
+    let (repo, target) = seeded();
+    let (status, body) = search(
+        &repo,
+        SearchParams {
+            q: Some("pasport renw".into()),
+            fuzzy: true,
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], target.id.to_string());
+    let score = items[0]["score"].as_f64().unwrap();
+    assert!(score > 0.3 && score < 1.0);
+}
+
+#[tokio::test]
+async fn fuzzy_results_are_sorted_by_score_and_respect_threshold() {
+    let (repo, _) = seeded();
+    repo.insert(Task::new_full("Renew passport photos", ""))
+        .unwrap();
+    let (_, body) = search(
+        &repo,
+        SearchParams {
+            q: Some("renew passport".into()),
+            fuzzy: true,
+            threshold: Some(0.1),
+            ..Default::default()
+        },
+    )
+    .await;
+    let items = body["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["title"], "Renew passport");
+    assert!(items[0]["score"].as_f64() > items[1]["score"].as_f64());
+
+    let (_, body) = search(
+        &repo,
+        SearchParams {
+            q: Some("renew passport".into()),
+            fuzzy: true,
+            threshold: Some(1.0),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(body["items"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn default_search_is_substring_without_scores() {
+    let (repo, target) = seeded();
+    let (_, body) = search(
+        &repo,
+        SearchParams {
+            q: Some("APPOINT".into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    let items = body["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], target.id.to_string());
+    assert!(items[0].get("score").is_none());
+
+    // a typo finds nothing without fuzzy
+    let (_, body) = search(
+        &repo,
+        SearchParams {
+            q: Some("pasport".into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(body["items"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn missing_query_or_bad_threshold_is_rejected() {
+    let (repo, _) = seeded();
+    let (status, _) = search(&repo, SearchParams::default()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = search(
+        &repo,
+        SearchParams {
+            q: Some("x".into()),
+            fuzzy: true,
+            threshold: Some(1.5),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn limit_caps_the_items_returned() {
+    let repo = TaskRepository::new();
+    for i in 0..120 {
+        repo.insert(Task::new_full(&format!("report {}", i), "d"))
+            .unwrap();
+    }
+    let params = |fuzzy, limit| SearchParams {
+        q: Some("report".into()),
+        fuzzy,
+        limit,
+        ..Default::default()
+    };
+    for fuzzy in [false, true] {
+        let (_, body) = search(&repo, params(fuzzy, Some(5))).await;
+        assert_eq!(body["items"].as_array().unwrap().len(), 5);
+        // the default and any larger limit stop at MAX_PER_PAGE
+        let (_, body) = search(&repo, params(fuzzy, None)).await;
+        assert_eq!(body["items"].as_array().unwrap().len(), 100);
+        let (_, body) = search(&repo, params(fuzzy, Some(1000))).await;
+        assert_eq!(body["items"].as_array().unwrap().len(), 100);
+    }
+}
@@ -0,0 +1,50 @@
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::seed::{seed_from_file, seed_from_str};
+use std::io::Write;
+
+#[test]
+fn seed_from_json_array_inserts_tasks() {
+    let repo = TaskRepository::new();
+    let json = r#"[
+        {"title": "demo one", "description": "first"},
+        {"title": "demo two", "description": "second"}
+    ]"#;
+    let tasks = seed_from_str(&repo, json).unwrap();
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(repo.count(), 2);
+    let mut titles: Vec<String> = repo.list().into_iter().map(|t| t.title).collect();
+    titles.sort();
+    assert_eq!(titles, vec!["demo one", "demo two"]);
+}
+
+#[test]
+fn seed_rejects_malformed_json_without_inserting() {
+    let repo = TaskRepository::new();
+    assert!(seed_from_str(&repo, "{not json").is_err());
+    assert_eq!(repo.count(), 0);
+}
+
+#[test]
+fn seed_rejects_invalid_task_without_inserting() {
+    let repo = TaskRepository::new();
+    let json = r#"[{"title": "ok", "description": ""}, {"title": "  ", "description": ""}]"#;
+    let err = seed_from_str(&repo, json).unwrap_err();
+    assert!(err.contains("index 1"));
+    assert_eq!(repo.count(), 0);
+}
+
+#[test]
+fn seed_from_file_loads_and_tolerates_missing_file() {
+    let path = std::env::temp_dir().join(format!("seed-{}.json", uuid::Uuid::new_v4()));
+    let mut file = std::fs::File::create(&path).unwrap();
+    write!(file, r#"[{{"title": "from file", "description": ""}}]"#).unwrap();
+
+    let repo = TaskRepository::new();
+    seed_from_file(&repo, path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(repo.count(), 1);
+
+    let empty = TaskRepository::new();
+    seed_from_file(&empty, "/definitely/not/here.json");
+    assert_eq!(empty.count(), 0);
+}
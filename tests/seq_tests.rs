@@ -0,0 +1,62 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{create_task, get_task_by_seq};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskCreate};
+
+async fn create(repo: &TaskRepository, title: &str) -> Task {
+    let payload = TaskCreate {
+        title: title.into(),
+        description: "d".into(),
+        ..Default::default()
+    };
+    let (_, Json(t)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap();
+    t
+}
+
+#[tokio::test]
+async fn seq_increments_per_create() {
+    let repo = TaskRepository::new();
+    let a = create(&repo, "a").await;
+    let b = create(&repo, "b").await;
+    let c = create(&repo, "c").await;
+    assert_eq!((a.seq, b.seq, c.seq), (1, 2, 3));
+}
+
+#[tokio::test]
+async fn lookup_by_seq_returns_matching_task() {
+    let repo = TaskRepository::new();
+    let _ = create(&repo, "a").await;
+    let b = create(&repo, "b").await;
+
+    assert_eq!(repo.get_by_seq(b.seq).unwrap().id, b.id);
+    let (code, Json(resp)) = get_task_by_seq(Path("2".into()), State(repo.clone())).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["task"]["id"], b.id.to_string());
+    assert_eq!(resp["task"]["seq"], 2);
+
+    let (code, _) = get_task_by_seq(Path("99".into()), State(repo.clone())).await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+    let (code, _) = get_task_by_seq(Path("abc".into()), State(repo.clone())).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn seq_survives_updates_and_is_not_reused_after_delete() {
+    let repo = TaskRepository::new();
+    let a = repo.insert(Task::new_full("a", "d")).unwrap();
+    repo.modify(&a.id, |t| t.title = "renamed".into());
+    assert_eq!(repo.get_by_seq(a.seq).unwrap().title, "renamed");
+
+    assert!(repo.remove(&a.id));
+    assert!(repo.get_by_seq(a.seq).is_none());
+    let b = repo.insert(Task::new_full("b", "d")).unwrap();
+    assert_eq!(b.seq, a.seq + 1);
+}
@@ -0,0 +1,89 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use chrono::{Duration, Utc};
+use rust_api_hub::config::SlaPolicy;
+use rust_api_hub::handlers::task_handler::{ListParams, get_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, Task};
+
+fn aged(title: &str, priority: Priority, age: Duration, completed: bool) -> Task {
+    let mut t = Task::new_full(title, "d");
+    t.priority = priority;
+    t.created_at = Utc::now() - age;
+    t.completed = completed;
+    t
+}
+
+async fn breached_by_title(repo: &TaskRepository) -> Vec<(String, serde_json::Value)> {
+    let params = ListParams {
+        with_sla: Some(true),
+        ..Default::default()
+    };
+    let Json(resp) = get_tasks(State(repo.clone()), HeaderMap::new(), Query(params))
+        .await
+        .unwrap();
+    let mut out: Vec<(String, serde_json::Value)> = resp["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| {
+            (
+                t["title"].as_str().unwrap().to_string(),
+                t["sla_breached"].clone(),
+            )
+        })
+        .collect();
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
+}
+
+#[tokio::test]
+async fn day_old_open_critical_task_is_flagged() {
+    let repo = TaskRepository::new();
+    let age = Duration::hours(25);
+    repo.insert(aged("open", Priority::Critical, age, false))
+        .unwrap();
+    repo.insert(aged("done", Priority::Critical, age, true))
+        .unwrap();
+    repo.insert(aged("fresh", Priority::Critical, Duration::hours(1), false))
+        .unwrap();
+    repo.insert(aged("medium", Priority::Medium, Duration::days(30), false))
+        .unwrap();
+
+    let got = breached_by_title(&repo).await;
+    assert_eq!(
+        got,
+        vec![
+            ("done".to_string(), serde_json::json!(false)),
+            ("fresh".to_string(), serde_json::json!(false)),
+            ("medium".to_string(), serde_json::json!(false)),
+            ("open".to_string(), serde_json::json!(true)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn items_are_not_annotated_by_default() {
+    let repo = TaskRepository::new();
+    repo.insert(aged("open", Priority::Critical, Duration::days(2), false))
+        .unwrap();
+    let Json(resp) = get_tasks(
+        State(repo.clone()),
+        HeaderMap::new(),
+        Query(ListParams::default()),
+    )
+    .await
+    .unwrap();
+    assert!(resp["items"][0].get("sla_breached").is_none());
+}
+
+#[test]
+fn policy_parses_from_env_format() {
+    let policy: SlaPolicy = "critical=12h, low=30d".parse().unwrap();
+    assert_eq!(policy.critical, Some(Duration::hours(12)));
+    assert_eq!(policy.high, None);
+    assert_eq!(policy.low, Some(Duration::days(30)));
+    assert!("critical=soon".parse::<SlaPolicy>().is_err());
+    assert!("urgent=1d".parse::<SlaPolicy>().is_err());
+}
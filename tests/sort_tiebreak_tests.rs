@@ -0,0 +1,82 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use chrono::Utc;
+use rust_api_hub::handlers::task_handler::{ListParams, get_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use uuid::Uuid;
+
+/// Insert `n` tasks that all share one `created_at`, returning their ids sorted ascending.
+fn seed_same_timestamp(repo: &TaskRepository, n: usize) -> Vec<Uuid> {
+    let now = Utc::now();
+    let mut ids = Vec::new();
+    for i in 0..n {
+        let mut t = Task::new_full(&format!("t{}", i), "d");
+        t.created_at = now;
+        t.updated_at = now;
+        ids.push(t.id);
+        repo.insert(t).unwrap();
+    }
+    ids.sort();
+    ids
+}
+
+fn ids_of(tasks: &[Task]) -> Vec<Uuid> {
+    tasks.iter().map(|t| t.id).collect()
+}
+
+#[test]
+fn created_at_sort_breaks_ties_by_id() {
+    let repo = TaskRepository::new();
+    let expected = seed_same_timestamp(&repo, 20);
+    assert_eq!(ids_of(&repo.list_sorted_by_created_at(false)), expected);
+    assert_eq!(ids_of(&repo.list_sorted_by_created_at(true)), expected);
+    // repeated calls give the same order
+    assert_eq!(
+        ids_of(&repo.list_sorted_by_created_at(false)),
+        ids_of(&repo.list_sorted_by_created_at(false))
+    );
+}
+
+#[tokio::test]
+async fn paging_over_equal_timestamps_is_stable() {
+    let repo = TaskRepository::new();
+    let expected = seed_same_timestamp(&repo, 25);
+
+    let mut seen = Vec::new();
+    for page in 1..=3 {
+        let params = ListParams {
+            page: Some(page),
+            per_page: Some(10),
+            ..Default::default()
+        };
+        let Json(resp) = get_tasks(State(repo.clone()), HeaderMap::new(), Query(params))
+            .await
+            .unwrap();
+        for item in resp["items"].as_array().unwrap() {
+            seen.push(Uuid::parse_str(item["id"].as_str().unwrap()).unwrap());
+        }
+    }
+    assert_eq!(seen, expected);
+}
+
+#[tokio::test]
+async fn priority_sort_breaks_ties_by_id() {
+    let repo = TaskRepository::new();
+    let expected = seed_same_timestamp(&repo, 10);
+    let params = ListParams {
+        sort: Some("priority:desc".into()),
+        ..Default::default()
+    };
+    let Json(resp) = get_tasks(State(repo.clone()), HeaderMap::new(), Query(params))
+        .await
+        .unwrap();
+    let got: Vec<Uuid> = resp["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| Uuid::parse_str(i["id"].as_str().unwrap()).unwrap())
+        .collect();
+    assert_eq!(got, expected);
+}
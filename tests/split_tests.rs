@@ -0,0 +1,79 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{SplitPayload, split_task};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, Task};
+
+fn parent(repo: &TaskRepository) -> Task {
+    let mut t = Task::new_full("big task", "lots to do");
+    t.tags = vec!["backend".into()];
+    t.priority = Priority::High;
+    repo.insert(t).unwrap()
+}
+
+fn titles(ts: &[&str]) -> Json<SplitPayload> {
+    Json(SplitPayload {
+        titles: ts.iter().map(|s| s.to_string()).collect(),
+    })
+}
+
+#[tokio::test]
+async fn split_creates_children_with_copied_tags_and_priority() {
+    let repo = TaskRepository::new();
+    let p = parent(&repo);
+
+    let (code, Json(resp)) = split_task(
+        Path(p.id.to_string()),
+        State(repo.clone()),
+        titles(&["part 1", "part 2"]),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CREATED);
+    let children: Vec<Task> = serde_json::from_value(resp["children"].clone()).unwrap();
+    assert_eq!(children.len(), 2);
+    assert_eq!(children[0].title, "part 1");
+    for c in children.iter() {
+        let stored = repo.get(&c.id).unwrap();
+        assert_eq!(stored.tags, vec!["backend"]);
+        assert_eq!(stored.priority, Priority::High);
+    }
+
+    // the parent stays and links its children in order
+    let stored_parent = repo.get(&p.id).unwrap();
+    let child_ids: Vec<_> = children.iter().map(|c| c.id).collect();
+    assert_eq!(stored_parent.subtask_ids, child_ids);
+    assert_eq!(resp["parent"]["subtask_ids"], serde_json::json!(child_ids));
+    assert_eq!(repo.count(), 3);
+    assert_eq!(repo.list_by_tag("backend").len(), 3);
+}
+
+#[tokio::test]
+async fn split_rejects_blank_titles() {
+    let repo = TaskRepository::new();
+    let p = parent(&repo);
+    let (code, _) = split_task(
+        Path(p.id.to_string()),
+        State(repo.clone()),
+        titles(&["ok", "  "]),
+    )
+    .await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    let (code, _) = split_task(Path(p.id.to_string()), State(repo.clone()), titles(&[])).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.count(), 1);
+    assert!(repo.get(&p.id).unwrap().subtask_ids.is_empty());
+}
+
+#[tokio::test]
+async fn split_missing_parent_returns_404() {
+    let repo = TaskRepository::new();
+    let (code, _) = split_task(
+        Path(uuid::Uuid::new_v4().to_string()),
+        State(repo.clone()),
+        titles(&["a"]),
+    )
+    .await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+    assert_eq!(repo.count(), 0);
+}
@@ -0,0 +1,81 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::HeaderMap;
+use rust_api_hub::handlers::task_handler::{TagsPayload, get_stats, set_tags};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskUpdate};
+
+#[tokio::test]
+async fn repeated_stats_reuse_the_cache() {
+    let repo = TaskRepository::new();
+    repo.insert(Task::new_full("a", "b")).unwrap();
+
+    let Json(first) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+    let Json(second) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+    let Json(third) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+    assert_eq!(repo.stats_computations(), 1);
+    assert_eq!(first, second);
+    assert_eq!(second, third);
+}
+
+#[tokio::test]
+async fn mutations_force_a_recompute() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    let Json(stats) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+    assert_eq!(stats["total"], 1);
+
+    repo.insert(Task::new_full("c", "d")).unwrap();
+    let Json(stats) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+    assert_eq!(stats["total"], 2);
+    assert_eq!(repo.stats_computations(), 2);
+
+    repo.update(
+        &t.id,
+        TaskUpdate {
+            completed: Some(true),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let Json(stats) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+    assert_eq!(stats["completed"], 1);
+    assert_eq!(repo.stats_computations(), 3);
+
+    let payload = TagsPayload {
+        tags: vec!["x".into()],
+    };
+    let _ = set_tags(
+        Path(t.id.to_string()),
+        State(repo.clone()),
+        axum::extract::Query(Default::default()),
+        Json(payload),
+    )
+    .await;
+    let Json(stats) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+    assert_eq!(stats["tag_distribution"][0]["tag"], "x");
+    assert_eq!(repo.stats_computations(), 4);
+
+    repo.remove(&t.id);
+    let Json(stats) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+    assert_eq!(stats["total"], 1);
+    assert_eq!(repo.stats_computations(), 5);
+}
+
+#[test]
+fn version_changes_on_every_mutation() {
+    let repo = TaskRepository::new();
+    let v0 = repo.version();
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    let v1 = repo.version();
+    assert!(v1 > v0);
+    repo.modify(&t.id, |t| t.title = "z".into());
+    let v2 = repo.version();
+    assert!(v2 > v1);
+    repo.remove(&t.id);
+    assert!(repo.version() > v2);
+    // reads do not bump the version
+    let v3 = repo.version();
+    let _ = repo.list();
+    assert_eq!(repo.version(), v3);
+}
@@ -1,5 +1,9 @@
+// fixture list kept as written; clippy would rather see an array
+#![allow(clippy::useless_vec)]
+
 use axum::Json;
 use axum::extract::State;
+use axum::http::HeaderMap;
 use rust_api_hub::models::repository::TaskRepository;
 use rust_api_hub::models::task::TaskCreate;
 
@@ -10,7 +14,9 @@ fn repo() -> TaskRepository {
 #[tokio::test]
 async fn stats_empty_repo_returns_zeros() {
     let repo = repo();
-    let Json(resp) = rust_api_hub::handlers::task_handler::get_stats(State(repo.clone())).await;
+    let Json(resp) =
+        rust_api_hub::handlers::task_handler::get_stats(State(repo.clone()), HeaderMap::new())
+            .await;
 
     assert_eq!(resp["total"].as_u64().unwrap(), 0);
     assert_eq!(resp["completed"].as_u64().unwrap(), 0);
@@ -28,10 +34,15 @@ async fn stats_mixed_completed_counts_correct() {
         let payload = TaskCreate {
             title: format!("task{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (_code, Json(task)) =
-            rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload))
-                .await;
+        let (_code, Json(task)) = rust_api_hub::handlers::task_handler::create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
 
         if i < 3 {
             // mark first 3 as completed
@@ -39,6 +50,7 @@ async fn stats_mixed_completed_counts_correct() {
                 title: None,
                 description: None,
                 completed: Some(true),
+                color: None,
             };
             let _ = rust_api_hub::handlers::task_handler::update_task(
                 axum::extract::Path(task.id.to_string()),
@@ -49,7 +61,9 @@ async fn stats_mixed_completed_counts_correct() {
         }
     }
 
-    let Json(resp) = rust_api_hub::handlers::task_handler::get_stats(State(repo.clone())).await;
+    let Json(resp) =
+        rust_api_hub::handlers::task_handler::get_stats(State(repo.clone()), HeaderMap::new())
+            .await;
 
     assert_eq!(resp["total"].as_u64().unwrap(), 5);
     assert_eq!(resp["completed"].as_u64().unwrap(), 3);
@@ -74,10 +88,15 @@ async fn stats_tag_distribution_sorted_by_frequency() {
         let payload = TaskCreate {
             title: format!("t{}", i),
             description: "d".into(),
+            ..Default::default()
         };
-        let (_code, Json(task)) =
-            rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload))
-                .await;
+        let (_code, Json(task)) = rust_api_hub::handlers::task_handler::create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
 
         // set tags
         let tags_payload = rust_api_hub::handlers::task_handler::TagsPayload {
@@ -86,12 +105,15 @@ async fn stats_tag_distribution_sorted_by_frequency() {
         let _ = rust_api_hub::handlers::task_handler::set_tags(
             axum::extract::Path(task.id.to_string()),
             State(repo.clone()),
+            axum::extract::Query(Default::default()),
             Json(tags_payload),
         )
         .await;
     }
 
-    let Json(resp) = rust_api_hub::handlers::task_handler::get_stats(State(repo.clone())).await;
+    let Json(resp) =
+        rust_api_hub::handlers::task_handler::get_stats(State(repo.clone()), HeaderMap::new())
+            .await;
 
     let dist = resp["tag_distribution"].as_array().unwrap();
     assert_eq!(dist.len(), 3); // a, b, c
@@ -0,0 +1,83 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{StatusPayload, set_status, update_task};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Status, Task, TaskUpdate};
+
+fn repo_with_task() -> (TaskRepository, Task) {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("s", "d")).unwrap();
+    (repo, t)
+}
+
+async fn put_status(
+    repo: &TaskRepository,
+    id: &str,
+    status: &str,
+) -> (StatusCode, serde_json::Value) {
+    let (code, Json(v)) = set_status(
+        Path(id.to_string()),
+        State(repo.clone()),
+        Json(StatusPayload {
+            status: status.into(),
+        }),
+    )
+    .await;
+    (code, v)
+}
+
+#[tokio::test]
+async fn status_transitions_keep_completed_in_sync() {
+    let (repo, t) = repo_with_task();
+    assert_eq!(t.status, Status::Todo);
+
+    let (code, resp) = put_status(&repo, &t.id.to_string(), "in_progress").await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["task"]["status"].as_str().unwrap(), "in_progress");
+    assert!(!resp["task"]["completed"].as_bool().unwrap());
+
+    let (code, resp) = put_status(&repo, &t.id.to_string(), "Blocked").await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["task"]["status"].as_str().unwrap(), "blocked");
+
+    let (code, resp) = put_status(&repo, &t.id.to_string(), "done").await;
+    assert_eq!(code, StatusCode::OK);
+    assert!(resp["task"]["completed"].as_bool().unwrap());
+    let stored = repo.get(&t.id).unwrap();
+    assert_eq!(stored.status, Status::Done);
+    assert!(stored.completed);
+}
+
+#[tokio::test]
+async fn invalid_status_returns_400_and_missing_task_404() {
+    let (repo, t) = repo_with_task();
+    let (code, resp) = put_status(&repo, &t.id.to_string(), "sleeping").await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert!(resp["error"].as_str().unwrap().contains("invalid status"));
+
+    let (code, _) = put_status(&repo, &uuid::Uuid::new_v4().to_string(), "done").await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn completed_update_moves_status() {
+    let (repo, t) = repo_with_task();
+    let upd = TaskUpdate {
+        title: None,
+        description: None,
+        completed: Some(true),
+        color: None,
+    };
+    let _ = update_task(Path(t.id.to_string()), State(repo.clone()), Json(upd)).await;
+    assert_eq!(repo.get(&t.id).unwrap().status, Status::Done);
+
+    let upd = TaskUpdate {
+        title: None,
+        description: None,
+        completed: Some(false),
+        color: None,
+    };
+    let _ = update_task(Path(t.id.to_string()), State(repo.clone()), Json(upd)).await;
+    assert_eq!(repo.get(&t.id).unwrap().status, Status::Todo);
+}
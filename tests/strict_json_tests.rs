@@ -0,0 +1,95 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt; // oneshot
+
+async fn send(
+    repo: &TaskRepository,
+    method: &str,
+    uri: &str,
+    strict: bool,
+    body: &str,
+) -> (StatusCode, serde_json::Value) {
+    let mut req = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json");
+    if strict {
+        req = req.header("x-strict", "true");
+    }
+    let req = req.body(Body::from(body.to_string())).unwrap();
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    let status = resp.status();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+    )
+}
+
+const TYPO: &str = r#"{"titel": "x", "description": "d", "extra": 1}"#;
+
+#[tokio::test]
+async fn strict_create_rejects_unknown_fields() {
+    let repo = TaskRepository::new();
+    // `title` is missing here, but the unknown keys are reported first
+    let (code, body) = send(&repo, "POST", "/tasks", true, TYPO).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "unknown fields");
+    let mut fields: Vec<&str> = body["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f.as_str().unwrap())
+        .collect();
+    fields.sort();
+    assert_eq!(fields, vec!["extra", "titel"]);
+    assert_eq!(repo.count(), 0);
+}
+
+#[tokio::test]
+async fn lenient_create_ignores_unknown_fields() {
+    let repo = TaskRepository::new();
+    let body = r#"{"title": "x", "description": "d", "extra": 1}"#;
+    let (code, _) = send(&repo, "POST", "/tasks", false, body).await;
+    assert_eq!(code, StatusCode::CREATED);
+    assert_eq!(repo.count(), 1);
+}
+
+#[tokio::test]
+async fn strict_create_accepts_known_fields() {
+    let repo = TaskRepository::new();
+    let body = r#"{"title": "x", "description": "d"}"#;
+    let (code, _) = send(&repo, "POST", "/tasks", true, body).await;
+    assert_eq!(code, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn strict_update_rejects_unknown_fields() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    let uri = format!("/tasks/{}", t.id);
+
+    let (code, body) = send(&repo, "PUT", &uri, true, r#"{"complete": true}"#).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(body["fields"], serde_json::json!(["complete"]));
+    assert!(!repo.get(&t.id).unwrap().completed);
+
+    let (code, _) = send(&repo, "PUT", &uri, false, r#"{"complete": true}"#).await;
+    assert_eq!(code, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn strict_create_over_the_body_limit_is_413() {
+    let repo = TaskRepository::new();
+    let description = "x".repeat(rust_api_hub::middleware::REQUEST_BODY_LIMIT);
+    let body = serde_json::json!({"title": "big", "description": description}).to_string();
+    let (status, resp) = send(&repo, "POST", "/tasks", true, &body).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(resp["error"], "payload too large");
+    assert_eq!(repo.count(), 0);
+}
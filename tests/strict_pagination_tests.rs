@@ -0,0 +1,66 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use rust_api_hub::handlers::task_handler::{ListParams, get_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn repo() -> TaskRepository {
+    let repo = TaskRepository::new();
+    repo.insert(Task::new_full("t", "d")).unwrap();
+    repo
+}
+
+fn params(page: usize, per_page: usize, strict: bool) -> Query<ListParams> {
+    Query(ListParams {
+        page: Some(page),
+        per_page: Some(per_page),
+        strict_pagination: Some(strict),
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn strict_mode_rejects_page_zero() {
+    let repo = repo();
+    let (code, Json(resp)) = get_tasks(State(repo.clone()), HeaderMap::new(), params(0, 10, true))
+        .await
+        .unwrap_err();
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(resp["error"], "page must be at least 1");
+}
+
+#[tokio::test]
+async fn strict_mode_rejects_per_page_over_cap() {
+    let repo = repo();
+    let (code, Json(resp)) =
+        get_tasks(State(repo.clone()), HeaderMap::new(), params(1, 1000, true))
+            .await
+            .unwrap_err();
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(resp["error"], "per_page must be at most 100");
+}
+
+#[tokio::test]
+async fn lenient_mode_clamps() {
+    let repo = repo();
+    let Json(resp) = get_tasks(
+        State(repo.clone()),
+        HeaderMap::new(),
+        params(0, 1000, false),
+    )
+    .await
+    .unwrap();
+    assert_eq!(resp["page"], 1);
+    assert_eq!(resp["per_page"], 100);
+    assert_eq!(resp["total"], 1);
+}
+
+#[tokio::test]
+async fn strict_mode_accepts_valid_bounds() {
+    let repo = repo();
+    let Json(resp) = get_tasks(State(repo.clone()), HeaderMap::new(), params(1, 100, true))
+        .await
+        .unwrap();
+    assert_eq!(resp["per_page"], 100);
+}
@@ -0,0 +1,119 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{
+    FilterUpdate, StatusPayload, UpdateByFilterPayload, UpdateFilter, set_status, update_by_filter,
+    update_task,
+};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskUpdate};
+use uuid::Uuid;
+
+fn repo(strict: bool) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        strict_subtask_completion: strict,
+        ..Default::default()
+    })
+}
+
+/// A parent with two open subtasks; returns (parent id, child ids).
+fn family(repo: &TaskRepository) -> (Uuid, Vec<Uuid>) {
+    let parent = repo.insert(Task::new_full("parent", "d")).unwrap();
+    let (_, children) = repo
+        .split(&parent.id, &["a".to_string(), "b".to_string()])
+        .unwrap();
+    (parent.id, children.iter().map(|c| c.id).collect())
+}
+
+async fn complete(repo: &TaskRepository, id: Uuid) -> (StatusCode, serde_json::Value) {
+    let (code, Json(v)) = update_task(
+        Path(id.to_string()),
+        State(repo.clone()),
+        Json(TaskUpdate {
+            completed: Some(true),
+            ..Default::default()
+        }),
+    )
+    .await;
+    (code, v)
+}
+
+#[tokio::test]
+async fn open_child_blocks_parent_completion() {
+    let repo = repo(true);
+    let (parent, children) = family(&repo);
+    complete(&repo, children[0]).await;
+
+    let (code, v) = complete(&repo, parent).await;
+    assert_eq!(code, StatusCode::CONFLICT);
+    assert_eq!(v["error"], "incomplete subtasks");
+    assert!(!repo.get(&parent).unwrap().completed);
+
+    let (code, _) = set_status(
+        Path(parent.to_string()),
+        State(repo.clone()),
+        Json(StatusPayload {
+            status: "done".into(),
+        }),
+    )
+    .await;
+    assert_eq!(code, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn parent_completes_once_children_are_done() {
+    let repo = repo(true);
+    let (parent, children) = family(&repo);
+    for c in children {
+        assert_eq!(complete(&repo, c).await.0, StatusCode::OK);
+    }
+    let (code, v) = complete(&repo, parent).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(v["task"]["completed"], true);
+}
+
+#[tokio::test]
+async fn rule_disabled_allows_completion_regardless() {
+    let repo = repo(false);
+    let (parent, _) = family(&repo);
+    let (code, _) = complete(&repo, parent).await;
+    assert_eq!(code, StatusCode::OK);
+}
+
+async fn complete_by_filter(repo: &TaskRepository, filter: UpdateFilter) -> StatusCode {
+    let payload = UpdateByFilterPayload {
+        filter,
+        update: FilterUpdate {
+            completed: Some(true),
+            ..Default::default()
+        },
+    };
+    update_by_filter(State(repo.clone()), Json(payload)).await.0
+}
+
+#[tokio::test]
+async fn update_by_filter_respects_open_children() {
+    let repo = repo(true);
+    let (parent, children) = family(&repo);
+    repo.modify(&parent, |t| t.tags = vec!["epic".into()]);
+
+    let only_parent = UpdateFilter {
+        tag: Some("epic".into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        complete_by_filter(&repo, only_parent).await,
+        StatusCode::CONFLICT
+    );
+    assert!(!repo.get(&parent).unwrap().completed);
+
+    // children completed by the same update do not block the parent
+    let everything = UpdateFilter {
+        completed: Some(false),
+        ..Default::default()
+    };
+    assert_eq!(complete_by_filter(&repo, everything).await, StatusCode::OK);
+    assert!(repo.get(&parent).unwrap().completed);
+    assert!(children.iter().all(|c| repo.get(c).unwrap().completed));
+}
@@ -0,0 +1,58 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{SetTagsParams, TagsPayload, set_tags};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+async fn put_tags(
+    repo: &TaskRepository,
+    task: &Task,
+    tags: &[&str],
+    strict: Option<bool>,
+) -> (StatusCode, serde_json::Value) {
+    let (status, Json(v)) = set_tags(
+        Path(task.id.to_string()),
+        State(repo.clone()),
+        Query(SetTagsParams {
+            strict_tags: strict,
+        }),
+        Json(TagsPayload {
+            tags: tags.iter().map(|s| s.to_string()).collect(),
+        }),
+    )
+    .await;
+    (status, v)
+}
+
+#[tokio::test]
+async fn strict_mode_rejects_case_variants() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    for tags in [&["a", "A"][..], &["b", " b "][..]] {
+        let (status, v) = put_tags(&repo, &t, tags, Some(true)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(v["error"], "duplicate tags after normalization");
+    }
+    assert!(repo.get(&t.id).unwrap().tags.is_empty());
+}
+
+#[tokio::test]
+async fn strict_mode_accepts_distinct_tags() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    let (status, v) = put_tags(&repo, &t, &["Feature", "backend"], Some(true)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["task"]["tags"], serde_json::json!(["feature", "backend"]));
+}
+
+#[tokio::test]
+async fn default_mode_dedupes() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    for strict in [None, Some(false)] {
+        let (status, _) = put_tags(&repo, &t, &["a", "A"], strict).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(repo.get(&t.id).unwrap().tags, vec!["a"]);
+    }
+}
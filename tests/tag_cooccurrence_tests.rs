@@ -0,0 +1,49 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use rust_api_hub::handlers::task_handler::{CooccurrenceParams, get_tag_cooccurrence};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use serde_json::json;
+
+fn add(repo: &TaskRepository, tags: &[&str]) {
+    let mut t = Task::new_full("t", "d");
+    t.tags = tags.iter().map(|s| s.to_string()).collect();
+    repo.insert(t).unwrap();
+}
+
+fn repo() -> TaskRepository {
+    let repo = TaskRepository::new();
+    add(&repo, &["backend", "urgent", "api"]);
+    add(&repo, &["backend", "urgent"]);
+    add(&repo, &["urgent", "backend", "ui"]);
+    add(&repo, &["api", "ui"]);
+    add(&repo, &["solo"]);
+    repo
+}
+
+#[tokio::test]
+async fn most_frequent_pair_ranks_first() {
+    let repo = repo();
+    let Json(resp) = get_tag_cooccurrence(State(repo.clone()), Query(Default::default())).await;
+    let pairs = resp["pairs"].as_array().unwrap();
+    assert_eq!(pairs[0], json!({"a": "backend", "b": "urgent", "count": 3}));
+    // every remaining pair occurs once, ordered alphabetically
+    assert_eq!(pairs[1], json!({"a": "api", "b": "backend", "count": 1}));
+    assert_eq!(pairs.len(), 6);
+}
+
+#[tokio::test]
+async fn top_limits_result() {
+    let repo = repo();
+    let params = CooccurrenceParams { top: Some(2) };
+    let Json(resp) = get_tag_cooccurrence(State(repo.clone()), Query(params)).await;
+    assert_eq!(resp["pairs"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn no_pairs_without_multi_tag_tasks() {
+    let repo = TaskRepository::new();
+    add(&repo, &["solo"]);
+    let Json(resp) = get_tag_cooccurrence(State(repo.clone()), Query(Default::default())).await;
+    assert_eq!(resp, json!({"pairs": []}));
+}
@@ -0,0 +1,49 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{MAX_TAGS_PER_TASK, TagsPayload, set_tags};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn huge_tag_array_is_rejected_quickly() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    let tags: Vec<String> = (0..50_000).map(|i| format!("tag-{}", i)).collect();
+
+    let start = Instant::now();
+    let (code, Json(resp)) = set_tags(
+        Path(t.id.to_string()),
+        State(repo.clone()),
+        axum::extract::Query(Default::default()),
+        Json(TagsPayload { tags }),
+    )
+    .await;
+    assert!(start.elapsed() < Duration::from_millis(50));
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        resp["error"],
+        format!("too many tags (max {})", MAX_TAGS_PER_TASK)
+    );
+    assert!(repo.get(&t.id).unwrap().tags.is_empty());
+}
+
+#[tokio::test]
+async fn tag_array_at_limit_is_accepted() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    let tags: Vec<String> = (0..MAX_TAGS_PER_TASK)
+        .map(|i| format!("tag-{}", i))
+        .collect();
+
+    let (code, _) = set_tags(
+        Path(t.id.to_string()),
+        State(repo.clone()),
+        axum::extract::Query(Default::default()),
+        Json(TagsPayload { tags }),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(repo.get(&t.id).unwrap().tags.len(), MAX_TAGS_PER_TASK);
+}
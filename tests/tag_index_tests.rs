@@ -0,0 +1,94 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use rust_api_hub::handlers::task_handler::{TagQuery, TagsPayload, get_tasks_by_tag, set_tags};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Task, TaskUpdate};
+use std::collections::BTreeSet;
+
+fn brute_force(repo: &TaskRepository, tag: &str) -> BTreeSet<String> {
+    repo.list()
+        .into_iter()
+        .filter(|t| t.tags.iter().any(|x| x.eq_ignore_ascii_case(tag)))
+        .map(|t| t.id.to_string())
+        .collect()
+}
+
+async fn indexed(repo: &TaskRepository, tag: &str) -> BTreeSet<String> {
+    let Json(resp) = get_tasks_by_tag(
+        State(repo.clone()),
+        Query(TagQuery {
+            tag: tag.to_string(),
+            contains: None,
+        }),
+    )
+    .await;
+    resp["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn tag(repo: &TaskRepository, id: &uuid::Uuid, tags: &[&str]) {
+    let payload = TagsPayload {
+        tags: tags.iter().map(|s| s.to_string()).collect(),
+    };
+    let _ = set_tags(
+        Path(id.to_string()),
+        State(repo.clone()),
+        axum::extract::Query(Default::default()),
+        Json(payload),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn tag_index_matches_brute_force_after_mutations() {
+    let repo = TaskRepository::new();
+    let ids: Vec<uuid::Uuid> = (0..6)
+        .map(|i| {
+            repo.insert(Task::new_full(&format!("t{}", i), "d"))
+                .unwrap()
+                .id
+        })
+        .collect();
+
+    tag(&repo, &ids[0], &["a", "b"]).await;
+    tag(&repo, &ids[1], &["b", "c"]).await;
+    tag(&repo, &ids[2], &["a"]).await;
+    tag(&repo, &ids[3], &["c"]).await;
+    // retag: drops b, adds c
+    tag(&repo, &ids[0], &["a", "c"]).await;
+    // clear tags
+    tag(&repo, &ids[2], &[]).await;
+    // remove a tagged task
+    repo.remove(&ids[1]);
+    // core-field update keeps tags
+    repo.update(
+        &ids[3],
+        TaskUpdate {
+            title: Some("renamed".into()),
+            description: None,
+            completed: None,
+            color: None,
+        },
+    )
+    .unwrap();
+    // direct overwrite with mixed case tags
+    let mut t = repo.get(&ids[4]).unwrap();
+    t.tags = vec!["B".into()];
+    repo.insert(t).unwrap();
+    repo.remove_many(&[ids[5]]);
+
+    for tag in ["a", "b", "c", "missing"] {
+        assert_eq!(
+            indexed(&repo, tag).await,
+            brute_force(&repo, tag),
+            "tag {}",
+            tag
+        );
+    }
+    assert_eq!(indexed(&repo, "c").await.len(), 2);
+    assert_eq!(indexed(&repo, "b").await.len(), 1);
+}
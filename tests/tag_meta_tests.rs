@@ -0,0 +1,88 @@
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{Path, State};
+use axum::http::{Request, StatusCode};
+use rust_api_hub::handlers::task_handler::{get_tag_meta, set_tag_meta};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::TagMeta;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt; // oneshot
+
+fn meta(color: &str) -> TagMeta {
+    TagMeta {
+        color: Some(color.into()),
+        description: Some("bugs".into()),
+    }
+}
+
+#[tokio::test]
+async fn set_and_get_tag_meta() {
+    let repo = TaskRepository::new();
+    let (code, Json(resp)) = set_tag_meta(
+        Path(" Bug ".to_string()),
+        State(repo.clone()),
+        Json(meta("#ff0000")),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["tag"], "bug");
+
+    // lookup uses the normalized tag, no task needs to carry it
+    let (code, Json(resp)) = get_tag_meta(Path("BUG".to_string()), State(repo.clone())).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["color"], "#ff0000");
+    assert_eq!(resp["description"], "bugs");
+}
+
+#[tokio::test]
+async fn invalid_color_returns_400() {
+    let repo = TaskRepository::new();
+    for bad in ["red", "#ff00", "ff0000", "#gg0000"] {
+        let (code, _) = set_tag_meta(
+            Path("bug".to_string()),
+            State(repo.clone()),
+            Json(meta(bad)),
+        )
+        .await;
+        assert_eq!(code, StatusCode::BAD_REQUEST, "color {}", bad);
+    }
+    assert!(repo.tag_meta("bug").is_none());
+
+    let (code, _) = set_tag_meta(
+        Path("bug".to_string()),
+        State(repo.clone()),
+        Json(meta("#F0a")),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn missing_tag_meta_returns_404() {
+    let repo = TaskRepository::new();
+    let (code, _) = get_tag_meta(Path("nope".to_string()), State(repo.clone())).await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn tag_meta_routes_are_wired() {
+    let repo = TaskRepository::new();
+    let req = Request::put("/tasks/tags/ui/meta")
+        .header("content-type", "application/json")
+        .body(Body::from(r##"{"color": "#00ff00"}"##))
+        .unwrap();
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = Request::get("/tasks/tags/ui/meta")
+        .body(Body::empty())
+        .unwrap();
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(v["color"], "#00ff00");
+    assert!(v["description"].is_null());
+}
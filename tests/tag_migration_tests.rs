@@ -0,0 +1,47 @@
+use axum::Json;
+use axum::extract::State;
+use rust_api_hub::config::{AppConfig, TagNormalization};
+use rust_api_hub::handlers::task_handler::normalize_all_tags;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn add(repo: &TaskRepository, tags: &[&str]) -> Task {
+    let mut t = Task::new_full("t", "d");
+    t.tags = tags.iter().map(|s| s.to_string()).collect();
+    repo.insert(t).unwrap()
+}
+
+#[tokio::test]
+async fn mixed_case_tags_are_normalized_and_only_changes_counted() {
+    let repo = TaskRepository::new();
+    let mixed = add(&repo, &[" Backend", "URGENT", "backend"]);
+    let clean = add(&repo, &["frontend", "ui"]);
+    let untagged = add(&repo, &[]);
+
+    let Json(resp) = normalize_all_tags(State(repo.clone())).await;
+    assert_eq!(resp["updated"], 1);
+
+    let mixed_after = repo.get(&mixed.id).unwrap();
+    assert_eq!(mixed_after.tags, vec!["backend", "urgent"]);
+    assert!(mixed_after.updated_at > mixed.updated_at);
+    assert_eq!(repo.get(&clean.id).unwrap(), clean);
+    assert_eq!(repo.get(&untagged.id).unwrap(), untagged);
+    assert_eq!(repo.list_by_tag("urgent").len(), 1);
+
+    // running again is a no-op
+    let Json(resp) = normalize_all_tags(State(repo.clone())).await;
+    assert_eq!(resp["updated"], 0);
+}
+
+#[tokio::test]
+async fn uses_configured_normalization_mode() {
+    let repo = TaskRepository::new().with_config(AppConfig {
+        tag_normalization: TagNormalization::Kebab,
+        ..Default::default()
+    });
+    let t = add(&repo, &["Front End", "front-end"]);
+
+    let Json(resp) = normalize_all_tags(State(repo.clone())).await;
+    assert_eq!(resp["updated"], 1);
+    assert_eq!(repo.get(&t.id).unwrap().tags, vec!["front-end"]);
+}
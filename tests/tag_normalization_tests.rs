@@ -0,0 +1,102 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use rust_api_hub::config::{AppConfig, TagNormalization};
+use rust_api_hub::handlers::task_handler::{TagQuery, TagsPayload, get_tasks_by_tag, set_tags};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn repo_with(mode: TagNormalization) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        tag_normalization: mode,
+        ..Default::default()
+    })
+}
+
+async fn tags_after_set(repo: &TaskRepository, tags: &[&str]) -> Vec<String> {
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    let payload = TagsPayload {
+        tags: tags.iter().map(|s| s.to_string()).collect(),
+    };
+    let _ = set_tags(
+        Path(t.id.to_string()),
+        State(repo.clone()),
+        axum::extract::Query(Default::default()),
+        Json(payload),
+    )
+    .await;
+    repo.get(&t.id).unwrap().tags
+}
+
+async fn search(repo: &TaskRepository, tag: &str) -> usize {
+    let Json(resp) = get_tasks_by_tag(
+        State(repo.clone()),
+        Query(TagQuery {
+            tag: tag.to_string(),
+            contains: None,
+        }),
+    )
+    .await;
+    resp["total"].as_u64().unwrap() as usize
+}
+
+#[tokio::test]
+async fn kebab_mode_joins_words_with_hyphens() {
+    let repo = repo_with(TagNormalization::Kebab);
+    let tags = tags_after_set(&repo, &["Front End", "front   end", "back--end "]).await;
+    assert_eq!(tags, vec!["front-end", "back-end"]);
+}
+
+#[tokio::test]
+async fn default_mode_only_lowercases() {
+    let repo = repo_with(TagNormalization::default());
+    let tags = tags_after_set(&repo, &["Front End"]).await;
+    assert_eq!(tags, vec!["front end"]);
+}
+
+#[tokio::test]
+async fn kebab_search_normalizes_the_query() {
+    let repo = repo_with(TagNormalization::Kebab);
+    tags_after_set(&repo, &["Front End"]).await;
+    assert_eq!(search(&repo, "FRONT END").await, 1);
+    assert_eq!(search(&repo, "front-end").await, 1);
+}
+
+#[test]
+fn tag_normalization_parses_from_config_strings() {
+    assert_eq!(
+        "kebab".parse::<TagNormalization>(),
+        Ok(TagNormalization::Kebab)
+    );
+    assert_eq!(
+        " Lowercase ".parse::<TagNormalization>(),
+        Ok(TagNormalization::Lowercase)
+    );
+    assert!("snake".parse::<TagNormalization>().is_err());
+}
+
+#[tokio::test]
+async fn kebab_rejects_tags_that_normalize_to_nothing() {
+    let repo = repo_with(TagNormalization::Kebab);
+    let t = repo.insert(Task::new_full("a", "b")).unwrap();
+    for bad in ["-", " - ", "--"] {
+        let (code, Json(err)) = set_tags(
+            Path(t.id.to_string()),
+            State(repo.clone()),
+            Query(Default::default()),
+            Json(TagsPayload {
+                tags: vec!["ok".into(), bad.into()],
+            }),
+        )
+        .await;
+        assert_eq!(code, axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(
+            err["error"],
+            format!("tag '{}' is empty after normalization", bad)
+        );
+    }
+    assert!(repo.get(&t.id).unwrap().tags.is_empty());
+
+    // the default mode keeps a lone hyphen as a tag
+    let repo = repo_with(TagNormalization::default());
+    assert_eq!(tags_after_set(&repo, &["-"]).await, vec!["-"]);
+}
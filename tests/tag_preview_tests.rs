@@ -0,0 +1,41 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use rust_api_hub::config::DEFAULT_MAX_TAG_LEN;
+use rust_api_hub::handlers::task_handler::{TagsPayload, preview_tag_normalization};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn payload(tags: &[&str]) -> Json<TagsPayload> {
+    Json(TagsPayload {
+        tags: tags.iter().map(|s| s.to_string()).collect(),
+    })
+}
+
+#[tokio::test]
+async fn mixed_case_duplicates_collapse() {
+    let repo = TaskRepository::new();
+    let (code, Json(v)) =
+        preview_tag_normalization(State(repo), payload(&["Backend", " backend ", "UI"])).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(v["normalized"], serde_json::json!(["backend", "ui"]));
+    assert_eq!(v["rejected"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn empty_and_over_length_tags_are_rejected() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    let long = "x".repeat(DEFAULT_MAX_TAG_LEN + 1);
+    let (code, Json(v)) =
+        preview_tag_normalization(State(repo.clone()), payload(&["ok", "  ", &long])).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(v["normalized"], serde_json::json!(["ok"]));
+    let rejected = v["rejected"].as_array().unwrap();
+    assert_eq!(rejected.len(), 2);
+    assert_eq!(rejected[0]["tag"], "  ");
+    assert_eq!(rejected[1]["tag"], long);
+    assert!(rejected[1]["reason"].as_str().unwrap().contains("too long"));
+    // preview never changes stored tasks
+    assert_eq!(repo.get(&t.id).unwrap(), t);
+}
@@ -0,0 +1,64 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{UnassignTagsPayload, set_tags_many};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn tagged(repo: &TaskRepository, title: &str, tags: &[&str]) -> Task {
+    let mut t = Task::new_full(title, "d");
+    t.tags = tags.iter().map(|s| s.to_string()).collect();
+    repo.insert(t).unwrap()
+}
+
+fn payload(ids: &[&Task], tags: &[&str]) -> UnassignTagsPayload {
+    UnassignTagsPayload {
+        ids: ids.iter().map(|t| t.id.to_string()).collect(),
+        tags: tags.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+#[tokio::test]
+async fn replaces_tags_on_every_listed_task() {
+    let repo = TaskRepository::new();
+    let a = tagged(&repo, "a", &["old", "x"]);
+    let b = tagged(&repo, "b", &["old"]);
+    let c = tagged(&repo, "c", &[]);
+    let untouched = tagged(&repo, "d", &["old"]);
+
+    let (status, Json(resp)) = set_tags_many(
+        State(repo.clone()),
+        Json(payload(&[&a, &b, &c], &["Release", "q3", "release"])),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(resp["updated"], 3);
+    for t in [&a, &b, &c] {
+        let stored = repo.get(&t.id).unwrap();
+        assert_eq!(stored.tags, vec!["release", "q3"]);
+        assert!(stored.updated_at > t.updated_at);
+    }
+    assert_eq!(repo.get(&untouched.id).unwrap().tags, vec!["old"]);
+    assert!(repo.list_by_tag("old").iter().all(|t| t.id == untouched.id));
+}
+
+#[tokio::test]
+async fn invalid_tag_changes_nothing() {
+    let repo = TaskRepository::new();
+    let a = tagged(&repo, "a", &["keep"]);
+    let (status, _) = set_tags_many(State(repo.clone()), Json(payload(&[&a], &["ok", "  "]))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.get(&a.id).unwrap().tags, vec!["keep"]);
+}
+
+#[tokio::test]
+async fn unknown_and_invalid_ids_are_ignored() {
+    let repo = TaskRepository::new();
+    let a = tagged(&repo, "a", &["x"]);
+    let mut p = payload(&[&a], &["y"]);
+    p.ids.push("nope".into());
+    p.ids.push(uuid::Uuid::new_v4().to_string());
+    let (status, Json(resp)) = set_tags_many(State(repo.clone()), Json(p)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(resp["updated"], 1);
+}
@@ -0,0 +1,53 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use rust_api_hub::handlers::task_handler::{TagQuery, get_tasks_by_tag};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn add(repo: &TaskRepository, tags: &[&str]) -> Task {
+    let mut t = Task::new_full("t", "d");
+    t.tags = tags.iter().map(|s| s.to_string()).collect();
+    repo.insert(t).unwrap()
+}
+
+async fn search(repo: &TaskRepository, tag: &str, contains: Option<bool>) -> serde_json::Value {
+    let Json(v) = get_tasks_by_tag(
+        State(repo.clone()),
+        Query(TagQuery {
+            tag: tag.into(),
+            contains,
+        }),
+    )
+    .await;
+    v
+}
+
+#[tokio::test]
+async fn contains_matches_tag_substrings() {
+    let repo = TaskRepository::new();
+    let feature = add(&repo, &["feature", "ui"]);
+    let both = add(&repo, &["feat", "new-feature"]);
+    add(&repo, &["bug"]);
+
+    let v = search(&repo, "FEAT", Some(true)).await;
+    assert_eq!(v["total"], 2);
+    let mut ids: Vec<_> = v["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap().to_string())
+        .collect();
+    let mut expected = vec![feature.id.to_string(), both.id.to_string()];
+    ids.sort();
+    expected.sort();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn exact_match_stays_the_default() {
+    let repo = TaskRepository::new();
+    add(&repo, &["feature"]);
+    assert_eq!(search(&repo, "feat", None).await["total"], 0);
+    assert_eq!(search(&repo, "feat", Some(false)).await["total"], 0);
+    assert_eq!(search(&repo, "feature", None).await["total"], 1);
+}
@@ -0,0 +1,88 @@
+use axum::Json;
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{Request, StatusCode};
+use rust_api_hub::handlers::task_handler::{UnassignTagsPayload, unassign_tags};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt; // oneshot
+
+fn tagged(repo: &TaskRepository, title: &str, tags: &[&str]) -> Task {
+    let mut t = Task::new_full(title, "d");
+    t.tags = tags.iter().map(|s| s.to_string()).collect();
+    repo.insert(t).unwrap()
+}
+
+fn payload(ids: &[&Task], tags: &[&str]) -> UnassignTagsPayload {
+    UnassignTagsPayload {
+        ids: ids.iter().map(|t| t.id.to_string()).collect(),
+        tags: tags.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+#[tokio::test]
+async fn unassign_counts_only_tasks_that_changed() {
+    let repo = TaskRepository::new();
+    let a = tagged(&repo, "a", &["shared", "x"]);
+    let b = tagged(&repo, "b", &["shared"]);
+    let c = tagged(&repo, "c", &["other"]);
+
+    let (code, Json(resp)) = unassign_tags(
+        State(repo.clone()),
+        Json(payload(&[&a, &b, &c], &["  SHARED "])),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["updated"], 2);
+
+    assert_eq!(repo.get(&a.id).unwrap().tags, vec!["x"]);
+    assert!(repo.get(&b.id).unwrap().tags.is_empty());
+    let c_after = repo.get(&c.id).unwrap();
+    assert_eq!(c_after.tags, vec!["other"]);
+    assert_eq!(c_after.updated_at, c.updated_at);
+    assert!(repo.list_by_tag("shared").is_empty());
+}
+
+#[tokio::test]
+async fn unassign_leaves_unlisted_tasks_alone() {
+    let repo = TaskRepository::new();
+    let a = tagged(&repo, "a", &["shared"]);
+    let b = tagged(&repo, "b", &["shared"]);
+
+    let (_, Json(resp)) =
+        unassign_tags(State(repo.clone()), Json(payload(&[&a], &["shared"]))).await;
+    assert_eq!(resp["updated"], 1);
+    assert_eq!(repo.get(&b.id).unwrap().tags, vec!["shared"]);
+}
+
+#[tokio::test]
+async fn unassign_ignores_unknown_and_invalid_ids() {
+    let repo = TaskRepository::new();
+    let a = tagged(&repo, "a", &["shared"]);
+    let mut p = payload(&[&a], &["shared"]);
+    p.ids.push("not-a-uuid".into());
+    p.ids.push(uuid::Uuid::new_v4().to_string());
+
+    let (code, Json(resp)) = unassign_tags(State(repo.clone()), Json(p)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["updated"], 1);
+}
+
+#[tokio::test]
+async fn unassign_route_is_wired() {
+    let repo = TaskRepository::new();
+    let a = tagged(&repo, "a", &["shared"]);
+    let body = serde_json::to_string(&payload(&[&a], &["shared"])).unwrap();
+    let req = Request::post("/tasks/tags/unassign")
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap();
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(v["updated"], 1);
+}
@@ -1,5 +1,6 @@
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
 use rust_api_hub::models::repository::TaskRepository;
 use rust_api_hub::models::task::TaskCreate;
 
@@ -14,9 +15,15 @@ async fn set_and_get_tags_roundtrip() {
     let payload = TaskCreate {
         title: "alpha".into(),
         description: "d".into(),
+        ..Default::default()
     };
-    let (_code, Json(task)) =
-        rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload)).await;
+    let (_code, Json(task)) = rust_api_hub::handlers::task_handler::create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap();
 
     // set tags
     let tags_payload = rust_api_hub::handlers::task_handler::TagsPayload {
@@ -28,6 +35,7 @@ async fn set_and_get_tags_roundtrip() {
     let (code_set, Json(resp_set)) = rust_api_hub::handlers::task_handler::set_tags(
         axum::extract::Path(task.id.to_string()),
         State(repo.clone()),
+        axum::extract::Query(Default::default()),
         Json(tags_payload),
     )
     .await;
@@ -54,10 +62,15 @@ async fn search_by_tag_returns_only_matching() {
         let payload = TaskCreate {
             title: name.to_string(),
             description: "d".into(),
+            ..Default::default()
         };
-        let (_code, Json(task)) =
-            rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload))
-                .await;
+        let (_code, Json(task)) = rust_api_hub::handlers::task_handler::create_task(
+            State(repo.clone()),
+            axum::http::HeaderMap::new(),
+            Json(payload),
+        )
+        .await
+        .unwrap();
         // set tags differently
         let tags: Vec<String> = if *name == "t1" {
             vec!["A", "B"]
@@ -71,14 +84,17 @@ async fn search_by_tag_returns_only_matching() {
         let _ = rust_api_hub::handlers::task_handler::set_tags(
             axum::extract::Path(task.id.to_string()),
             State(repo.clone()),
+            axum::extract::Query(Default::default()),
             Json(tags_payload),
         )
         .await;
     }
 
     // search for tag 'a'
-    let q =
-        axum::extract::Query(rust_api_hub::handlers::task_handler::TagQuery { tag: "a".into() });
+    let q = axum::extract::Query(rust_api_hub::handlers::task_handler::TagQuery {
+        tag: "a".into(),
+        contains: None,
+    });
     let Json(resp) =
         rust_api_hub::handlers::task_handler::get_tasks_by_tag(State(repo.clone()), q).await;
     let items = resp["items"].as_array().unwrap();
@@ -92,9 +108,15 @@ async fn invalid_tags_rejected() {
     let payload = TaskCreate {
         title: "bad-tags".into(),
         description: "d".into(),
+        ..Default::default()
     };
-    let (_code, Json(task)) =
-        rust_api_hub::handlers::task_handler::create_task(State(repo.clone()), Json(payload)).await;
+    let (_code, Json(task)) = rust_api_hub::handlers::task_handler::create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap();
 
     // include empty tag -> should fail
     let tags_payload = rust_api_hub::handlers::task_handler::TagsPayload {
@@ -103,9 +125,45 @@ async fn invalid_tags_rejected() {
     let (code_set, Json(resp_set)) = rust_api_hub::handlers::task_handler::set_tags(
         axum::extract::Path(task.id.to_string()),
         State(repo.clone()),
+        axum::extract::Query(Default::default()),
         Json(tags_payload),
     )
     .await;
     assert_eq!(code_set.as_u16(), 400);
     assert!(resp_set["error"].as_str().unwrap().contains("empty"));
 }
+
+#[tokio::test]
+async fn tag_exists_checks_case_insensitively() {
+    use rust_api_hub::handlers::task_handler::tag_exists;
+    let repo = TaskRepository::new();
+    let mut t = rust_api_hub::models::task::Task::new_full("t", "d");
+    t.tags = vec!["feature".into()];
+    repo.insert(t.clone()).unwrap();
+
+    let (code, Json(resp)) = tag_exists(
+        Path((t.id.to_string(), "Feature".into())),
+        State(repo.clone()),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert!(resp["exists"].as_bool().unwrap());
+
+    let (code, Json(resp)) = tag_exists(
+        Path((t.id.to_string(), "backend".into())),
+        State(repo.clone()),
+    )
+    .await;
+    assert_eq!(code, StatusCode::OK);
+    assert!(!resp["exists"].as_bool().unwrap());
+
+    let (code, _) = tag_exists(
+        Path((uuid::Uuid::new_v4().to_string(), "feature".into())),
+        State(repo.clone()),
+    )
+    .await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+
+    let (code, _) = tag_exists(Path(("nope".into(), "feature".into())), State(repo)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,94 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt;
+
+fn seeded() -> (TaskRepository, Task) {
+    let repo = TaskRepository::new();
+    let mut t = Task::new_full("Write, report", "quarterly");
+    t.tags = vec!["work".into(), "q3".into()];
+    let t = repo.insert(t).unwrap();
+    (repo, t)
+}
+
+async fn send(repo: &TaskRepository, req: Request<Body>) -> (StatusCode, String, String) {
+    let resp = build_router(repo.clone()).oneshot(req).await.unwrap();
+    let status = resp.status();
+    let ct = resp.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    (status, ct, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn assert_single_row(body: &str, t: &Task) {
+    let mut rdr = csv::Reader::from_reader(body.as_bytes());
+    let headers = rdr.headers().unwrap().clone();
+    assert_eq!(
+        headers.iter().collect::<Vec<_>>(),
+        [
+            "id",
+            "title",
+            "description",
+            "completed",
+            "priority",
+            "tags",
+            "created_at",
+            "updated_at"
+        ]
+    );
+    let rows: Vec<_> = rdr.records().map(|r| r.unwrap()).collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(&rows[0][0], t.id.to_string());
+    assert_eq!(&rows[0][1], "Write, report");
+    assert_eq!(&rows[0][5], "work;q3");
+}
+
+#[tokio::test]
+async fn csv_suffix_returns_one_row() {
+    let (repo, t) = seeded();
+    let req = Request::get(format!("/tasks/{}.csv", t.id))
+        .body(Body::empty())
+        .unwrap();
+    let (status, ct, body) = send(&repo, req).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ct, "text/csv");
+    assert_single_row(&body, &t);
+}
+
+#[tokio::test]
+async fn accept_header_selects_csv() {
+    let (repo, t) = seeded();
+    let req = Request::get(format!("/tasks/{}", t.id))
+        .header(header::ACCEPT, "text/csv")
+        .body(Body::empty())
+        .unwrap();
+    let (status, ct, body) = send(&repo, req).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ct, "text/csv");
+    assert_single_row(&body, &t);
+
+    let req = Request::get(format!("/tasks/{}", t.id))
+        .body(Body::empty())
+        .unwrap();
+    let (_, ct, body) = send(&repo, req).await;
+    assert_eq!(ct, "application/json");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(v["task"]["id"], t.id.to_string());
+}
+
+#[tokio::test]
+async fn missing_task_as_csv_is_json_404() {
+    let repo = TaskRepository::new();
+    let req = Request::get(format!("/tasks/{}.csv", uuid::Uuid::new_v4()))
+        .body(Body::empty())
+        .unwrap();
+    let (status, ct, body) = send(&repo, req).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(ct, "application/json");
+    let v: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(v["error"], "not found");
+}
@@ -5,7 +5,7 @@ fn test_create_task_properties() {
     let t = Task::new_full("title1", "desc1");
     assert_eq!(t.title, "title1");
     assert_eq!(t.description, "desc1");
-    assert_eq!(t.completed, false);
+    assert!(!t.completed);
 }
 
 #[test]
@@ -15,6 +15,7 @@ fn test_apply_title_update() {
         title: Some("AA".to_string()),
         description: None,
         completed: None,
+        color: None,
     };
     let new = t.apply_update(upd);
     assert_eq!(new.title, "AA");
@@ -28,6 +29,7 @@ fn test_apply_all_update() {
         title: Some("X".to_string()),
         description: Some("Y".to_string()),
         completed: Some(true),
+        color: None,
     };
     let new = t.apply_update(upd);
     assert_eq!(new.title, "X");
@@ -48,3 +50,40 @@ fn test_uuid_uniqueness() {
     let b = Task::new_full("2", "2");
     assert_ne!(a.id, b.id);
 }
+
+#[test]
+fn test_slug_follows_title() {
+    let mut t = Task::new_full("Fix the Bug!", "d");
+    assert_eq!(t.slug, "fix-the-bug");
+    let upd = TaskUpdate {
+        title: Some("Ship v2 Now".to_string()),
+        description: None,
+        completed: None,
+        color: None,
+    };
+    let new = t.apply_update(upd);
+    assert_eq!(new.slug, "ship-v2-now");
+    assert_eq!(new.to_json()["slug"], "ship-v2-now");
+}
+
+#[test]
+fn test_completed_at_tracks_completion() {
+    let mut t = Task::new_full("c", "d");
+    assert!(t.completed_at.is_none());
+    let done = TaskUpdate {
+        title: None,
+        description: None,
+        completed: Some(true),
+        color: None,
+    };
+    t.apply_update(done);
+    assert!(t.completed_at.is_some());
+    let reopen = TaskUpdate {
+        title: None,
+        description: None,
+        completed: Some(false),
+        color: None,
+    };
+    t.apply_update(reopen);
+    assert!(t.completed_at.is_none());
+}
@@ -0,0 +1,89 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use rust_api_hub::handlers::task_handler::{
+    ListParams, convert_to_template, get_tasks, get_templates, instantiate_template,
+};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, Task};
+
+fn seeded() -> (TaskRepository, Task) {
+    let repo = TaskRepository::new();
+    let mut t = Task::new_full("Weekly report", "collect numbers");
+    t.tags = vec!["ops".into(), "weekly".into()];
+    t.priority = Priority::High;
+    t.completed = true;
+    let t = repo.insert(t).unwrap();
+    repo.insert(Task::new_full("regular", "d")).unwrap();
+    (repo, t)
+}
+
+async fn listed_titles(repo: &TaskRepository) -> Vec<String> {
+    let Json(v) = get_tasks(
+        State(repo.clone()),
+        HeaderMap::new(),
+        Query(ListParams::default()),
+    )
+    .await
+    .unwrap();
+    v["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["title"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn converting_hides_the_task_from_the_default_list() {
+    let (repo, t) = seeded();
+    assert_eq!(listed_titles(&repo).await.len(), 2);
+
+    let (status, Json(v)) = convert_to_template(Path(t.id.to_string()), State(repo.clone())).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["task"]["is_template"], true);
+
+    assert_eq!(listed_titles(&repo).await, vec!["regular"]);
+    let Json(templates) = get_templates(State(repo.clone())).await;
+    let items = templates["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], t.id.to_string());
+}
+
+#[tokio::test]
+async fn instantiating_copies_fields_with_a_fresh_identity() {
+    let (repo, t) = seeded();
+    let _ = convert_to_template(Path(t.id.to_string()), State(repo.clone())).await;
+
+    let (status, Json(task)) = instantiate_template(Path(t.id.to_string()), State(repo.clone()))
+        .await
+        .unwrap();
+    assert_eq!(status, StatusCode::CREATED);
+    assert_ne!(task.id, t.id);
+    assert!(task.created_at > t.created_at);
+    assert!(!task.is_template);
+    assert!(!task.completed);
+    assert_eq!(task.title, t.title);
+    assert_eq!(task.description, t.description);
+    assert_eq!(task.tags, t.tags);
+    assert_eq!(task.priority, Priority::High);
+
+    // the instance is a regular task, the template is untouched
+    let titles = listed_titles(&repo).await;
+    assert!(titles.contains(&"Weekly report".to_string()));
+    assert!(repo.get(&t.id).unwrap().is_template);
+}
+
+#[tokio::test]
+async fn only_templates_can_be_instantiated() {
+    let (repo, t) = seeded();
+    let (status, _) = instantiate_template(Path(t.id.to_string()), State(repo.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = instantiate_template(Path("nope".into()), State(repo.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(repo.count(), 2);
+}
@@ -0,0 +1,59 @@
+use rust_api_hub::utils::text::slugify;
+
+#[test]
+fn slugify_basic_title() {
+    assert_eq!(slugify("Fix the Bug!"), "fix-the-bug");
+}
+
+#[test]
+fn slugify_collapses_and_trims_separators() {
+    assert_eq!(slugify("  --Hello,   World--  "), "hello-world");
+    assert_eq!(slugify("v2.0 release"), "v2-0-release");
+    assert_eq!(slugify("!!!"), "");
+}
+
+#[test]
+fn glob_match_wildcards() {
+    use rust_api_hub::utils::text::glob_match;
+    assert!(glob_match("tmp/*", "tmp/"));
+    assert!(glob_match("tmp/*", "tmp/a/b"));
+    assert!(!glob_match("tmp/*", "xtmp/a"));
+    assert!(glob_match("*-draft", "q1-draft"));
+    assert!(glob_match("v?", "v2"));
+    assert!(!glob_match("v?", "v10"));
+    assert!(glob_match("a*b*c", "aXXbYYc"));
+    assert!(!glob_match("a*b*c", "aXXbYY"));
+    assert!(glob_match("*", ""));
+    assert!(glob_match("exact", "exact"));
+}
+
+#[test]
+fn trigrams_pad_each_word() {
+    use rust_api_hub::utils::text::trigrams;
+    let t = trigrams("Hi");
+    assert_eq!(t.len(), 3);
+    assert!(t.contains("  h") && t.contains(" hi") && t.contains("hi "));
+    assert!(trigrams("  ,; ").is_empty());
+}
+
+#[test]
+fn trigram_similarity_scores_overlap() {
+    use rust_api_hub::utils::text::trigram_similarity;
+    assert_eq!(trigram_similarity("Hello", "hello"), 1.0);
+    assert_eq!(trigram_similarity("abc", "xyz"), 0.0);
+    assert_eq!(trigram_similarity("", ""), 0.0);
+    // "  h", " he", "hel", "lo " shared out of 7 distinct trigrams
+    assert!((trigram_similarity("helo", "hello") - 4.0 / 7.0).abs() < 1e-9);
+}
+
+#[test]
+fn precomputed_query_trigrams_score_the_same() {
+    use rust_api_hub::utils::text::{trigram_similarity, trigram_similarity_to, trigrams};
+    let q = trigrams("helo wrld");
+    for text in ["hello world", "", "helo wrld", "unrelated"] {
+        assert_eq!(
+            trigram_similarity_to(&q, text),
+            trigram_similarity("helo wrld", text)
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, Request, StatusCode};
+use rust_api_hub::handlers::task_handler::{MinutesPayload, get_stats, log_time, set_estimate};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt;
+
+async fn log(
+    repo: &TaskRepository,
+    id: &str,
+    minutes: Option<u32>,
+) -> (StatusCode, serde_json::Value) {
+    let (status, Json(v)) = log_time(
+        Path(id.to_string()),
+        State(repo.clone()),
+        Json(MinutesPayload { minutes }),
+    )
+    .await;
+    (status, v)
+}
+
+async fn estimate(
+    repo: &TaskRepository,
+    id: &str,
+    minutes: Option<u32>,
+) -> (StatusCode, serde_json::Value) {
+    let (status, Json(v)) = set_estimate(
+        Path(id.to_string()),
+        State(repo.clone()),
+        Json(MinutesPayload { minutes }),
+    )
+    .await;
+    (status, v)
+}
+
+#[tokio::test]
+async fn logging_time_accumulates() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    let id = t.id.to_string();
+    for m in [15, 30, 45] {
+        let (status, _) = log(&repo, &id, Some(m)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (_, v) = log(&repo, &id, Some(10)).await;
+    assert_eq!(v["task"]["logged_minutes"], 100);
+    assert_eq!(repo.get(&t.id).unwrap().logged_minutes, 100);
+}
+
+#[tokio::test]
+async fn non_positive_increments_are_rejected() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    let id = t.id.to_string();
+    for minutes in [Some(0), None] {
+        let (status, _) = log(&repo, &id, minutes).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+    let (status, _) = estimate(&repo, &id, Some(0)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let resp = build_router(repo.clone())
+        .oneshot(
+            Request::post(format!("/tasks/{}/log-time", id))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"minutes": -5}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(resp.status().is_client_error());
+    assert_eq!(repo.get(&t.id).unwrap().logged_minutes, 0);
+}
+
+#[tokio::test]
+async fn estimate_can_be_set_and_cleared() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("t", "d")).unwrap();
+    let id = t.id.to_string();
+    let (status, v) = estimate(&repo, &id, Some(120)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["task"]["estimate_minutes"], 120);
+    let (_, v) = estimate(&repo, &id, None).await;
+    assert!(v["task"]["estimate_minutes"].is_null());
+
+    let (status, _) = estimate(&repo, &uuid::Uuid::new_v4().to_string(), Some(5)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn stats_total_estimates_and_logged_time() {
+    let repo = TaskRepository::new();
+    let a = repo.insert(Task::new_full("a", "d")).unwrap();
+    let b = repo.insert(Task::new_full("b", "d")).unwrap();
+    repo.insert(Task::new_full("c", "d")).unwrap();
+
+    let Json(stats) = get_stats(State(repo.clone()), HeaderMap::new()).await;
+    assert_eq!(stats["total_estimated_minutes"], 0);
+    assert_eq!(stats["total_logged_minutes"], 0);
+
+    estimate(&repo, &a.id.to_string(), Some(60)).await;
+    estimate(&repo, &b.id.to_string(), Some(90)).await;
+    log(&repo, &a.id.to_string(), Some(20)).await;
+    log(&repo, &b.id.to_string(), Some(5)).await;
+    log(&repo, &b.id.to_string(), Some(10)).await;
+
+    // the cached stats are recomputed after the writes above
+    let Json(stats) = get_stats(State(repo), HeaderMap::new()).await;
+    assert_eq!(stats["total_estimated_minutes"], 150);
+    assert_eq!(stats["total_logged_minutes"], 35);
+}
@@ -0,0 +1,70 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use rust_api_hub::handlers::task_handler::{ListParams, get_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn repo() -> TaskRepository {
+    let repo = TaskRepository::new();
+    for title in [
+        "Deploy api",
+        "deploy web",
+        "Design review",
+        "Fix deploy script",
+    ] {
+        repo.insert(Task::new_full(title, "d")).unwrap();
+    }
+    repo
+}
+
+async fn list(repo: &TaskRepository, params: ListParams) -> (Vec<String>, u64) {
+    let Json(resp) = get_tasks(State(repo.clone()), HeaderMap::new(), Query(params))
+        .await
+        .unwrap();
+    let mut titles: Vec<String> = resp["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["title"].as_str().unwrap().to_string())
+        .collect();
+    titles.sort();
+    (titles, resp["total"].as_u64().unwrap())
+}
+
+#[tokio::test]
+async fn prefix_matches_subset_case_insensitively() {
+    let repo = repo();
+    let params = ListParams {
+        title_prefix: Some("DEP".into()),
+        ..Default::default()
+    };
+    let (titles, total) = list(&repo, params).await;
+    assert_eq!(titles, vec!["Deploy api", "deploy web"]);
+    assert_eq!(total, 2);
+}
+
+#[tokio::test]
+async fn prefix_total_counts_before_paging() {
+    let repo = repo();
+    let params = ListParams {
+        title_prefix: Some("de".into()),
+        per_page: Some(1),
+        ..Default::default()
+    };
+    let (titles, total) = list(&repo, params).await;
+    assert_eq!(titles.len(), 1);
+    assert_eq!(total, 3);
+}
+
+#[tokio::test]
+async fn empty_prefix_is_a_no_op() {
+    let repo = repo();
+    let params = ListParams {
+        title_prefix: Some(String::new()),
+        ..Default::default()
+    };
+    let (titles, total) = list(&repo, params).await;
+    assert_eq!(titles.len(), 4);
+    assert_eq!(total, 4);
+}
@@ -0,0 +1,90 @@
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, header};
+use rust_api_hub::config::AppConfig;
+use rust_api_hub::handlers::task_handler::{create_task, import_tasks};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::TaskCreate;
+use rust_api_hub::utils::text::{render_title_template, validate_title_template};
+
+fn templated_repo(template: &str) -> TaskRepository {
+    TaskRepository::new().with_config(AppConfig {
+        title_template: Some(template.into()),
+        ..Default::default()
+    })
+}
+
+fn create(title: &str) -> TaskCreate {
+    TaskCreate {
+        title: title.into(),
+        description: "d".into(),
+        ..Default::default()
+    }
+}
+
+fn today() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+#[tokio::test]
+async fn template_is_applied_on_create() {
+    let repo = templated_repo("[{date}] {title}");
+    let (_, Json(task)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(create("ship it")),
+    )
+    .await
+    .unwrap();
+    assert_eq!(task.title, format!("[{}] ship it", today()));
+    assert_eq!(repo.get(&task.id).unwrap().title, task.title);
+}
+
+#[tokio::test]
+async fn template_is_applied_on_import() {
+    let repo = templated_repo("{title} (imported)");
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+    let body = Bytes::from("title,description\na,x\n,missing\n");
+    let (_, Json(resp)) = import_tasks(
+        State(repo.clone()),
+        headers,
+        Query(Default::default()),
+        body,
+    )
+    .await;
+    assert_eq!(resp["imported"], 1);
+    assert_eq!(resp["failed"], 1);
+    assert_eq!(resp["tasks"][0]["title"], "a (imported)");
+}
+
+#[tokio::test]
+async fn unset_template_keeps_titles_verbatim() {
+    let repo = TaskRepository::new();
+    let (_, Json(task)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(create("ship it")),
+    )
+    .await
+    .unwrap();
+    assert_eq!(task.title, "ship it");
+}
+
+#[test]
+fn placeholders_in_the_title_are_not_expanded() {
+    let out = render_title_template("[{date}] {title}", "about {date}", "2024-01-02");
+    assert_eq!(out, "[2024-01-02] about {date}");
+}
+
+#[test]
+fn invalid_templates_are_rejected() {
+    assert!(validate_title_template("[{date}] {title}").is_ok());
+    assert!(validate_title_template("{title}").is_ok());
+    assert!(validate_title_template("{date}").is_err());
+    assert!(validate_title_template("{user} {title}").is_err());
+    assert!(validate_title_template("{title").is_err());
+    assert!(validate_title_template("title}").is_err());
+    assert!(validate_title_template("{{title}}").is_err());
+}
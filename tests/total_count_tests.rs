@@ -0,0 +1,75 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+use rust_api_hub::routes::build_router;
+use tower::ServiceExt; // oneshot
+
+async fn get(repo: &TaskRepository, uri: &str) -> (Response, serde_json::Value) {
+    let resp = build_router(repo.clone())
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (parts, body) = resp.into_parts();
+    let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+    let json = serde_json::from_slice(&bytes).unwrap();
+    (Response::from_parts(parts, Body::empty()), json)
+}
+
+fn total_header(resp: &Response) -> u64 {
+    resp.headers()
+        .get("x-total-count")
+        .expect("x-total-count header")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+fn seeded() -> TaskRepository {
+    let repo = TaskRepository::new();
+    for i in 0..3 {
+        let mut t = Task::new_full(&format!("t{}", i), "d");
+        t.tags = vec!["urgent".into()];
+        repo.insert(t).unwrap();
+    }
+    repo.insert(Task::new_full("plain", "d")).unwrap();
+    repo
+}
+
+#[tokio::test]
+async fn list_header_matches_total() {
+    let repo = seeded();
+    let (resp, body) = get(&repo, "/tasks?per_page=2").await;
+    assert_eq!(body["total"], 4);
+    assert_eq!(total_header(&resp), 4);
+
+    let (resp, body) = get(&repo, "/tasks?completed=true").await;
+    assert_eq!(body["total"], 0);
+    assert!(body["items"].as_array().unwrap().is_empty());
+    assert_eq!(total_header(&resp), 0);
+}
+
+#[tokio::test]
+async fn tag_search_header_matches_total() {
+    let repo = seeded();
+    let (resp, body) = get(&repo, "/tasks/search/by_tag?tag=urgent").await;
+    assert_eq!(body["total"], 3);
+    assert_eq!(total_header(&resp), 3);
+
+    let (resp, body) = get(&repo, "/tasks/search/by_tag?tag=missing").await;
+    assert_eq!(body["total"], 0);
+    assert_eq!(total_header(&resp), 0);
+}
+
+#[tokio::test]
+async fn grouped_header_counts_all_buckets() {
+    let repo = seeded();
+    let (resp, _) = get(&repo, "/tasks/grouped/by_priority").await;
+    assert_eq!(total_header(&resp), 4);
+
+    let (resp, _) = get(&repo, "/tasks/grouped/by_priority?completed=true").await;
+    assert_eq!(total_header(&resp), 0);
+}
@@ -0,0 +1,98 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{
+    FilterUpdate, UpdateByFilterPayload, UpdateFilter, update_by_filter,
+};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{Priority, Status, Task, TaskCreate};
+
+fn add(repo: &TaskRepository, title: &str, priority: Priority) -> Task {
+    repo.insert(Task::from_create(&TaskCreate {
+        title: title.into(),
+        description: "d".into(),
+        priority: Some(priority),
+        ..Default::default()
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn completes_all_high_priority_tasks() {
+    let repo = TaskRepository::new();
+    let a = add(&repo, "a", Priority::High);
+    let b = add(&repo, "b", Priority::High);
+    let low = add(&repo, "c", Priority::Low);
+
+    let payload = UpdateByFilterPayload {
+        filter: UpdateFilter {
+            priority: Some("high".into()),
+            ..Default::default()
+        },
+        update: FilterUpdate {
+            completed: Some(true),
+            ..Default::default()
+        },
+    };
+    let (code, Json(resp)) = update_by_filter(State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["updated"], 2);
+
+    for id in [a.id, b.id] {
+        let t = repo.get(&id).unwrap();
+        assert!(t.completed);
+        assert_eq!(t.status, Status::Done);
+        assert!(t.completed_at.is_some());
+    }
+    assert!(!repo.get(&low.id).unwrap().completed);
+}
+
+#[tokio::test]
+async fn empty_filter_is_rejected() {
+    let repo = TaskRepository::new();
+    let t = add(&repo, "a", Priority::High);
+    let payload = UpdateByFilterPayload {
+        filter: UpdateFilter::default(),
+        update: FilterUpdate {
+            completed: Some(true),
+            ..Default::default()
+        },
+    };
+    let (code, _) = update_by_filter(State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert!(!repo.get(&t.id).unwrap().completed);
+}
+
+#[tokio::test]
+async fn adds_tags_to_tasks_matching_tag_and_status() {
+    let repo = TaskRepository::new();
+    let a = add(&repo, "a", Priority::Low);
+    let b = add(&repo, "b", Priority::Low);
+    repo.modify(&a.id, |t| t.tags = vec!["backend".into()]);
+    repo.modify(&b.id, |t| {
+        t.tags = vec!["backend".into()];
+        t.completed = true;
+    });
+
+    let payload = UpdateByFilterPayload {
+        filter: UpdateFilter {
+            completed: Some(false),
+            tag: Some("Backend".into()),
+            ..Default::default()
+        },
+        update: FilterUpdate {
+            priority: Some("high".into()),
+            add_tags: vec!["urgent".into(), "backend".into()],
+            ..Default::default()
+        },
+    };
+    let (code, Json(resp)) = update_by_filter(State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["updated"], 1);
+
+    let a = repo.get(&a.id).unwrap();
+    assert_eq!(a.priority, Priority::High);
+    assert_eq!(a.tags, vec!["backend", "urgent"]);
+    assert_eq!(repo.get(&b.id).unwrap().tags, vec!["backend"]);
+    assert_eq!(repo.list_by_tag("urgent").len(), 1);
+}
@@ -0,0 +1,64 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_api_hub::handlers::task_handler::{create_task, update_task};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::{
+    MAX_DESCRIPTION_LEN, MAX_TITLE_LEN, Task, TaskCreate, TaskUpdate,
+};
+
+#[tokio::test]
+async fn create_reports_every_violation() {
+    let repo = TaskRepository::new();
+    let payload = TaskCreate {
+        title: "  ".into(),
+        description: "x".repeat(MAX_DESCRIPTION_LEN + 1),
+        ..Default::default()
+    };
+    let (code, Json(resp)) = create_task(
+        State(repo.clone()),
+        axum::http::HeaderMap::new(),
+        Json(payload),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    let errors = resp["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0]["field"], "title");
+    assert_eq!(errors[0]["message"], "title must not be empty");
+    assert_eq!(errors[1]["field"], "description");
+    assert_eq!(repo.count(), 0);
+}
+
+#[tokio::test]
+async fn update_reports_every_violation() {
+    let repo = TaskRepository::new();
+    let t = repo.insert(Task::new_full("ok", "d")).unwrap();
+    let payload = TaskUpdate {
+        title: Some(format!("bad\u{7}{}", "x".repeat(MAX_TITLE_LEN))),
+        description: Some("bell\u{7}".into()),
+        ..Default::default()
+    };
+    let (code, Json(resp)) =
+        update_task(Path(t.id.to_string()), State(repo.clone()), Json(payload)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    let fields: Vec<&str> = resp["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, vec!["title", "title", "description"]);
+    assert_eq!(repo.get(&t.id).unwrap().title, "ok");
+}
+
+#[test]
+fn newlines_and_tabs_are_allowed_in_descriptions() {
+    let c = TaskCreate {
+        title: "t".into(),
+        description: "line 1\n\tline 2\r\n".into(),
+        ..Default::default()
+    };
+    assert!(c.validate().is_ok());
+}
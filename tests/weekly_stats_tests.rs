@@ -0,0 +1,122 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use chrono::{TimeZone, Utc};
+use rust_api_hub::handlers::task_handler::{WeeklyParams, get_weekly_stats};
+use rust_api_hub::models::repository::TaskRepository;
+use rust_api_hub::models::task::Task;
+
+fn task_at(title: &str, y: i32, m: u32, d: u32, completed_on: Option<(i32, u32, u32)>) -> Task {
+    let mut t = Task::new_full(title, "d");
+    t.created_at = Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap();
+    t.updated_at = t.created_at;
+    if let Some((cy, cm, cd)) = completed_on {
+        t.completed = true;
+        t.completed_at = Some(Utc.with_ymd_and_hms(cy, cm, cd, 12, 0, 0).unwrap());
+    }
+    t
+}
+
+#[tokio::test]
+async fn weekly_stats_counts_per_iso_week() {
+    let repo = TaskRepository::new();
+    // 2024-01-15 (Mon) .. 2024-01-21 (Sun) is 2024-W03; 2024-01-22 starts W04
+    repo.insert(task_at("a", 2024, 1, 15, None)).unwrap();
+    repo.insert(task_at("b", 2024, 1, 17, Some((2024, 1, 23))))
+        .unwrap();
+    repo.insert(task_at("c", 2024, 1, 22, Some((2024, 1, 24))))
+        .unwrap();
+    // outside the range
+    repo.insert(task_at("old", 2023, 12, 1, None)).unwrap();
+
+    let params = WeeklyParams {
+        from: Some("2024-01-15".into()),
+        to: Some("2024-01-28".into()),
+    };
+    let (code, Json(resp)) = get_weekly_stats(State(repo), Query(params)).await;
+    assert_eq!(code, StatusCode::OK);
+    let weeks = resp["weeks"].as_array().unwrap();
+    assert_eq!(weeks.len(), 2);
+    assert_eq!(weeks[0]["week"], "2024-W03");
+    assert_eq!(weeks[0]["created"], 2);
+    assert_eq!(weeks[0]["completed"], 0);
+    assert_eq!(weeks[1]["week"], "2024-W04");
+    assert_eq!(weeks[1]["created"], 1);
+    assert_eq!(weeks[1]["completed"], 2);
+}
+
+#[tokio::test]
+async fn weekly_stats_defaults_to_eight_weeks() {
+    let repo = TaskRepository::new();
+    repo.insert(Task::new_full("now", "d")).unwrap();
+    let (code, Json(resp)) = get_weekly_stats(State(repo), Query(WeeklyParams::default())).await;
+    assert_eq!(code, StatusCode::OK);
+    let weeks = resp["weeks"].as_array().unwrap();
+    assert_eq!(weeks.len(), 8);
+    let created: u64 = weeks.iter().map(|w| w["created"].as_u64().unwrap()).sum();
+    assert_eq!(created, 1);
+}
+
+#[tokio::test]
+async fn weekly_stats_rejects_bad_range() {
+    let repo = TaskRepository::new();
+    let params = WeeklyParams {
+        from: Some("2024-02-01".into()),
+        to: Some("2024-01-01".into()),
+    };
+    let (code, _) = get_weekly_stats(State(repo.clone()), Query(params)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    let params = WeeklyParams {
+        from: Some("last week".into()),
+        to: None,
+    };
+    let (code, _) = get_weekly_stats(State(repo), Query(params)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn weekly_stats_extreme_dates_do_not_panic() {
+    let repo = TaskRepository::new();
+    for (from, to) in [
+        (None, Some("-262143-01-01")),
+        (Some("-262143-01-01"), Some("-262143-01-01")),
+        (Some("+262142-12-31"), Some("+262142-12-31")),
+    ] {
+        let params = WeeklyParams {
+            from: from.map(Into::into),
+            to: to.map(Into::into),
+        };
+        let (code, Json(resp)) = get_weekly_stats(State(repo.clone()), Query(params)).await;
+        assert!(
+            code == StatusCode::OK || code == StatusCode::BAD_REQUEST,
+            "{from:?}..{to:?} -> {code}: {resp}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn weekly_stats_rejects_ranges_over_the_cap() {
+    let repo = TaskRepository::new();
+    let params = WeeklyParams {
+        from: Some("0001-01-01".into()),
+        to: Some("+262142-12-31".into()),
+    };
+    let (code, Json(resp)) = get_weekly_stats(State(repo.clone()), Query(params)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+    assert!(resp["error"].as_str().unwrap().contains("range too long"));
+
+    // 2015-01-05 is a Monday; 520 weeks fit exactly, one more day spills into week 521
+    let params = WeeklyParams {
+        from: Some("2015-01-05".into()),
+        to: Some("2024-12-22".into()),
+    };
+    let (code, Json(resp)) = get_weekly_stats(State(repo.clone()), Query(params)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(resp["weeks"].as_array().unwrap().len(), 520);
+    let params = WeeklyParams {
+        from: Some("2015-01-05".into()),
+        to: Some("2024-12-23".into()),
+    };
+    let (code, _) = get_weekly_stats(State(repo), Query(params)).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+}